@@ -63,6 +63,9 @@ mod tests {
             change_address: "".to_string(),
             network: "MAINNET".to_string(),
             seg_wit: "NONE".to_string(),
+            max_fee: 0,
+            max_fee_ratio: 0,
+            allow_absurd_fee: false,
         };
         let coin_info = coin_info_from_param("BITCOINCASH", "MAINNET", "NONE", "").unwrap();
         let tran = BitcoinForkSinger::<BchAddress, LegacyTransactionSignComponent<BchSignHasher>> {
@@ -16,7 +16,9 @@ use core::result;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use tcx_chain::Address;
-use tcx_constants::btc_fork_network::{network_form_hrp, network_from_coin, BtcForkNetwork};
+use tcx_constants::btc_fork_network::{
+    network_form_hrp, network_from_base58_prefix, network_from_coin, BtcForkNetwork,
+};
 use tcx_constants::coin_info::coin_info_from_param;
 use tcx_constants::CoinInfo;
 use tcx_primitive::{Ss58Codec, TypedPrivateKey, TypedPublicKey};
@@ -57,13 +59,21 @@ impl Address for BtcForkAddress {
         Ok(addr.to_string())
     }
 
+    /// Networks share version bytes, 0xc4 is the p2sh prefix of both bitcoin and dogecoin
+    /// testnet, so a base58 address is checked against the prefixes of the coin rather than the
+    /// network `from_str` picks for it
     fn is_valid(address: &str, coin: &CoinInfo) -> bool {
-        let ret = BtcForkAddress::from_str(address);
-        if ret.is_err() {
-            false
-        } else {
-            let addr: BtcForkAddress = ret.unwrap();
-            addr.network.network == coin.network
+        let network = match network_from_coin(coin) {
+            Some(network) => network,
+            None => return false,
+        };
+        if let Ok(data) = decode_base58(address) {
+            return data[0] == network.p2pkh_prefix || data[0] == network.p2sh_prefix;
+        }
+
+        match BtcForkAddress::from_str(address) {
+            Ok(addr) => addr.network.network == coin.network,
+            Err(_) => false,
         }
     }
 }
@@ -257,11 +267,22 @@ impl FromStr for BtcForkAddress {
                     Payload::ScriptHash(ScriptHashType::from_slice(&data[1..]).unwrap()),
                 )
             }
-            x => {
-                return Err(BtcAddressError::Base58(base58::Error::InvalidVersion(
-                    vec![x],
-                )));
-            }
+            // the networks without a segwit variant, dogecoin, dash and the registered ones
+            x => match network_from_base58_prefix(x) {
+                Some(network) if network.p2pkh_prefix == x => (
+                    network,
+                    Payload::PubkeyHash(PubkeyHashType::from_slice(&data[1..]).unwrap()),
+                ),
+                Some(network) => (
+                    network,
+                    Payload::ScriptHash(ScriptHashType::from_slice(&data[1..]).unwrap()),
+                ),
+                None => {
+                    return Err(BtcAddressError::Base58(base58::Error::InvalidVersion(
+                        vec![x],
+                    )));
+                }
+            },
         };
 
         Ok(BtcForkAddress { network, payload })
@@ -287,7 +308,7 @@ impl Display for BtcForkAddress {
                 version: ver,
                 program: ref prog,
             } => {
-                let hrp = self.network.hrp;
+                let hrp = &self.network.hrp;
                 let mut bech32_writer = bech32::Bech32Writer::new(hrp, fmt)?;
                 bech32::WriteBase32::write_u5(&mut bech32_writer, ver)?;
                 bech32::ToBase32::write_base32(&prog, &mut bech32_writer)
@@ -328,8 +349,12 @@ mod tests {
     use std::str::FromStr;
     use tcx_constants::btc_fork_network::network_from_param;
 
+    use crate::WifDisplay;
     use tcx_constants::{CoinInfo, CurveType};
-    use tcx_primitive::{Bip32DeterministicPrivateKey, Derive, DeterministicPrivateKey, Ss58Codec};
+    use tcx_primitive::{
+        Bip32DeterministicPrivateKey, Derive, DeterministicPrivateKey, Secp256k1PrivateKey,
+        Ss58Codec, TypedPrivateKey, TypedPublicKey,
+    };
 
     #[test]
     pub fn test_btc_fork_address() {
@@ -559,4 +584,83 @@ mod tests {
         let coin = coin_info_from_param("LITECOIN", "MAINNET", "P2WPKH", "").unwrap();
         assert!(!BtcForkAddress::is_valid("aaa", &coin));
     }
+
+    #[test]
+    pub fn test_dogecoin_and_dash_address() {
+        let pub_key =
+            hex::decode("02506bc1dc099358e5137292f4efdd57e400f29ba5132aa5d12b18dac1c1f6aaba")
+                .unwrap();
+        let sk =
+            Secp256k1PrivateKey::from_wif("L2hfzPyVC1jWH7n2QLTe7tVTb6btg9smp5UVzhEBxLYaSFF7sCZB")
+                .unwrap();
+        let typed_private_key = TypedPrivateKey::Secp256k1(sk);
+        let cases = vec![
+            (
+                "DOGECOIN",
+                "MAINNET",
+                "DSBWjKzZtz7fPzu4N6mBRwQFHCQ6KQSjue",
+                "QU6b9EnUSdDdWeB4zcJS17L548dTii1ahLAQnKd2gh9vtBDyN44D",
+            ),
+            (
+                "DOGECOIN",
+                "TESTNET",
+                "nqEaTLjUpxaPGyUFPvQdgLzYX4nPLCD1Py",
+                "ckDvbdh8yPG3PbGptu4Z5ZBViCJc1SWvCzsEE2NtSQi1FembAgkd",
+            ),
+            (
+                "DASH",
+                "MAINNET",
+                "XwjG2KhpZHRy1wK3VQ5qjhvSEQFV2RV9Xq",
+                "XGmbSfMrVhMxLSnQS6TWd7gUW7sU7xV2BepQXDZPGhpfqQPCp9Y1",
+            ),
+            (
+                "DASH",
+                "TESTNET",
+                "yhMs3GnFzq63MgEb4FQEmjLnWgjrbZ4YVd",
+                "cT4fTJyLd5RmSZFHnkGmVCzXDKuJLbyTt7cy77ghTTCagzNdPH1j",
+            ),
+        ];
+        for (coin, network, address, wif) in cases {
+            let coin_info = coin_info_from_param(coin, network, "", "").unwrap();
+            assert_eq!(coin_info.seg_wit, "NONE");
+            let typed_public_key =
+                TypedPublicKey::from_slice(CurveType::SECP256k1, &pub_key).unwrap();
+            assert_eq!(
+                BtcForkAddress::from_public_key(&typed_public_key, &coin_info).unwrap(),
+                address
+            );
+            assert_eq!(typed_private_key.fmt(&coin_info).unwrap(), wif);
+
+            let addr = BtcForkAddress::from_str(address).unwrap();
+            assert_eq!(addr.network.coin, coin);
+            assert_eq!(addr.network.network, network);
+            assert_eq!(addr.to_string(), address);
+            assert!(BtcForkAddress::is_valid(address, &coin_info));
+        }
+
+        let addr = BtcForkAddress::from_str("ADUgrTcGDYQerXnNAk6dYwDxFALYdRsyQX").unwrap();
+        assert_eq!(addr.network.coin, "DOGECOIN");
+        assert_eq!(addr.script_pubkey().is_p2sh(), true);
+        let addr = BtcForkAddress::from_str("7oT4wopXxTdPwvwr2E6ieBbEU8ntMDAywm").unwrap();
+        assert_eq!(addr.network.coin, "DASH");
+        assert_eq!(addr.script_pubkey().is_p2sh(), true);
+
+        // dogecoin testnet shares the p2sh prefix 0xc4 with bitcoin testnet
+        let addr = BtcForkAddress::from_str("2NARMf1Wb3rhiYhGBwYuCgKEDi4zmojTsvk").unwrap();
+        assert_eq!(addr.script_pubkey().is_p2sh(), true);
+        let coin_info = coin_info_from_param("DOGECOIN", "TESTNET", "", "").unwrap();
+        assert!(BtcForkAddress::is_valid(
+            "2NARMf1Wb3rhiYhGBwYuCgKEDi4zmojTsvk",
+            &coin_info
+        ));
+        assert!(!BtcForkAddress::is_valid(
+            "yhMs3GnFzq63MgEb4FQEmjLnWgjrbZ4YVd",
+            &coin_info
+        ));
+        let coin_info = coin_info_from_param("DASH", "TESTNET", "", "").unwrap();
+        assert!(!BtcForkAddress::is_valid(
+            "2NARMf1Wb3rhiYhGBwYuCgKEDi4zmojTsvk",
+            &coin_info
+        ));
+    }
 }
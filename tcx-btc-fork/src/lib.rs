@@ -32,6 +32,8 @@ pub enum Error {
     UnsupportedChain,
     #[fail(display = "missing_network")]
     MissingNetwork,
+    #[fail(display = "absurd_fee")]
+    AbsurdFee,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
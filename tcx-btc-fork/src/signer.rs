@@ -11,19 +11,21 @@ use std::str::FromStr;
 
 use crate::address::BtcForkAddress;
 use crate::transaction::{BtcForkSignedTxOutput, BtcForkTxInput, Utxo};
+use crate::Error;
 use bitcoin::util::bip143::SighashComponents;
 use bitcoin_hashes::hash160;
 use bitcoin_hashes::hex::FromHex as HashFromHex;
 use bitcoin_hashes::hex::ToHex as HashToHex;
 use std::marker::PhantomData;
 use tcx_chain::Address;
-use tcx_constants::CoinInfo;
+use tcx_constants::{network_from_coin, BtcForkNetwork, CoinInfo};
 use tcx_primitive::{
-    Bip32DeterministicPublicKey, Derive, DeterministicPublicKey, FromHex, PrivateKey, PublicKey,
-    TypedDeterministicPublicKey,
+    get_account_path, Bip32DeterministicPublicKey, Derive, DeterministicPublicKey, FromHex,
+    PrivateKey, PublicKey,
 };
 
-const DUST: u64 = 546;
+// the fee may be up to a quarter of the amount sent unless the input sets another ratio
+const DEFAULT_MAX_FEE_RATIO: u32 = 25;
 const SIGHASH_ALL: u8 = 0x01;
 
 pub trait ScriptPubKeyComponent {
@@ -48,12 +50,23 @@ impl<S: ScriptPubKeyComponent + Address, T: BitcoinTransactionSignComponent>
         address: &str,
         tx: &BitcoinForkSinger<S, T>,
     ) -> Result<BtcForkSignedTxOutput> {
-        let change_address = if self.determinable() {
-            let dpk = self.find_deterministic_public_key(symbol, address)?;
-            tx.change_address(&dpk)?
+        let (change_address, change_address_path) = if !tx.tx_input.change_address.is_empty() {
+            (tx.tx_input.change_address.to_string(), "".to_string())
+        } else if self.determinable() {
+            let relative_path = format!("1/{}", tx.tx_input.change_address_index);
+            let pub_key = self.derive_public_key(symbol, address, &relative_path)?;
+            let account = self
+                .account(symbol, address)
+                .ok_or_else(|| format_err!("account_not_found"))?;
+            let account_path = get_account_path(&account.derivation_path)?;
+            (
+                S::from_public_key(&pub_key, &tx.coin_info)?,
+                format!("{}/{}", account_path, relative_path),
+            )
         } else {
-            S::address_script_pub_key(&address)?
+            (address.to_string(), "".to_string())
         };
+        let change_script = S::address_script_pub_key(&change_address)?;
 
         let mut sks = vec![];
 
@@ -73,7 +86,12 @@ impl<S: ScriptPubKeyComponent + Address, T: BitcoinTransactionSignComponent>
             }
         }
 
-        tx.sign_transaction(&sks, change_address)
+        let mut output = tx.sign_transaction(&sks, change_script)?;
+        if tx.change_amount() >= tx.dust_threshold()? {
+            output.change_address = change_address;
+            output.change_address_path = change_address_path;
+        }
+        Ok(output)
     }
 }
 
@@ -93,16 +111,17 @@ impl<S: ScriptPubKeyComponent + Address, T: BitcoinTransactionSignComponent>
         S::address_script_pub_key(&self.tx_input.to)
     }
 
-    fn change_address(&self, dpk: &TypedDeterministicPublicKey) -> Result<Script> {
-        if !self.tx_input.change_address.is_empty() {
-            S::address_script_pub_key(&self.tx_input.change_address)
-        } else {
-            let from = &self.tx_input.unspents.first().expect("first_utxo").address;
-            // todo: address is error
-            let _change_path = format!("1/{}", &self.tx_input.change_address_index);
-            let pub_key = dpk.public_key().as_secp256k1()?.0;
-            S::address_script_like(&from, &pub_key)
-        }
+    fn network(&self) -> Result<BtcForkNetwork> {
+        network_from_coin(&self.coin_info).ok_or_else(|| Error::MissingNetwork.into())
+    }
+
+    fn dust_threshold(&self) -> Result<i64> {
+        Ok(self.network()?.dust_threshold as i64)
+    }
+
+    fn change_amount(&self) -> i64 {
+        let total_amount: i64 = self.tx_input.unspents.iter().map(|x| x.amount).sum();
+        total_amount - self.tx_input.amount - self.tx_input.fee
     }
 
     pub fn derive_pub_key_at_path(xpub: &str, child_path: &str) -> Result<bitcoin::PublicKey> {
@@ -113,22 +132,41 @@ impl<S: ScriptPubKeyComponent + Address, T: BitcoinTransactionSignComponent>
         Ok(index_ext_pub_key.public_key().0)
     }
 
+    /// A fee above `max_fee` or above `max_fee_ratio` percent of the amount is most likely in the
+    /// wrong unit, it's refused unless `allow_absurd_fee` is set. `max_fee` falls back to the
+    /// cap of the network
+    fn ensure_fee_sane(&self) -> Result<()> {
+        let input = &self.tx_input;
+        if input.allow_absurd_fee {
+            return Ok(());
+        }
+        let max_fee = if input.max_fee == 0 {
+            i128::from(self.network()?.max_fee)
+        } else {
+            i128::from(input.max_fee)
+        };
+        let max_fee_ratio = if input.max_fee_ratio == 0 {
+            DEFAULT_MAX_FEE_RATIO
+        } else {
+            input.max_fee_ratio
+        };
+        tcx_ensure!(
+            i128::from(input.fee) * 100 <= i128::from(input.amount) * i128::from(max_fee_ratio)
+                && i128::from(input.fee) <= max_fee,
+            Error::AbsurdFee
+        );
+        Ok(())
+    }
+
     fn tx_outs(&self, change_script_pubkey: Script) -> Result<Vec<TxOut>> {
-        let mut total_amount = 0;
+        let dust = self.dust_threshold()?;
 
-        for unspent in &self.tx_input.unspents {
-            total_amount += unspent.amount;
-        }
+        ensure!(self.tx_input.amount >= dust, "amount_less_than_minimum");
 
-        ensure!(
-            self.tx_input.amount >= DUST as i64,
-            "amount_less_than_minimum"
-        );
+        self.ensure_fee_sane()?;
 
-        ensure!(
-            total_amount >= (self.tx_input.amount + self.tx_input.fee),
-            "total amount must ge amount + fee"
-        );
+        let change_amount = self.change_amount();
+        ensure!(change_amount >= 0, "total amount must ge amount + fee");
 
         let mut tx_outs: Vec<TxOut> = vec![];
 
@@ -138,9 +176,8 @@ impl<S: ScriptPubKeyComponent + Address, T: BitcoinTransactionSignComponent>
             script_pubkey: receive_script_pubkey,
         };
         tx_outs.push(receiver_tx_out);
-        let change_amount = total_amount - self.tx_input.amount - self.tx_input.fee;
 
-        if change_amount >= DUST as i64 {
+        if change_amount >= dust {
             let change_tx_out = TxOut {
                 value: change_amount as u64,
                 script_pubkey: change_script_pubkey,
@@ -187,6 +224,8 @@ impl<S: ScriptPubKeyComponent + Address, T: BitcoinTransactionSignComponent>
         Ok(BtcForkSignedTxOutput {
             signature: tx_bytes.to_hex(),
             tx_hash: signed_tx.txid().into_inner().to_hex(),
+            change_address: "".to_string(),
+            change_address_path: "".to_string(),
         })
     }
 }
@@ -373,8 +412,9 @@ mod tests {
     use super::*;
 
     use super::BitcoinForkSinger;
-    use tcx_chain::{Keystore, TransactionSigner};
+    use tcx_chain::{Keystore, Metadata, TransactionSigner};
     use tcx_constants::coin_info::coin_info_from_param;
+    use tcx_constants::{TEST_MNEMONIC, TEST_PASSWORD};
     use tcx_primitive::Secp256k1PrivateKey;
 
     #[test]
@@ -397,6 +437,9 @@ mod tests {
             change_address: "".to_string(),
             network: "TESTNET".to_string(),
             seg_wit: "NONE".to_string(),
+            max_fee: 0,
+            max_fee_ratio: 0,
+            allow_absurd_fee: false,
         };
         let coin_info = coin_info_from_param("LITECOIN", "TESTNET", "NONE", "").unwrap();
         let tran =
@@ -440,6 +483,9 @@ mod tests {
             change_address: "mkeNU5nVnozJiaACDELLCsVUc8Wxoh1rQN".to_string(),
             network: "TESTNET".to_string(),
             seg_wit: "NONE".to_string(),
+            max_fee: 0,
+            max_fee_ratio: 0,
+            allow_absurd_fee: false,
         };
         let coin_info = coin_info_from_param("LITECOIN", "TESTNET", "NONE", "").unwrap();
         let tran =
@@ -498,6 +544,9 @@ mod tests {
             change_address: "".to_string(),
             network: "TESTNET".to_string(),
             seg_wit: "NONE".to_string(),
+            max_fee: 0,
+            max_fee_ratio: 0,
+            allow_absurd_fee: false,
         };
         let coin_info = coin_info_from_param("LITECOIN", "TESTNET", "NONE", "").unwrap();
         let tran =
@@ -515,9 +564,59 @@ mod tests {
             .unwrap();
         assert_eq!(
             expected.tx_hash,
-            "1d95b4e3cac9b81fb9459799cb6c7ffc38b5b78ee38fdcfdb972dc17f77db761"
+            "96fe3a5ff0e01e533f19642a0bd05ed4925dfdc271124bc08c3aa4a8bdb9d5c8"
+        );
+        assert_eq!(expected.signature, "010000000201a1d4a6b051e732341c0101a62488051bb5bf969d1f15324bbf6a1d2035c957000000006b483045022100a49798664e490075f9d111c6b6e8541781a5a88df1b95eb910dd307298ead4e802203adb4a21f2e680e1d05f6346ec25b1077f60e58c6289606cc9dad15698b5368d0121033d710ab45bb54ac99618ad23b3c1da661631aa25f23bfe9d22b41876f1d46e4effffffff00a1d4a6b051e732341c0101a62488051bb5bf969d1f15324bbf6a1d2035c957000000006a473044022100c7e2dba307022d45067e7b3eceb2b288f49037f43c8bac271ccc831f250b9438021f14103613f41f6d6811f70359077ae96dc2055fcb9dd5aff21469e1fb51a9870121033d710ab45bb54ac99618ad23b3c1da661631aa25f23bfe9d22b41876f1d46e4effffffff02e0c81000000000001976a914461bf9360ec1bc9fe438df19ef36c7c2bb26ef8288ac92a40d00000000001976a9143770c8c6671d27e2a9f4502d74932bf740c1ff8688ac00000000");
+        assert_eq!(
+            expected.change_address,
+            "mka6ZzaxDdD49uzxMrtz9H8NVxCEoVMHu3"
         );
-        assert_eq!(expected.signature, "010000000201a1d4a6b051e732341c0101a62488051bb5bf969d1f15324bbf6a1d2035c957000000006a47304402202b007c693e784805eaae1e68a35d8d4a15331a29aa19a27fecd5a530cd6bba95022000dfa238c90a5a48c66817e0a0a69e275d159e566c1eb661b6c362b249f8aa510121033d710ab45bb54ac99618ad23b3c1da661631aa25f23bfe9d22b41876f1d46e4effffffff00a1d4a6b051e732341c0101a62488051bb5bf969d1f15324bbf6a1d2035c957000000006a4730440220320b151cab9701c0e031310e4c7e6ff4712b291b79bee2a7f20bd2acd40a5a93022030fc72cf9f2f66aa5d88d97ef0e3c537dfa11f9d1ef6cac7bd68e45d3cb410700121033d710ab45bb54ac99618ad23b3c1da661631aa25f23bfe9d22b41876f1d46e4effffffff02e0c81000000000001976a914461bf9360ec1bc9fe438df19ef36c7c2bb26ef8288ac92a40d00000000001976a9145ec105a23edc97d73a8de1e49d498684c40aa84988ac00000000");
+        assert_eq!(expected.change_address_path, "m/44'/1'/0'/1/1");
+    }
+
+    #[test]
+    fn test_sign_ltc_spend_change() {
+        let keystore_json = r#"
+        {"id":"ae45d424-31d8-49f7-a601-1272b40c566d","version":11000,"keyHash":"512115eca3ae86646aeb06861d551e403b543509","crypto":{"cipher":"aes-128-ctr","cipherparams":{"iv":"588233984e9576f058bd7bae018eaa38"},"ciphertext":"8a5451c57fed478c7d45f5391659a6fb5fc85a347f1f7aaead450ad4ef4fe434d042d57aa990d850165293609aa746c715c805b236c3d54d86e7dea7d938ce55fcb2684e0eb7e0e6cc7d","kdf":"pbkdf2","kdfparams":{"c":1024,"prf":"hmac-sha256","dklen":32,"salt":"ee656af962155e4e6e763b0883ed0d8cc37c2fa21a7ef01b1d3b18f352f74c69"},"mac":"a661aa444869aac9ea33f066676c6bfb49d079ab986d0ee755f8a1747b2b7f17"},"activeAccounts":[{"address":"mkeNU5nVnozJiaACDELLCsVUc8Wxoh1rQN","derivationPath":"m/44'/1'/0'/0/0","curve":"SECP256k1","coin":"LITECOIN","network":"TESTNET","segWit":"NONE","extPubKey":"036c2b38ad8000000023332f38a77023d3c1a450499c8aeb3db2e666aa2cc6fff7db6797c5d2aef8fc036663443d71127b332c68cd6bffb6c2b5eb4dc6861404ed055dc36a25b8c18020"}],"imTokenMeta":{"name":"LTC-Wallet-1","passwordHint":"","timestamp":1576561805,"source":"MNEMONIC"}}
+        "#;
+        // the change output of test_sign_ltc_multi_utxo
+        let unspents = vec![Utxo {
+            tx_hash: "c8d5b9bda8a43a8cc04b1271c2fd5d92d45ed00b2a64193f531ee0f05f3afe96".to_string(),
+            vout: 1,
+            amount: 894098,
+            address: "mka6ZzaxDdD49uzxMrtz9H8NVxCEoVMHu3".to_string(),
+            script_pub_key: "76a9143770c8c6671d27e2a9f4502d74932bf740c1ff8688ac".to_string(),
+            derived_path: "1/1".to_string(),
+            sequence: 0,
+        }];
+        let tx_input = BtcForkTxInput {
+            to: "mmuf77YiGckWgfvd32viaj7EKfrUN1FdAz".to_string(),
+            amount: 500000,
+            unspents,
+            fee: 5902,
+            change_address_index: 2u32,
+            change_address: "".to_string(),
+            network: "TESTNET".to_string(),
+            seg_wit: "NONE".to_string(),
+            max_fee: 0,
+            max_fee_ratio: 0,
+            allow_absurd_fee: false,
+        };
+        let coin_info = coin_info_from_param("LITECOIN", "TESTNET", "NONE", "").unwrap();
+        let tran = BtcForkTransaction::new(tx_input, coin_info);
+
+        let mut keystore = Keystore::from_json(keystore_json).unwrap();
+        let _ = keystore.unlock_by_password("imtoken1");
+        let actual = keystore
+            .sign_transaction("LITECOIN", "mkeNU5nVnozJiaACDELLCsVUc8Wxoh1rQN", &tran)
+            .unwrap();
+        assert_eq!(
+            actual.tx_hash,
+            "dfa732a0840c4f9fa509253842c0fa1db5d90a433fb8ce418b8959f4e0842774"
+        );
+        assert_eq!(actual.signature, "010000000196fe3a5ff0e01e533f19642a0bd05ed4925dfdc271124bc08c3aa4a8bdb9d5c8010000006a47304402205ea72018cb1e4e12baf30cbe099524907425053a66f1c8ecfe66e904ee0b51c7022053972e0111e553048fcd73a5f9a6a64253250b0d3dd8b659c5a59c9c889189b0012102397b033dcc33ccd53c13697cc2dd7785cc526c217327f1bb66b08aea171b069dffffffff0220a10700000000001976a914461bf9360ec1bc9fe438df19ef36c7c2bb26ef8288ac64ec0500000000001976a9144cbdedfd376b834eae69decc49c6c877aaaaff1188ac00000000");
+        assert_eq!(actual.change_address, "mnWjBBB4MxY8j4HNynvZP3Z8TSDXw3inPT");
+        assert_eq!(actual.change_address_path, "m/44'/1'/0'/1/2");
     }
 
     #[test]
@@ -543,6 +642,9 @@ mod tests {
             change_address: "mkeNU5nVnozJiaACDELLCsVUc8Wxoh1rQN".to_string(),
             network: "TESTNET".to_string(),
             seg_wit: "NONE".to_string(),
+            max_fee: 0,
+            max_fee_ratio: 0,
+            allow_absurd_fee: false,
         };
         let coin_info = coin_info_from_param("LITECOIN", "TESTNET", "NONE", "").unwrap();
         let tran =
@@ -588,6 +690,9 @@ mod tests {
             change_address: "mkeNU5nVnozJiaACDELLCsVUc8Wxoh1rQN".to_string(),
             network: "TESTNET".to_string(),
             seg_wit: "NONE".to_string(),
+            max_fee: 0,
+            max_fee_ratio: 0,
+            allow_absurd_fee: false,
         };
         let coin_info = coin_info_from_param("LITECOIN", "TESTNET", "NONE", "").unwrap();
         let tran =
@@ -632,6 +737,9 @@ mod tests {
             change_address: "mkeNU5nVnozJiaACDELLCsVUc8Wxoh1rQN".to_string(),
             network: "TESTNET".to_string(),
             seg_wit: "NONE".to_string(),
+            max_fee: 0,
+            max_fee_ratio: 0,
+            allow_absurd_fee: false,
         };
         let coin_info = coin_info_from_param("LITECOIN", "TESTNET", "NONE", "").unwrap();
         let tran =
@@ -671,6 +779,9 @@ mod tests {
             change_address: "".to_string(),
             network: "TESTNET".to_string(),
             seg_wit: "NONE".to_string(),
+            max_fee: 0,
+            max_fee_ratio: 0,
+            allow_absurd_fee: false,
         };
         let tran =
             BitcoinForkSinger::<BtcForkAddress, LegacyTransactionSignComponent<LegacySignHasher>> {
@@ -714,6 +825,9 @@ mod tests {
             change_address: "".to_string(),
             network: "TESTNET".to_string(),
             seg_wit: "NONE".to_string(),
+            max_fee: 0,
+            max_fee_ratio: 0,
+            allow_absurd_fee: false,
         };
         let tran =
             BitcoinForkSinger::<BtcForkAddress, LegacyTransactionSignComponent<LegacySignHasher>> {
@@ -759,6 +873,9 @@ mod tests {
                 change_address: "".to_string(),
                 network: "TESTNET".to_string(),
                 seg_wit: "NONE".to_string(),
+                max_fee: 0,
+                max_fee_ratio: 0,
+                allow_absurd_fee: false,
             };
             let tran = BitcoinForkSinger::<
                 BtcForkAddress,
@@ -801,6 +918,9 @@ mod tests {
             change_address: "mszYqVnqKoQx4jcTdJXxwKAissE3Jbrrc1".to_string(),
             network: "TESTNET".to_string(),
             seg_wit: "NONE".to_string(),
+            max_fee: 0,
+            max_fee_ratio: 0,
+            allow_absurd_fee: false,
         };
         let coin_info = coin_info_from_param("LITECOIN", "TESTNET", "NONE", "").unwrap();
         let tran =
@@ -821,6 +941,157 @@ mod tests {
         assert_eq!(actual.signature, "01000000015884e5db9de218238671572340b207ee85b628074e7e467096c267266baf77a4000000006b483045022100eefdd6cace70ee64d6a29bca5f52c338b2b3ecf6e6c7b222818c9bba60f094fb022053535e23a77afc7255c18ae8c6e6bf0f8b6e3f552d08519455714cbe59e489cf01210223078d2942df62c45621d209fab84ea9a7a23346201b7727b9b45a29c4e76f5effffffff0220a10700000000001976a9147821c0a3768aa9d1a37e16cf76002aef5373f1a888ac801a0600000000001976a91488d9931ea73d60eaf7e5671efc0552b912911f2a88ac00000000");
     }
 
+    #[test]
+    fn test_sign_dash() {
+        // the scripts of test_sign_ltc_change_address, a legacy signature doesn't commit to the chain
+        let unspents = vec![Utxo {
+            tx_hash: "a477af6b2667c29670467e4e0728b685ee07b240235771862318e29ddbe58458".to_string(),
+            vout: 0,
+            amount: 1000000,
+            address: "yYo3PeSBv2rMnJeyLUCCzx4Y8VhPppZKkC".to_string(),
+            script_pub_key: "76a91488d9931ea73d60eaf7e5671efc0552b912911f2a88ac".to_string(),
+            derived_path: "0/0".to_string(),
+            sequence: 0,
+        }];
+        let tx_input = BtcForkTxInput {
+            to: "yXGeNPQXYFXhLAN1ZKrAjxzzBnZ2JZNKnh".to_string(),
+            amount: 500000,
+            unspents,
+            fee: 100000,
+            change_address_index: 0,
+            change_address: "yYo3PeSBv2rMnJeyLUCCzx4Y8VhPppZKkC".to_string(),
+            network: "TESTNET".to_string(),
+            seg_wit: "NONE".to_string(),
+            max_fee: 0,
+            max_fee_ratio: 0,
+            allow_absurd_fee: false,
+        };
+        let coin_info = coin_info_from_param("DASH", "TESTNET", "NONE", "").unwrap();
+        let tran = BtcForkTransaction::new(tx_input, coin_info);
+
+        let prv_key =
+            Secp256k1PrivateKey::from_wif("cSBnVM4xvxarwGQuAfQFwqDg9k5tErHUHzgWsEfD4zdwUasvqRVY")
+                .unwrap();
+        let change_addr = BtcForkAddress::from_str("yYo3PeSBv2rMnJeyLUCCzx4Y8VhPppZKkC").unwrap();
+        let actual = tran
+            .sign_transaction(&vec![prv_key], change_addr.script_pubkey())
+            .unwrap();
+        assert_eq!(actual.signature, "01000000015884e5db9de218238671572340b207ee85b628074e7e467096c267266baf77a4000000006b483045022100eefdd6cace70ee64d6a29bca5f52c338b2b3ecf6e6c7b222818c9bba60f094fb022053535e23a77afc7255c18ae8c6e6bf0f8b6e3f552d08519455714cbe59e489cf01210223078d2942df62c45621d209fab84ea9a7a23346201b7727b9b45a29c4e76f5effffffff0220a10700000000001976a9147821c0a3768aa9d1a37e16cf76002aef5373f1a888ac801a0600000000001976a91488d9931ea73d60eaf7e5671efc0552b912911f2a88ac00000000");
+    }
+
+    #[test]
+    fn test_sign_dogecoin_dust() {
+        let sign = |amount: i64, fee: i64| {
+            let unspents = vec![Utxo {
+                tx_hash: "a477af6b2667c29670467e4e0728b685ee07b240235771862318e29ddbe58458"
+                    .to_string(),
+                vout: 0,
+                amount: 3000000,
+                address: "ngfkoiPQkALhhbtdg9CbuZiJ8sjvfqCg8s".to_string(),
+                script_pub_key: "76a91488d9931ea73d60eaf7e5671efc0552b912911f2a88ac".to_string(),
+                derived_path: "0/0".to_string(),
+                sequence: 0,
+            }];
+            let tx_input = BtcForkTxInput {
+                to: "nf9MnTMkNP23FTbftzrZeaekCAbZDERAVN".to_string(),
+                amount,
+                unspents,
+                fee,
+                change_address_index: 0,
+                change_address: "ngfkoiPQkALhhbtdg9CbuZiJ8sjvfqCg8s".to_string(),
+                network: "TESTNET".to_string(),
+                seg_wit: "NONE".to_string(),
+                max_fee: 0,
+                // the fees are as large as the amounts here
+                max_fee_ratio: 100,
+                allow_absurd_fee: false,
+            };
+            let coin_info = coin_info_from_param("DOGECOIN", "TESTNET", "NONE", "").unwrap();
+            let tran = BtcForkTransaction::new(tx_input, coin_info);
+            let prv_key = Secp256k1PrivateKey::from_wif(
+                "cSBnVM4xvxarwGQuAfQFwqDg9k5tErHUHzgWsEfD4zdwUasvqRVY",
+            )
+            .unwrap();
+            let change_addr =
+                BtcForkAddress::from_str("ngfkoiPQkALhhbtdg9CbuZiJ8sjvfqCg8s").unwrap();
+            tran.sign_transaction(&vec![prv_key], change_addr.script_pubkey())
+        };
+        let outputs = |signed: BtcForkSignedTxOutput| {
+            let tx: Transaction =
+                bitcoin::consensus::deserialize(&hex::decode(signed.signature).unwrap()).unwrap();
+            tx.output.iter().map(|x| x.value).collect::<Vec<u64>>()
+        };
+
+        // 0.01 DOGE is the dogecoin dust, far above the bitcoin one
+        let err = sign(999999, 100000).err().unwrap();
+        assert_eq!(format!("{}", err), "amount_less_than_minimum");
+        assert_eq!(
+            outputs(sign(1000000, 1000000).unwrap()),
+            vec![1000000, 1000000]
+        );
+        // the change below the dust goes to the fee
+        assert_eq!(outputs(sign(1500000, 1000000).unwrap()), vec![1500000]);
+    }
+
+    #[test]
+    fn test_sign_absurd_fee() {
+        let sign =
+            |amount: i64, fee: i64, max_fee: i64, max_fee_ratio: u32, allow_absurd_fee: bool| {
+                let unspents = vec![Utxo {
+                    tx_hash: "a477af6b2667c29670467e4e0728b685ee07b240235771862318e29ddbe58458"
+                        .to_string(),
+                    vout: 0,
+                    amount: 1000000000,
+                    address: "mszYqVnqKoQx4jcTdJXxwKAissE3Jbrrc1".to_string(),
+                    script_pub_key: "76a91488d9931ea73d60eaf7e5671efc0552b912911f2a88ac"
+                        .to_string(),
+                    derived_path: "0/0".to_string(),
+                    sequence: 0,
+                }];
+                let tx_input = BtcForkTxInput {
+                    to: "mrU9pEmAx26HcbKVrABvgL7AwA5fjNFoDc".to_string(),
+                    amount,
+                    unspents,
+                    fee,
+                    change_address_index: 0,
+                    change_address: "mszYqVnqKoQx4jcTdJXxwKAissE3Jbrrc1".to_string(),
+                    network: "TESTNET".to_string(),
+                    seg_wit: "NONE".to_string(),
+                    max_fee,
+                    max_fee_ratio,
+                    allow_absurd_fee,
+                };
+                let coin_info = coin_info_from_param("LITECOIN", "TESTNET", "NONE", "").unwrap();
+                let tran = BtcForkTransaction::new(tx_input, coin_info);
+                let prv_key = Secp256k1PrivateKey::from_wif(
+                    "cSBnVM4xvxarwGQuAfQFwqDg9k5tErHUHzgWsEfD4zdwUasvqRVY",
+                )
+                .unwrap();
+                let change_addr =
+                    BtcForkAddress::from_str("mszYqVnqKoQx4jcTdJXxwKAissE3Jbrrc1").unwrap();
+                tran.sign_transaction(&vec![prv_key], change_addr.script_pubkey())
+            };
+        let is_absurd = |ret: Result<BtcForkSignedTxOutput>| match ret {
+            Err(err) => format!("{}", err) == "absurd_fee",
+            Ok(_) => false,
+        };
+
+        // by default the fee may be up to a quarter of the amount
+        assert!(sign(1000000, 250000, 0, 0, false).is_ok());
+        assert!(is_absurd(sign(1000000, 250001, 0, 0, false)));
+        assert!(sign(1000000, 100000, 100000, 0, false).is_ok());
+        assert!(is_absurd(sign(1000000, 100001, 100000, 0, false)));
+        assert!(sign(1000000, 100000, 0, 10, false).is_ok());
+        assert!(is_absurd(sign(1000000, 100001, 0, 10, false)));
+        assert!(sign(1000000, 5000000, 100000, 10, true).is_ok());
+
+        // a fee of 0.2 LTC meant as 0.002 LTC is small next to 5 LTC but above the 0.1 LTC cap
+        // of the network
+        assert!(is_absurd(sign(500000000, 20000000, 0, 0, false)));
+        assert!(sign(500000000, 10000000, 0, 0, false).is_ok());
+        assert!(sign(500000000, 20000000, 30000000, 0, false).is_ok());
+    }
+
     #[test]
     fn test_sign_segwit_ltc() {
         let unspents = vec![Utxo {
@@ -841,6 +1112,9 @@ mod tests {
             change_address: "".to_string(),
             network: "".to_string(),
             seg_wit: "".to_string(),
+            max_fee: 0,
+            max_fee_ratio: 0,
+            allow_absurd_fee: false,
         };
         let coin_info = coin_info_from_param("LITECOIN", "MAINNET", "NONE", "").unwrap();
         let tran = BitcoinForkSinger::<BtcForkAddress, SegWitTransactionSignComponent> {
@@ -862,4 +1136,68 @@ mod tests {
             .unwrap();
         assert_eq!(expected.signature, "020000000001018bba45b98e54a14d79ca2a5e253f727bff45cf58b5ac5421dd6a37756eb668e801000000171600147b03478d2f7c984179084baa38f790ed1d37629bffffffff01c01f2e010000000017a91400aff21f24bc08af58e41e4186d8492a10b84f9e8702483045022100d0cc3d94c7b7b34fdcc2adc4fd3f735560407581afd6caa11c8d04b963a048a00220777d98e0122fe97206875f49556a401dfc449739ec30e44cb9ed9b92a0b3ff1b01210209c629c64829ec2e99703600ee86c7161a9ed13213e714726210274c29cf780900000000");
     }
+
+    #[test]
+    fn test_sign_segwit_ltc_spend_change() {
+        let coin_info = coin_info_from_param("LITECOIN", "TESTNET", "P2WPKH", "").unwrap();
+        let mut keystore =
+            Keystore::from_mnemonic(TEST_MNEMONIC, TEST_PASSWORD, Metadata::default()).unwrap();
+        keystore.unlock_by_password(TEST_PASSWORD).unwrap();
+        let account = keystore.derive_coin::<BtcForkAddress>(&coin_info).unwrap();
+        assert_eq!(account.address, "QQipB2W59wxiW7ePhkNX1tVLDP79rMjeXP");
+
+        let sign = |keystore: &mut Keystore, unspent: Utxo, amount: i64| {
+            let tx_input = BtcForkTxInput {
+                to: "mmuf77YiGckWgfvd32viaj7EKfrUN1FdAz".to_string(),
+                amount,
+                unspents: vec![unspent],
+                fee: 5902,
+                change_address_index: 1u32,
+                change_address: "".to_string(),
+                network: "TESTNET".to_string(),
+                seg_wit: "P2WPKH".to_string(),
+                max_fee: 0,
+                max_fee_ratio: 0,
+                allow_absurd_fee: false,
+            };
+            let tran = BtcForkSegWitTransaction::new(tx_input, coin_info.clone());
+            keystore
+                .sign_transaction("LITECOIN", "QQipB2W59wxiW7ePhkNX1tVLDP79rMjeXP", &tran)
+                .unwrap()
+        };
+
+        let unspent = Utxo {
+            tx_hash: "57c935201d6abf4b32151f9d96bfb51b058824a601011c3432e751b0a6d4a101".to_string(),
+            vout: 0,
+            amount: 1000000,
+            address: "QQipB2W59wxiW7ePhkNX1tVLDP79rMjeXP".to_string(),
+            script_pub_key: "".to_string(),
+            derived_path: "0/0".to_string(),
+            sequence: 0,
+        };
+        let actual = sign(&mut keystore, unspent, 100000);
+        // the change is a p2sh-p2wpkh address like the account
+        assert_eq!(actual.change_address, "QMDFM2YPdgr676hYJg3m7QR4XmmS96Ytv7");
+        assert_eq!(actual.change_address_path, "m/49'/1'/0'/1/1");
+        assert_eq!(
+            actual.tx_hash,
+            "4de420add40165737a24b0677177fd99cc7dadf76060db5a85aea5d0965b4ba4"
+        );
+        assert_eq!(actual.signature, "0200000000010101a1d4a6b051e732341c0101a62488051bb5bf969d1f15324bbf6a1d2035c9570000000017160014654fbb08267f3d50d715a8f1abb55979b160dd5bffffffff02a0860100000000001976a914461bf9360ec1bc9fe438df19ef36c7c2bb26ef8288ac92a40d000000000017a91406ab4711f6a0c5e85f5f6f8b3c285a88ca37da1b8702483045022100a2b4ec03aeed2632accb4be87c826717a1ea79634dfb50954befd718f1643b2402204ba64231b534347795934566c8de7754da11b6cf53ee9fa38a538797b585f3f00121031aee5e20399d68cf0035d1a21564868f22bc448ab205292b4279136b15ecaebc00000000");
+
+        let change = Utxo {
+            tx_hash: "a44b5b96d0a5ae855adb6060f7ad7dcc99fd777167b0247a736501d4ad20e44d".to_string(),
+            vout: 1,
+            amount: 894098,
+            address: "QMDFM2YPdgr676hYJg3m7QR4XmmS96Ytv7".to_string(),
+            script_pub_key: "a91406ab4711f6a0c5e85f5f6f8b3c285a88ca37da1b87".to_string(),
+            derived_path: "1/1".to_string(),
+            sequence: 0,
+        };
+        let actual = sign(&mut keystore, change, 888196);
+        // there is no change left
+        assert_eq!(actual.change_address, "");
+        assert_eq!(actual.change_address_path, "");
+        assert_eq!(actual.signature, "020000000001014de420add40165737a24b0677177fd99cc7dadf76060db5a85aea5d0965b4ba401000000171600143c84c1327824d927d62429c7832d4440ba258ce6ffffffff01848d0d00000000001976a914461bf9360ec1bc9fe438df19ef36c7c2bb26ef8288ac02483045022100c82c22496c5e9682b653792d0b5fe154c8c431c71e5039c6db7b307814330d2202201560edc27ef23b63c6beff4789e8c465848067f321a73f294ca30eb9baf5e7f80121031492dd0eba8bed80f7a561e30e7def01fc620a5fc4e8e2f172038844c9593e2700000000");
+    }
 }
@@ -4,46 +4,88 @@
 // - https://www.bitcoincash.org/spec/transaction.html
 
 /// FUNCTION: sign_tx(SignParam{input: BtcForkTxInput}): BtcForkSignedTxOutput
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Utxo {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub tx_hash: std::string::String,
     #[prost(int32, tag = "2")]
+    #[serde(default)]
     pub vout: i32,
     #[prost(int64, tag = "3")]
+    #[serde(default)]
+    #[serde(with = "tcx_constants::json_int64")]
     pub amount: i64,
     #[prost(string, tag = "4")]
+    #[serde(default)]
     pub address: std::string::String,
     #[prost(string, tag = "5")]
+    #[serde(default)]
     pub script_pub_key: std::string::String,
     #[prost(string, tag = "6")]
+    #[serde(default)]
     pub derived_path: std::string::String,
     #[prost(int64, tag = "7")]
+    #[serde(default)]
+    #[serde(with = "tcx_constants::json_int64")]
     pub sequence: i64,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BtcForkTxInput {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub to: std::string::String,
     #[prost(int64, tag = "2")]
+    #[serde(default)]
+    #[serde(with = "tcx_constants::json_int64")]
     pub amount: i64,
     #[prost(message, repeated, tag = "3")]
+    #[serde(default)]
     pub unspents: ::std::vec::Vec<Utxo>,
     #[prost(int64, tag = "4")]
+    #[serde(default)]
+    #[serde(with = "tcx_constants::json_int64")]
     pub fee: i64,
     #[prost(uint32, tag = "5")]
+    #[serde(default)]
     pub change_address_index: u32,
     #[prost(string, tag = "6")]
+    #[serde(default)]
     pub change_address: std::string::String,
     #[prost(string, tag = "7")]
+    #[serde(default)]
     pub network: std::string::String,
     #[prost(string, tag = "8")]
+    #[serde(default)]
     pub seg_wit: std::string::String,
+    /// the fee is refused with absurd_fee when it's above maxFee or above maxFeeRatio percent of
+    /// the amount, 0 takes the maxFee of the network and 25 for maxFeeRatio. allowAbsurdFee skips the check
+    #[prost(int64, tag = "9")]
+    #[serde(default)]
+    #[serde(with = "tcx_constants::json_int64")]
+    pub max_fee: i64,
+    #[prost(uint32, tag = "10")]
+    #[serde(default)]
+    pub max_fee_ratio: u32,
+    #[prost(bool, tag = "11")]
+    #[serde(default)]
+    pub allow_absurd_fee: bool,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BtcForkSignedTxOutput {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub signature: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub tx_hash: std::string::String,
+    #[prost(string, tag = "3")]
+    #[serde(default)]
+    pub change_address: std::string::String,
+    #[prost(string, tag = "4")]
+    #[serde(default)]
+    pub change_address_path: std::string::String,
 }
@@ -1,7 +1,6 @@
-use bip39::{Language, Mnemonic, Seed};
-
 use uuid::Uuid;
 
+use super::slip39::{combine_shares, generate_shares, Slip39Group};
 use super::Account;
 use super::Address;
 use super::Result;
@@ -15,28 +14,83 @@ use tcx_constants::{CoinInfo, CurveType};
 use tcx_crypto::hash::dsha256;
 use tcx_crypto::{Crypto, Key, Pbkdf2Params};
 use tcx_primitive::{
-    generate_mnemonic, get_account_path, Derive, ToHex, TypedDeterministicPrivateKey,
-    TypedDeterministicPublicKey, TypedPrivateKey,
+    generate_mnemonic, get_account_path, get_hardened_path, language_from_code,
+    mnemonic_to_seed_with_passphrase, normalize_mnemonic, validate_mnemonic, Derive, ToHex,
+    TypedDeterministicPrivateKey, TypedDeterministicPublicKey, TypedPrivateKey, TypedPublicKey,
 };
+use zeroize::Zeroizing;
+
+/// The secret an HD keystore derives its keys from
+enum Secret {
+    Mnemonic {
+        mnemonic: Zeroizing<String>,
+        passphrase: Zeroizing<String>,
+    },
+    /// The BIP-32 seed of a keystore recovered from SLIP-39 shares
+    Seed(Zeroizing<Vec<u8>>),
+}
 
+/// The unlocked secrets, the secret and the derived keys are wiped when the cache is dropped
 struct Cache {
-    mnemonic: String,
+    secret: Secret,
     keys: HashMap<String, TypedDeterministicPrivateKey>,
 }
 
+impl Cache {
+    fn root_key(&self, curve: CurveType) -> Result<TypedDeterministicPrivateKey> {
+        match &self.secret {
+            Secret::Mnemonic {
+                mnemonic,
+                passphrase,
+            } => TypedDeterministicPrivateKey::from_mnemonic_with_passphrase(
+                curve, mnemonic, passphrase,
+            ),
+            Secret::Seed(seed) => TypedDeterministicPrivateKey::from_seed(curve, seed),
+        }
+    }
+
+    /// The BIP-32 seed, which is the master secret of the SLIP-39 shares
+    fn seed(&self) -> Result<Zeroizing<Vec<u8>>> {
+        match &self.secret {
+            Secret::Mnemonic {
+                mnemonic,
+                passphrase,
+            } => mnemonic_to_seed_with_passphrase(mnemonic, passphrase)
+                .map_err(transform_mnemonic_error),
+            Secret::Seed(seed) => Ok(seed.clone()),
+        }
+    }
+}
+
 pub struct HdKeystore {
     store: Store,
     cache: Option<Cache>,
 }
 
 pub fn key_hash_from_mnemonic(mnemonic: &str) -> Result<String> {
-    let mn =
-        Mnemonic::from_phrase(mnemonic, Language::English).map_err(transform_mnemonic_error)?;
+    key_hash_from_mnemonic_with_passphrase(mnemonic, "")
+}
 
-    let seed = Seed::new(&mn, "");
+/// The hash of the seed, so the same mnemonic with different passphrases are different wallets
+pub fn key_hash_from_mnemonic_with_passphrase(mnemonic: &str, passphrase: &str) -> Result<String> {
+    let seed =
+        mnemonic_to_seed_with_passphrase(mnemonic, passphrase).map_err(transform_mnemonic_error)?;
 
-    let bytes = dsha256(seed.as_bytes())[..20].to_vec();
-    Ok(hex::encode(bytes))
+    Ok(key_hash_from_seed(&seed))
+}
+
+fn key_hash_from_seed(seed: &[u8]) -> String {
+    let bytes = dsha256(seed)[..20].to_vec();
+    hex::encode(bytes)
+}
+
+/// Check that `mnemonic` is a phrase of the language of `language_code`, e.g. `ja`. Importing
+/// detects the language, this only tells a phrase of the wrong language apart from a bad one
+pub fn check_mnemonic_language(mnemonic: &str, language_code: &str) -> Result<()> {
+    let language = language_from_code(language_code)?;
+    validate_mnemonic(&normalize_mnemonic(mnemonic), Some(language))
+        .map_err(transform_mnemonic_error)?;
+    Ok(())
 }
 
 impl HdKeystore {
@@ -55,29 +109,54 @@ impl HdKeystore {
     }
 
     pub(crate) fn unlock_by_password(&mut self, password: &str) -> Result<()> {
-        let mnemonic_bytes = self
+        let secret_bytes = self
             .store
             .crypto
             .decrypt(Key::Password(password.to_owned()))?;
-        self.cache_mnemonic(mnemonic_bytes)
+        let passphrase_bytes = self.decrypt_passphrase(Key::Password(password.to_owned()))?;
+        self.cache_secret(secret_bytes, passphrase_bytes)
     }
 
     pub(crate) fn unlock_by_derived_key(&mut self, derived_key: &str) -> Result<()> {
-        let mnemonic_bytes = self
+        let secret_bytes = self
             .store
             .crypto
             .decrypt(Key::DerivedKey(derived_key.to_owned()))?;
-        self.cache_mnemonic(mnemonic_bytes)
+        let passphrase_bytes = self.decrypt_passphrase(Key::DerivedKey(derived_key.to_owned()))?;
+        self.cache_secret(secret_bytes, passphrase_bytes)
+    }
+
+    fn decrypt_passphrase(&self, key: Key) -> Result<Zeroizing<Vec<u8>>> {
+        match &self.store.enc_passphrase {
+            Some(enc_pair) => self.store.crypto.decrypt_enc_pair(key, enc_pair),
+            None => Ok(Zeroizing::new(vec![])),
+        }
     }
 
-    fn cache_mnemonic(&mut self, mnemonic_bytes: Vec<u8>) -> Result<()> {
-        let mnemonic_str = String::from_utf8(mnemonic_bytes)?;
+    fn cache_secret(
+        &mut self,
+        secret_bytes: Zeroizing<Vec<u8>>,
+        passphrase_bytes: Zeroizing<Vec<u8>>,
+    ) -> Result<()> {
+        if self.store.seed_only {
+            self.cache = Some(Cache {
+                secret: Secret::Seed(secret_bytes),
+                keys: HashMap::new(),
+            });
+            return Ok(());
+        }
+
+        // borrow the bytes so that the error doesn't carry a copy of them
+        let mnemonic_str = std::str::from_utf8(&secret_bytes)?;
+        let passphrase_str = std::str::from_utf8(&passphrase_bytes)?;
 
-        let _mnemonic = Mnemonic::from_phrase(&mnemonic_str, Language::English)
-            .map_err(transform_mnemonic_error)?;
+        validate_mnemonic(mnemonic_str, None).map_err(transform_mnemonic_error)?;
 
         self.cache = Some(Cache {
-            mnemonic: mnemonic_str,
+            secret: Secret::Mnemonic {
+                mnemonic: Zeroizing::new(mnemonic_str.to_owned()),
+                passphrase: Zeroizing::new(passphrase_str.to_owned()),
+            },
             keys: HashMap::new(),
         });
 
@@ -92,10 +171,26 @@ impl HdKeystore {
         self.cache.is_none()
     }
 
-    pub(crate) fn mnemonic(&self) -> Result<String> {
+    pub(crate) fn mnemonic(&self) -> Result<Zeroizing<String>> {
         let cache = self.cache.as_ref().ok_or(Error::KeystoreLocked)?;
 
-        Ok(cache.mnemonic.to_string())
+        match &cache.secret {
+            Secret::Mnemonic { mnemonic, .. } => Ok(mnemonic.clone()),
+            Secret::Seed(_) => Err(Error::KeystoreWithoutMnemonic.into()),
+        }
+    }
+
+    /// Whether the words at the zero based positions match the mnemonic, confirming a backup
+    /// without exporting the whole phrase
+    pub(crate) fn verify_mnemonic_words(&self, words: &[(usize, &str)]) -> Result<bool> {
+        let mnemonic = self.mnemonic()?;
+        let phrase: Vec<&str> = mnemonic.split(' ').collect();
+        let mut matched = !words.is_empty();
+        for (index, word) in words {
+            let expected = phrase.get(*index).ok_or(Error::MnemonicWordIndexInvalid)?;
+            matched &= normalize_mnemonic(word).as_str() == *expected;
+        }
+        Ok(matched)
     }
 
     pub(crate) fn find_private_key(&self, symbol: &str, address: &str) -> Result<TypedPrivateKey> {
@@ -105,7 +200,7 @@ impl HdKeystore {
             .account(symbol, address)
             .ok_or(Error::AccountNotFound)?;
 
-        let root = TypedDeterministicPrivateKey::from_mnemonic(account.curve, &cache.mnemonic)?;
+        let root = cache.root_key(account.curve)?;
 
         Ok(root.derive(&account.derivation_path)?.private_key())
     }
@@ -122,6 +217,25 @@ impl HdKeystore {
         TypedDeterministicPublicKey::from_hex(account.curve, &account.ext_pub_key)
     }
 
+    /// The public key at `relative_path` below an account, derived from the stored extended
+    /// public key so the keystore can stay locked
+    pub(crate) fn derive_public_key(
+        &self,
+        symbol: &str,
+        address: &str,
+        relative_path: &str,
+    ) -> Result<TypedPublicKey> {
+        let account = self
+            .account(symbol, address)
+            .ok_or(Error::AccountNotFound)?;
+        if account.ext_pub_key.is_empty() {
+            return Err(Error::CannotDeriveKey.into());
+        }
+
+        let dpk = TypedDeterministicPublicKey::from_hex(account.curve, &account.ext_pub_key)?;
+        Ok(dpk.derive(relative_path)?.public_key())
+    }
+
     pub(crate) fn find_private_key_by_path(
         &mut self,
         symbol: &str,
@@ -135,7 +249,7 @@ impl HdKeystore {
                 .account(symbol, main_address)
                 .ok_or(Error::AccountNotFound)?;
 
-            let esk = TypedDeterministicPrivateKey::from_mnemonic(account.curve, &cache.mnemonic)?;
+            let esk = cache.root_key(account.curve)?;
 
             let k = esk.derive(&get_account_path(&account.derivation_path)?)?;
 
@@ -158,11 +272,37 @@ impl HdKeystore {
     }
 
     pub fn from_mnemonic(mnemonic: &str, password: &str, meta: Metadata) -> Result<HdKeystore> {
-        let mnemonic: &str = &mnemonic.split_whitespace().collect::<Vec<&str>>().join(" ");
+        Self::from_mnemonic_with_kdf_params(mnemonic, password, meta, Pbkdf2Params::default())
+    }
 
-        let key_hash = key_hash_from_mnemonic(mnemonic)?;
+    pub fn from_mnemonic_with_kdf_params(
+        mnemonic: &str,
+        password: &str,
+        meta: Metadata,
+        kdf_params: Pbkdf2Params,
+    ) -> Result<HdKeystore> {
+        Self::from_mnemonic_with_passphrase(mnemonic, "", password, meta, kdf_params)
+    }
 
-        let crypto: Crypto<Pbkdf2Params> = Crypto::new(password, mnemonic.as_bytes());
+    /// A non-empty `passphrase` is kept in `enc_passphrase` and applied to the seed of every curve
+    pub fn from_mnemonic_with_passphrase(
+        mnemonic: &str,
+        passphrase: &str,
+        password: &str,
+        meta: Metadata,
+        kdf_params: Pbkdf2Params,
+    ) -> Result<HdKeystore> {
+        let mnemonic: &str = &normalize_mnemonic(mnemonic);
+
+        let key_hash = key_hash_from_mnemonic_with_passphrase(mnemonic, passphrase)?;
+
+        let crypto: Crypto<Pbkdf2Params> =
+            Crypto::new_with_kdf_params(password, mnemonic.as_bytes(), kdf_params);
+        let enc_passphrase = if passphrase.is_empty() {
+            None
+        } else {
+            Some(crypto.derive_enc_pair(password, passphrase.as_bytes())?)
+        };
         Ok(HdKeystore {
             store: Store {
                 key_hash,
@@ -170,6 +310,8 @@ impl HdKeystore {
                 id: Uuid::new_v4().to_hyphenated().to_string(),
                 version: Self::VERSION,
                 active_accounts: vec![],
+                enc_passphrase,
+                seed_only: false,
                 meta,
             },
 
@@ -177,18 +319,70 @@ impl HdKeystore {
         })
     }
 
+    /// Recover the keystore of SLIP-39 shares. The master secret is the BIP-32 seed as SLIP-39
+    /// defines it, so the keystore keeps the seed and has no mnemonic. Substrate derives sr25519
+    /// keys from the mnemonic entropy rather than the seed, there are none of them
+    pub fn from_slip39_shares_with_kdf_params(
+        shares: &[String],
+        passphrase: &str,
+        password: &str,
+        meta: Metadata,
+        kdf_params: Pbkdf2Params,
+    ) -> Result<HdKeystore> {
+        let seed = combine_shares(shares, passphrase)?;
+
+        let crypto: Crypto<Pbkdf2Params> = Crypto::new_with_kdf_params(password, &seed, kdf_params);
+        Ok(HdKeystore {
+            store: Store {
+                key_hash: key_hash_from_seed(&seed),
+                crypto,
+                id: Uuid::new_v4().to_hyphenated().to_string(),
+                version: Self::VERSION,
+                active_accounts: vec![],
+                enc_passphrase: None,
+                seed_only: true,
+                meta,
+            },
+
+            cache: None,
+        })
+    }
+
+    /// Split the BIP-32 seed into SLIP-39 shares, grouped as `groups`. The seed of a mnemonic
+    /// already carries its language and passphrase, so the recovered keystore derives the same
+    /// keys
+    pub(crate) fn slip39_shares(
+        &self,
+        group_threshold: u8,
+        groups: &[Slip39Group],
+        passphrase: &str,
+    ) -> Result<Vec<Vec<String>>> {
+        let cache = self.cache.as_ref().ok_or(Error::KeystoreLocked)?;
+
+        let seed = cache.seed()?;
+        generate_shares(&seed, group_threshold, groups, passphrase)
+    }
+
     pub(crate) fn derive_coin<A: Address>(&mut self, coin_info: &CoinInfo) -> Result<Account> {
         let cache = self.cache.as_ref().ok_or(Error::KeystoreLocked)?;
 
-        let root = TypedDeterministicPrivateKey::from_mnemonic(coin_info.curve, &cache.mnemonic)?;
+        let root = cache.root_key(coin_info.curve)?;
 
         let private_key = root.derive(&coin_info.derivation_path)?.private_key();
         let public_key = private_key.public_key();
 
         let address = A::from_public_key(&public_key, coin_info)?;
-        // todo: ext_pub_key
+        // sr25519 soft junctions derive from the public key of the hardened part, ed25519 and
+        // BLS derive hardened children only
         let ext_pub_key = match coin_info.curve {
-            CurveType::SubSr25519 | CurveType::BLS | CurveType::ED25519 => "".to_owned(),
+            CurveType::BLS | CurveType::ED25519 => "".to_owned(),
+            CurveType::SubSr25519 => root
+                .derive(&get_hardened_path(
+                    coin_info.curve,
+                    &coin_info.derivation_path,
+                )?)?
+                .deterministic_public_key()
+                .to_hex(),
             _ => root
                 .derive(&get_account_path(&coin_info.derivation_path)?)?
                 .deterministic_public_key()
@@ -304,6 +498,152 @@ mod tests {
         }
     }
 
+    fn bitcoin_coin_info() -> CoinInfo {
+        CoinInfo {
+            coin: "BITCOIN".to_string(),
+            derivation_path: "m/44'/0'/0'/0/0".to_string(),
+            curve: CurveType::SECP256k1,
+            network: "MAINNET".to_string(),
+            seg_wit: "NONE".to_string(),
+        }
+    }
+
+    /// Export the keystore as 2-of-3 shares, recover it from two of them and compare the
+    /// derived accounts
+    fn assert_slip39_roundtrip(mut keystore: HdKeystore) {
+        let groups = [Slip39Group {
+            member_threshold: 2,
+            member_count: 3,
+        }];
+        assert_eq!(
+            format!("{}", keystore.slip39_shares(1, &groups, "").err().unwrap()),
+            "keystore_locked"
+        );
+
+        keystore.unlock_by_password(TEST_PASSWORD).unwrap();
+        let acc = keystore
+            .derive_coin::<MockAddress>(&bitcoin_coin_info())
+            .unwrap();
+        let shares = keystore.slip39_shares(1, &groups, "").unwrap();
+        assert_eq!(shares[0].len(), 3);
+
+        let mut recovered = HdKeystore::from_slip39_shares_with_kdf_params(
+            &shares[0][1..],
+            "",
+            TEST_PASSWORD,
+            Metadata::default(),
+            Pbkdf2Params::default(),
+        )
+        .unwrap();
+        assert_eq!(recovered.store.key_hash, keystore.store.key_hash);
+
+        // the seed survives a round trip through the keystore json
+        let json = serde_json::to_string(&recovered.store).unwrap();
+        recovered = HdKeystore::from_store(serde_json::from_str(&json).unwrap());
+        recovered.unlock_by_password(TEST_PASSWORD).unwrap();
+        assert_eq!(
+            recovered
+                .derive_coin::<MockAddress>(&bitcoin_coin_info())
+                .unwrap(),
+            acc
+        );
+        assert_eq!(
+            format!("{}", recovered.mnemonic().err().unwrap()),
+            "keystore_without_mnemonic"
+        );
+
+        // the shares of the recovered keystore split the same seed
+        let shares = recovered.slip39_shares(1, &groups, "").unwrap();
+        let recovered = HdKeystore::from_slip39_shares_with_kdf_params(
+            &shares[0][..2],
+            "",
+            TEST_PASSWORD,
+            Metadata::default(),
+            Pbkdf2Params::default(),
+        )
+        .unwrap();
+        assert_eq!(recovered.store.key_hash, keystore.store.key_hash);
+
+        let err = HdKeystore::from_slip39_shares_with_kdf_params(
+            &shares[0][..1],
+            "",
+            TEST_PASSWORD,
+            Metadata::default(),
+            Pbkdf2Params::default(),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(format!("{}", err), "slip39_shares_insufficient");
+    }
+
+    #[test]
+    pub fn slip39_shares_roundtrip() {
+        let keystore =
+            HdKeystore::from_mnemonic(TEST_MNEMONIC, TEST_PASSWORD, Metadata::default()).unwrap();
+        assert_slip39_roundtrip(keystore);
+    }
+
+    #[test]
+    pub fn slip39_shares_roundtrip_with_passphrase() {
+        let keystore = HdKeystore::from_mnemonic_with_passphrase(
+            TEST_MNEMONIC,
+            "TREZOR",
+            TEST_PASSWORD,
+            Metadata::default(),
+            Pbkdf2Params::default(),
+        )
+        .unwrap();
+        assert_slip39_roundtrip(keystore);
+    }
+
+    #[test]
+    pub fn slip39_shares_roundtrip_japanese() {
+        let keystore = HdKeystore::from_mnemonic(
+            "そつう　れきだい　ほんやく　わかす　りくつ　ばいか　ろせん　やちん　そつう　れきだい　ほんやく　わかめ",
+            TEST_PASSWORD,
+            Metadata::default(),
+        )
+        .unwrap();
+        assert_slip39_roundtrip(keystore);
+    }
+
+    #[test]
+    pub fn from_slip39_test_vector() {
+        // vector 1 of SLIP-39, the master secret bb54aac4b89dc868ba37d9cc21b2cece is the seed of
+        // xprv9s21ZrQH143K4QViKpwKCpS2zVbz8GrZgpEchMDg6KME9HZtjfL7iThE9w5muQA4YPHKN1u5VM1w8D4pvnjxa2BmpGMfXr7hnRrRHZ93awZ
+        let shares = ["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard".to_string()];
+        let mut keystore = HdKeystore::from_slip39_shares_with_kdf_params(
+            &shares,
+            "TREZOR",
+            TEST_PASSWORD,
+            Metadata::default(),
+            Pbkdf2Params::default(),
+        )
+        .unwrap();
+        keystore.unlock_by_password(TEST_PASSWORD).unwrap();
+
+        let acc = keystore
+            .derive_coin::<MockAddress>(&bitcoin_coin_info())
+            .unwrap();
+        assert_eq!(
+            acc.public_key.unwrap(),
+            "0356648e3c1890268482b5e58b3448f04de015b53ee161f562cbb888d7c1fef812"
+        );
+
+        let coin_info = CoinInfo {
+            coin: "KUSAMA".to_string(),
+            derivation_path: "//kusama//imToken/0".to_string(),
+            curve: CurveType::SubSr25519,
+            network: "".to_string(),
+            seg_wit: "".to_string(),
+        };
+        let err = keystore
+            .derive_coin::<MockAddress>(&coin_info)
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "invalid_curve_type");
+    }
+
     #[test]
     pub fn from_blank_space_mnemonic() {
         let mut keystore =
@@ -338,6 +678,95 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn derive_public_key_without_unlock() {
+        let mut keystore =
+            HdKeystore::from_mnemonic(TEST_MNEMONIC, TEST_PASSWORD, Metadata::default()).unwrap();
+        keystore.unlock_by_password(TEST_PASSWORD).unwrap();
+        let coin_info = CoinInfo {
+            coin: "KUSAMA".to_string(),
+            derivation_path: "//kusama//imToken/0".to_string(),
+            curve: CurveType::SubSr25519,
+            network: "".to_string(),
+            seg_wit: "".to_string(),
+        };
+        let acc = keystore.derive_coin::<MockAddress>(&coin_info).unwrap();
+        assert!(!acc.ext_pub_key.is_empty());
+        let coin_info = CoinInfo {
+            coin: "TEZOS".to_string(),
+            derivation_path: "m/44'/1729'/0'/0'".to_string(),
+            curve: CurveType::ED25519,
+            network: "MAINNET".to_string(),
+            seg_wit: "".to_string(),
+        };
+        keystore.derive_coin::<MockAddress>(&coin_info).unwrap();
+        keystore.lock();
+
+        let public_key = keystore
+            .derive_public_key("KUSAMA", "mock_address", "/0")
+            .unwrap();
+        assert_eq!(Some(hex::encode(public_key.to_bytes())), acc.public_key);
+        let public_key = keystore
+            .derive_public_key("KUSAMA", "mock_address", "/1")
+            .unwrap();
+        assert_ne!(Some(hex::encode(public_key.to_bytes())), acc.public_key);
+
+        let err = keystore
+            .derive_public_key("KUSAMA", "mock_address", "//1")
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "cannot_derive_from_hardened_key");
+        let err = keystore
+            .derive_public_key("TEZOS", "mock_address", "m/1'")
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "can_not_derive_key");
+    }
+
+    #[test]
+    pub fn from_mnemonic_with_passphrase() {
+        let coin_info = CoinInfo {
+            coin: "BITCOIN".to_string(),
+            derivation_path: "m/44'/0'/0'/0/0".to_string(),
+            curve: CurveType::SECP256k1,
+            network: "MAINNET".to_string(),
+            seg_wit: "NONE".to_string(),
+        };
+        let mut keystore =
+            HdKeystore::from_mnemonic(TEST_MNEMONIC, TEST_PASSWORD, Metadata::default()).unwrap();
+        keystore.unlock_by_password(TEST_PASSWORD).unwrap();
+        let acc = keystore.derive_coin::<MockAddress>(&coin_info).unwrap();
+
+        let keystore = HdKeystore::from_mnemonic_with_passphrase(
+            TEST_MNEMONIC,
+            "TREZOR",
+            TEST_PASSWORD,
+            Metadata::default(),
+            Pbkdf2Params::default(),
+        )
+        .unwrap();
+        assert!(keystore.store.enc_passphrase.is_some());
+        assert_eq!(
+            keystore.store.key_hash,
+            key_hash_from_mnemonic_with_passphrase(TEST_MNEMONIC, "TREZOR").unwrap()
+        );
+        assert_ne!(
+            keystore.store.key_hash,
+            key_hash_from_mnemonic(TEST_MNEMONIC).unwrap()
+        );
+
+        // the passphrase survives a round trip through the keystore json
+        let json = serde_json::to_string(&keystore.store).unwrap();
+        let mut keystore = HdKeystore::from_store(serde_json::from_str(&json).unwrap());
+        keystore.unlock_by_password(TEST_PASSWORD).unwrap();
+        match &keystore.cache.as_ref().unwrap().secret {
+            Secret::Mnemonic { passphrase, .. } => assert_eq!(passphrase.as_str(), "TREZOR"),
+            Secret::Seed(_) => panic!("expected a mnemonic"),
+        }
+        let passphrase_acc = keystore.derive_coin::<MockAddress>(&coin_info).unwrap();
+        assert_ne!(passphrase_acc.public_key, acc.public_key);
+    }
+
     #[test]
     pub fn from_mnemonic() {
         let mut keystore =
@@ -349,19 +778,61 @@ mod tests {
             .crypto
             .decrypt(Key::Password(TEST_PASSWORD.to_owned()))
             .unwrap();
-        let decrypted_mnemonic = String::from_utf8(decrypted_bytes).unwrap();
+        let decrypted_mnemonic = String::from_utf8(decrypted_bytes.to_vec()).unwrap();
         assert_eq!(decrypted_mnemonic, TEST_MNEMONIC);
         assert_eq!(keystore.store.active_accounts.len(), 0);
 
         keystore.unlock_by_password(TEST_PASSWORD).unwrap();
 
         let mnemonic = keystore.mnemonic().unwrap();
-        assert_eq!(mnemonic, TEST_MNEMONIC);
+        assert_eq!(mnemonic.as_str(), TEST_MNEMONIC);
+
+        keystore.lock();
+        assert!(keystore.cache.is_none());
+        assert_eq!(
+            format!("{}", keystore.mnemonic().err().unwrap()),
+            "keystore_locked"
+        );
 
         let wrong_password_err = keystore.unlock_by_password("WrongPassword").err().unwrap();
         assert_eq!(format!("{}", wrong_password_err), "password_incorrect");
     }
 
+    #[test]
+    pub fn verify_mnemonic_words() {
+        let mut keystore =
+            HdKeystore::from_mnemonic(TEST_MNEMONIC, TEST_PASSWORD, Metadata::default()).unwrap();
+        assert_eq!(
+            format!(
+                "{}",
+                keystore
+                    .verify_mnemonic_words(&[(0, "inject")])
+                    .err()
+                    .unwrap()
+            ),
+            "keystore_locked"
+        );
+
+        keystore.unlock_by_password(TEST_PASSWORD).unwrap();
+        assert!(keystore
+            .verify_mnemonic_words(&[(0, "inject"), (5, "pact"), (11, " sketch")])
+            .unwrap());
+        assert!(!keystore
+            .verify_mnemonic_words(&[(0, "inject"), (5, "shadow")])
+            .unwrap());
+        assert!(!keystore.verify_mnemonic_words(&[]).unwrap());
+        assert_eq!(
+            format!(
+                "{}",
+                keystore
+                    .verify_mnemonic_words(&[(12, "sketch")])
+                    .err()
+                    .unwrap()
+            ),
+            "mnemonic_word_index_invalid"
+        );
+    }
+
     //    #[test]
     //    pub fn generate_seed() {
     //        let mnemonic = Mnemonic::from_phrase(
@@ -1,21 +1,25 @@
 use super::Result;
+#[cfg(not(target_arch = "wasm32"))]
 use std::time::{SystemTime, UNIX_EPOCH};
 
 mod guard;
 mod hd;
 mod private;
+mod slip39;
+mod v3;
 
 use serde::{Deserialize, Serialize};
 
 use tcx_constants::{CoinInfo, CurveType};
 
 pub use self::{
-    guard::KeystoreGuard, hd::key_hash_from_mnemonic, hd::HdKeystore,
-    private::key_hash_from_private_key, private::PrivateKeystore,
+    guard::KeystoreGuard, hd::check_mnemonic_language, hd::key_hash_from_mnemonic,
+    hd::key_hash_from_mnemonic_with_passphrase, hd::HdKeystore, private::key_hash_from_private_key,
+    private::PrivateKeystore, slip39::Slip39Group, v3::eth_address, v3::V3Keystore,
 };
 
 use crate::signer::ChainSigner;
-use tcx_crypto::{Crypto, Pbkdf2Params};
+use tcx_crypto::{Crypto, EncPair, Pbkdf2Params};
 use tcx_primitive::{TypedDeterministicPublicKey, TypedPrivateKey, TypedPublicKey};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +30,12 @@ pub(crate) struct Store {
     pub key_hash: String,
     pub crypto: Crypto<Pbkdf2Params>,
     pub active_accounts: Vec<Account>,
+    /// The BIP-39 passphrase of an HD keystore, encrypted by the same password as the mnemonic
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enc_passphrase: Option<EncPair>,
+    /// An HD keystore recovered from SLIP-39 shares encrypts the BIP-32 seed, it has no mnemonic
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub seed_only: bool,
 
     #[serde(rename = "imTokenMeta")]
     pub meta: Metadata,
@@ -51,15 +61,36 @@ pub enum Error {
     InvalidVersion,
     #[fail(display = "pkstore_can_not_add_other_curve_account")]
     PkstoreCannotAddOtherCurveAccount,
+    #[fail(display = "v3_keystore_invalid")]
+    V3KeystoreInvalid,
+    #[fail(display = "v3_keystore_address_not_match")]
+    V3KeystoreAddressNotMatch,
+    #[fail(display = "kdf_not_supported")]
+    KdfNotSupported,
+    #[fail(display = "slip39_share_invalid")]
+    Slip39ShareInvalid,
+    #[fail(display = "slip39_share_checksum_invalid")]
+    Slip39ShareChecksumInvalid,
+    #[fail(display = "slip39_shares_insufficient")]
+    Slip39SharesInsufficient,
+    #[fail(display = "slip39_threshold_invalid")]
+    Slip39ThresholdInvalid,
+    #[fail(display = "keystore_without_mnemonic")]
+    KeystoreWithoutMnemonic,
+    #[fail(display = "keystore_not_hd")]
+    KeystoreNotHd,
+    #[fail(display = "mnemonic_word_index_invalid")]
+    MnemonicWordIndexInvalid,
 }
 
-fn transform_mnemonic_error(err: failure::Error) -> Error {
-    let err = err.downcast::<bip39::ErrorKind>().unwrap();
-    match err {
-        bip39::ErrorKind::InvalidChecksum => Error::MnemonicChecksumInvalid,
-        bip39::ErrorKind::InvalidWord => Error::MnemonicWordInvalid,
-        bip39::ErrorKind::InvalidWordLength(_) => Error::MnemonicLengthInvalid,
-        _ => Error::MnemonicInvalid,
+/// Map the bip39 errors, the language errors of `tcx_primitive::MnemonicError` are kept
+fn transform_mnemonic_error(err: failure::Error) -> failure::Error {
+    match err.downcast::<bip39::ErrorKind>() {
+        Ok(bip39::ErrorKind::InvalidChecksum) => Error::MnemonicChecksumInvalid.into(),
+        Ok(bip39::ErrorKind::InvalidWord) => Error::MnemonicWordInvalid.into(),
+        Ok(bip39::ErrorKind::InvalidWordLength(_)) => Error::MnemonicLengthInvalid.into(),
+        Ok(_) => Error::MnemonicInvalid.into(),
+        Err(err) => err,
     }
 }
 
@@ -111,12 +142,23 @@ pub struct Metadata {
     pub source: Source,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 fn metadata_default_time() -> i64 {
     let start = SystemTime::now();
     let since_the_epoch = start.duration_since(UNIX_EPOCH).expect("get timestamp");
     since_the_epoch.as_secs() as i64
 }
 
+// there is no system clock on wasm32-unknown-unknown, ask the host instead
+#[cfg(target_arch = "wasm32")]
+fn metadata_default_time() -> i64 {
+    (js_sys::Date::now() / 1000.0) as i64
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
 fn metadata_default_source() -> Source {
     Source::Mnemonic
 }
@@ -201,7 +243,7 @@ impl Keystore {
         self.store()
             .crypto
             .generate_derived_key(password)
-            .map(|arr| hex::encode(arr))
+            .map(|dk| hex::encode(dk.as_slice()))
     }
 
     pub fn is_locked(&self) -> bool {
@@ -221,7 +263,28 @@ impl Keystore {
     pub fn export(&self) -> Result<String> {
         match self {
             Keystore::PrivateKey(pk_store) => pk_store.private_key(),
-            Keystore::Hd(hd_store) => hd_store.mnemonic(),
+            Keystore::Hd(hd_store) => hd_store.mnemonic().map(|mnemonic| mnemonic.to_string()),
+        }
+    }
+
+    /// The SLIP-39 shares of an unlocked HD keystore
+    pub fn slip39_shares(
+        &self,
+        group_threshold: u8,
+        groups: &[Slip39Group],
+        passphrase: &str,
+    ) -> Result<Vec<Vec<String>>> {
+        match self {
+            Keystore::PrivateKey(_) => Err(Error::KeystoreNotHd.into()),
+            Keystore::Hd(ks) => ks.slip39_shares(group_threshold, groups, passphrase),
+        }
+    }
+
+    /// Check the words at the zero based positions of an unlocked HD keystore's mnemonic
+    pub fn verify_mnemonic_words(&self, words: &[(usize, &str)]) -> Result<bool> {
+        match self {
+            Keystore::PrivateKey(_) => Err(Error::KeystoreNotHd.into()),
+            Keystore::Hd(ks) => ks.verify_mnemonic_words(words),
         }
     }
 
@@ -303,6 +366,19 @@ impl Keystore {
         }
     }
 
+    /// The public key at `relative_path` below an account of a hd keystore, it can be locked
+    pub fn derive_public_key(
+        &self,
+        symbol: &str,
+        address: &str,
+        relative_path: &str,
+    ) -> Result<TypedPublicKey> {
+        match self {
+            Keystore::Hd(ks) => ks.derive_public_key(symbol, address, relative_path),
+            _ => Err(Error::CannotDeriveKey.into()),
+        }
+    }
+
     pub fn account(&self, symbol: &str, address: &str) -> Option<&Account> {
         match self {
             Keystore::PrivateKey(ks) => ks.account(symbol, address),
@@ -10,6 +10,7 @@ use crate::keystore::Store;
 use tcx_crypto::hash::dsha256;
 use tcx_primitive::TypedPrivateKey;
 use uuid::Uuid;
+use zeroize::Zeroizing;
 
 pub fn key_hash_from_private_key(data: &[u8]) -> String {
     hex::encode(dsha256(data)[..20].to_vec())
@@ -18,7 +19,8 @@ pub fn key_hash_from_private_key(data: &[u8]) -> String {
 pub struct PrivateKeystore {
     store: Store,
 
-    private_key: Option<Vec<u8>>,
+    /// The decrypted private key, wiped when the keystore is locked or dropped
+    private_key: Option<Zeroizing<Vec<u8>>>,
 }
 
 impl PrivateKeystore {
@@ -112,10 +114,20 @@ impl PrivateKeystore {
     }
 
     pub fn from_private_key(private_key: &str, password: &str, meta: Metadata) -> PrivateKeystore {
-        let key_data: Vec<u8> = hex::decode(private_key).expect("hex can't decode");
+        Self::from_private_key_with_kdf_params(private_key, password, meta, Pbkdf2Params::default())
+    }
+
+    pub fn from_private_key_with_kdf_params(
+        private_key: &str,
+        password: &str,
+        meta: Metadata,
+        kdf_params: Pbkdf2Params,
+    ) -> PrivateKeystore {
+        let key_data = Zeroizing::new(hex::decode(private_key).expect("hex can't decode"));
         let key_hash = key_hash_from_private_key(&key_data);
         //        let pk_bytes = hex::decode(private_key).expect("valid private_key");
-        let crypto: Crypto<Pbkdf2Params> = Crypto::new(password, &key_data);
+        let crypto: Crypto<Pbkdf2Params> =
+            Crypto::new_with_kdf_params(password, &key_data, kdf_params);
 
         let store = Store {
             key_hash,
@@ -124,6 +136,8 @@ impl PrivateKeystore {
             id: Uuid::new_v4().to_hyphenated().to_string(),
             version: PrivateKeystore::VERSION,
             active_accounts: vec![],
+            enc_passphrase: None,
+            seed_only: false,
         };
 
         PrivateKeystore {
@@ -156,11 +170,10 @@ impl PrivateKeystore {
 
     pub(crate) fn private_key(&self) -> Result<String> {
         tcx_ensure!(self.private_key.is_some(), Error::KeystoreLocked);
-        let vec = self.private_key.as_ref().unwrap().to_vec();
-        Ok(hex::encode(&vec))
+        Ok(hex::encode(self.private_key.as_ref().unwrap().as_slice()))
     }
 
-    fn decrypt_private_key(&self, key: Key) -> Result<Vec<u8>> {
+    fn decrypt_private_key(&self, key: Key) -> Result<Zeroizing<Vec<u8>>> {
         self.store.crypto.decrypt(key)
     }
 }
@@ -186,4 +199,25 @@ mod tests {
         assert_ne!(keystore.store.id, "");
         assert_eq!(keystore.store.active_accounts.len(), 0);
     }
+
+    #[test]
+    pub fn lock_test() {
+        let mut keystore = PrivateKeystore::from_private_key(
+            "a392604efc2fad9c0b3da43b5f698a2e3f270f170d859912be0d54742275c5f6",
+            TEST_PASSWORD,
+            Metadata::default(),
+        );
+        keystore.unlock_by_password(TEST_PASSWORD).unwrap();
+        assert_eq!(
+            keystore.private_key().unwrap(),
+            "a392604efc2fad9c0b3da43b5f698a2e3f270f170d859912be0d54742275c5f6"
+        );
+
+        keystore.lock();
+        assert!(keystore.is_locked());
+        assert_eq!(
+            format!("{}", keystore.private_key().err().unwrap()),
+            "keystore_locked"
+        );
+    }
 }
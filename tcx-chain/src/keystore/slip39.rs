@@ -0,0 +1,197 @@
+use sssmc39::ErrorKind;
+use zeroize::Zeroizing;
+
+use super::Error;
+use super::Result;
+
+/// The iteration exponent of the Feistel cipher that encrypts the master secret with the
+/// passphrase, 10000 * 2^e PBKDF2 rounds in total
+const ITERATION_EXPONENT: u8 = 1;
+/// The shortest share is 20 words, for a 128 bits master secret
+const MIN_SHARE_WORDS: usize = 20;
+const MAX_SHARE_COUNT: u8 = 16;
+
+/// The member threshold and member count of one group of shares
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slip39Group {
+    pub member_threshold: u8,
+    pub member_count: u8,
+}
+
+fn validate_groups(group_threshold: u8, groups: &[Slip39Group]) -> Result<()> {
+    tcx_ensure!(
+        !groups.is_empty() && groups.len() <= MAX_SHARE_COUNT as usize,
+        Error::Slip39ThresholdInvalid
+    );
+    tcx_ensure!(
+        group_threshold >= 1 && group_threshold as usize <= groups.len(),
+        Error::Slip39ThresholdInvalid
+    );
+    for group in groups {
+        tcx_ensure!(
+            group.member_threshold >= 1
+                && group.member_threshold <= group.member_count
+                && group.member_count <= MAX_SHARE_COUNT,
+            Error::Slip39ThresholdInvalid
+        );
+        // a 1-of-n group would only be n copies of the same share
+        tcx_ensure!(
+            group.member_threshold > 1 || group.member_count == 1,
+            Error::Slip39ThresholdInvalid
+        );
+    }
+    Ok(())
+}
+
+fn transform_slip39_error(err: sssmc39::Error) -> Error {
+    match err.kind() {
+        ErrorKind::Checksum(_) => Error::Slip39ShareChecksumInvalid,
+        ErrorKind::Argument(_) | ErrorKind::Config(_) => Error::Slip39ThresholdInvalid,
+        _ => Error::Slip39ShareInvalid,
+    }
+}
+
+/// Split `master_secret` into the mnemonic shares of every group
+pub fn generate_shares(
+    master_secret: &[u8],
+    group_threshold: u8,
+    groups: &[Slip39Group],
+    passphrase: &str,
+) -> Result<Vec<Vec<String>>> {
+    validate_groups(group_threshold, groups)?;
+    let group_params: Vec<(u8, u8)> = groups
+        .iter()
+        .map(|group| (group.member_threshold, group.member_count))
+        .collect();
+
+    let group_shares = sssmc39::generate_mnemonics(
+        group_threshold,
+        &group_params,
+        master_secret,
+        passphrase,
+        ITERATION_EXPONENT,
+    )
+    .map_err(transform_slip39_error)?;
+
+    let mut shares = vec![];
+    for group_share in group_shares {
+        let mnemonics = group_share
+            .mnemonic_list()
+            .map_err(transform_slip39_error)?;
+        shares.push(mnemonics.iter().map(|words| words.join(" ")).collect());
+    }
+    Ok(shares)
+}
+
+/// Recover the master secret from enough shares of enough groups, the order of the shares
+/// doesn't matter. A wrong passphrase recovers a different secret rather than an error
+pub fn combine_shares(shares: &[String], passphrase: &str) -> Result<Zeroizing<Vec<u8>>> {
+    tcx_ensure!(!shares.is_empty(), Error::Slip39SharesInsufficient);
+    let mnemonics: Vec<Vec<String>> = shares
+        .iter()
+        .map(|share| {
+            share
+                .split_whitespace()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<String>>()
+        })
+        .collect();
+    tcx_ensure!(
+        mnemonics.iter().all(|words| words.len() >= MIN_SHARE_WORDS),
+        Error::Slip39ShareInvalid
+    );
+
+    let master_secret = sssmc39::combine_mnemonics(&mnemonics, passphrase).map_err(|err| {
+        match err.kind() {
+            // combining fails with a value error when a group has fewer shares than its threshold
+            ErrorKind::Value(_) => Error::Slip39SharesInsufficient,
+            _ => transform_slip39_error(err),
+        }
+    })?;
+    Ok(Zeroizing::new(master_secret))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{combine_shares, generate_shares, Slip39Group};
+
+    #[test]
+    fn test_generate_and_combine_shares() {
+        let master_secret = hex::decode("bb54aac4b89dc868ba37d9cc21b2cece").unwrap();
+        let groups = [
+            Slip39Group {
+                member_threshold: 2,
+                member_count: 3,
+            },
+            Slip39Group {
+                member_threshold: 1,
+                member_count: 1,
+            },
+        ];
+        let shares = generate_shares(&master_secret, 2, &groups, "TREZOR").unwrap();
+        assert_eq!(shares.len(), 2);
+        assert_eq!(shares[0].len(), 3);
+        assert_eq!(shares[1].len(), 1);
+        assert_eq!(shares[0][0].split(' ').count(), 20);
+
+        let recovered = combine_shares(
+            &[
+                shares[1][0].clone(),
+                shares[0][2].clone(),
+                shares[0][0].clone(),
+            ],
+            "TREZOR",
+        )
+        .unwrap();
+        assert_eq!(recovered.as_slice(), master_secret.as_slice());
+
+        let err = combine_shares(&[shares[1][0].clone(), shares[0][2].clone()], "TREZOR")
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "slip39_shares_insufficient");
+
+        // swap two different words, the RS1024 checksum detects it
+        let mut words: Vec<&str> = shares[0][0].split(' ').collect();
+        let i = (5..words.len() - 1)
+            .find(|&i| words[i] != words[i + 1])
+            .unwrap();
+        words.swap(i, i + 1);
+        let err = combine_shares(
+            &[shares[1][0].clone(), shares[0][1].clone(), words.join(" ")],
+            "TREZOR",
+        )
+        .err()
+        .unwrap();
+        assert_eq!(format!("{}", err), "slip39_share_checksum_invalid");
+
+        let err = combine_shares(&["inject kidney empty".to_string()], "")
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "slip39_share_invalid");
+    }
+
+    #[test]
+    fn test_invalid_threshold() {
+        let master_secret = [0u8; 16];
+        let invalid_groups = vec![
+            (0, vec![(2, 3)]),
+            (2, vec![(2, 3)]),
+            (1, vec![(4, 3)]),
+            (1, vec![(1, 3)]),
+            (1, vec![(2, 17)]),
+        ];
+        for (group_threshold, groups) in invalid_groups {
+            let groups: Vec<Slip39Group> = groups
+                .iter()
+                .map(|(member_threshold, member_count)| Slip39Group {
+                    member_threshold: *member_threshold,
+                    member_count: *member_count,
+                })
+                .collect();
+            let err = generate_shares(&master_secret, group_threshold, &groups, "")
+                .err()
+                .unwrap();
+            assert_eq!(format!("{}", err), "slip39_threshold_invalid");
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+use tcx_crypto::hash::keccak256;
+use tcx_crypto::{Crypto, KdfParams, Key, Pbkdf2Params, SCryptParams};
+use tcx_primitive::{PrivateKey, Secp256k1PrivateKey};
+
+use super::Error;
+use super::Result;
+
+/// A Web3 Secret Storage keystore of geth and MyEtherWallet. The `crypto` section is kept as json
+/// because its type depends on the `kdf`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct V3Keystore {
+    pub version: i64,
+    pub id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub address: String,
+    #[serde(alias = "Crypto")]
+    pub crypto: Value,
+}
+
+impl V3Keystore {
+    pub const VERSION: i64 = 3;
+
+    pub fn from_json(json: &str) -> Result<V3Keystore> {
+        let keystore: V3Keystore =
+            serde_json::from_str(json).map_err(|_| Error::V3KeystoreInvalid)?;
+        tcx_ensure!(keystore.version == Self::VERSION, Error::InvalidVersion);
+        tcx_ensure!(
+            keystore.crypto["cipher"] == "aes-128-ctr",
+            Error::V3KeystoreInvalid
+        );
+        Ok(keystore)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Encrypt a secp256k1 private key, the address is the ethereum address of the key
+    pub fn from_private_key<T>(private_key: &[u8], password: &str, kdf_params: T) -> Result<Self>
+    where
+        T: KdfParams + Serialize,
+    {
+        let address = eth_address(private_key)?;
+        let crypto: Crypto<T> = Crypto::new_with_kdf_params(password, private_key, kdf_params);
+        Ok(V3Keystore {
+            version: Self::VERSION,
+            id: Uuid::new_v4().to_hyphenated().to_string(),
+            address,
+            crypto: serde_json::to_value(&crypto)?,
+        })
+    }
+
+    /// The decrypted key, 32 bytes for the keystores of geth. Old imToken keystores encrypt the
+    /// WIF of the key instead, which is returned as is for the caller to parse
+    pub fn decrypt(&self, password: &str) -> Result<Zeroizing<Vec<u8>>> {
+        let key = Key::Password(password.to_owned());
+        let decrypted = match self.crypto["kdf"].as_str().unwrap_or("") {
+            "scrypt" => self.typed_crypto::<SCryptParams>()?.decrypt(key)?,
+            "pbkdf2" => {
+                tcx_ensure!(
+                    self.crypto["kdfparams"]["prf"] == "hmac-sha256",
+                    Error::KdfNotSupported
+                );
+                self.typed_crypto::<Pbkdf2Params>()?.decrypt(key)?
+            }
+            _ => return Err(Error::KdfNotSupported.into()),
+        };
+
+        if decrypted.len() == 32 && !self.address.is_empty() {
+            let address = self.address.trim_start_matches("0x").to_lowercase();
+            tcx_ensure!(
+                eth_address(&decrypted)? == address,
+                Error::V3KeystoreAddressNotMatch
+            );
+        }
+        Ok(decrypted)
+    }
+
+    fn typed_crypto<T>(&self) -> Result<Crypto<T>>
+    where
+        T: KdfParams + for<'de> Deserialize<'de>,
+    {
+        serde_json::from_value(self.crypto.clone()).map_err(|_| Error::V3KeystoreInvalid.into())
+    }
+}
+
+/// The hex of the last 20 bytes of the keccak256 of the uncompressed public key, without `0x`
+pub fn eth_address(private_key: &[u8]) -> Result<String> {
+    let public_key = Secp256k1PrivateKey::from_slice(private_key)?
+        .public_key()
+        .to_uncompressed();
+    Ok(hex::encode(&keccak256(&public_key[1..])[12..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::V3Keystore;
+    use tcx_constants::TEST_PASSWORD;
+    use tcx_crypto::{Pbkdf2Params, SCryptParams};
+
+    // the pbkdf2 test vector of the Web3 Secret Storage Definition
+    static PBKDF2_KEYSTORE: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": {"iv": "6087dab2f9fdbbfaddc31a909735c1e6"},
+            "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 262144,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+            },
+            "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "address": "008aeeda4d805471df9b2a5b0f38a0c3bcba786b",
+        "version": 3
+    }"#;
+
+    #[test]
+    fn test_decrypt_pbkdf2() {
+        let keystore = V3Keystore::from_json(PBKDF2_KEYSTORE).unwrap();
+        let private_key = keystore.decrypt("testpassword").unwrap();
+        assert_eq!(
+            hex::encode(private_key.as_slice()),
+            "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d"
+        );
+
+        let err = keystore.decrypt("wrong password").err().unwrap();
+        assert_eq!(format!("{}", err), "password_incorrect");
+
+        let mut keystore = keystore;
+        keystore.address = "ef678007d18427e6022059dbc264f27507cd1ffc".to_string();
+        let err = keystore.decrypt("testpassword").err().unwrap();
+        assert_eq!(format!("{}", err), "v3_keystore_address_not_match");
+    }
+
+    #[test]
+    fn test_decrypt_scrypt_wif() {
+        let json = r#"{
+            "version": 3,
+            "id": "5e0d4ab2-1b4f-4f0e-a3a5-3a2b7f6c0c2e",
+            "crypto": {
+                "mac": "a10b412993ec783e854cb339b1f4165a013d41267adb561ed9ab47c209dea3ab",
+                "cipherparams": {"iv": "799f757ee52b7c95aa76967fa908676c"},
+                "kdfparams": {
+                    "dklen": 32,
+                    "r": 8,
+                    "salt": "e3fa7f40fecac7f6c61326dfb6aba4697c2daba9ecd41f017e996ae15aa18a51",
+                    "p": 1,
+                    "n": 1024
+                },
+                "cipher": "aes-128-ctr",
+                "ciphertext": "d5c053f4893fbbaa0d58cc87d5b82abdbac55599a46fe6eb8e355487e5c4799039a4a4ae7f365db3d573946f3acb51a2cfb2aafe",
+                "kdf": "scrypt"
+            }
+        }"#;
+        let keystore = V3Keystore::from_json(json).unwrap();
+        let wif = keystore.decrypt("Insecure Pa55w0rd").unwrap();
+        assert_eq!(
+            String::from_utf8(wif.to_vec()).unwrap(),
+            "L2hfzPyVC1jWH7n2QLTe7tVTb6btg9smp5UVzhEBxLYaSFF7sCZB"
+        );
+    }
+
+    #[test]
+    fn test_from_private_key() {
+        let private_key =
+            hex::decode("a392604efc2fad9c0b3da43b5f698a2e3f270f170d859912be0d54742275c5f6")
+                .unwrap();
+        let keystore = V3Keystore::from_private_key(
+            &private_key,
+            TEST_PASSWORD,
+            Pbkdf2Params::with_rounds(1024),
+        )
+        .unwrap();
+        assert_eq!(keystore.address, "ef678007d18427e6022059dbc264f27507cd1ffc");
+        assert_eq!(keystore.crypto["kdf"], "pbkdf2");
+
+        let keystore = V3Keystore::from_json(&keystore.to_json()).unwrap();
+        assert_eq!(
+            keystore.decrypt(TEST_PASSWORD).unwrap().as_slice(),
+            private_key.as_slice()
+        );
+
+        let keystore =
+            V3Keystore::from_private_key(&private_key, TEST_PASSWORD, SCryptParams::with_n(1024))
+                .unwrap();
+        assert_eq!(keystore.crypto["kdfparams"]["n"], 1024);
+        assert_eq!(
+            keystore.decrypt(TEST_PASSWORD).unwrap().as_slice(),
+            private_key.as_slice()
+        );
+
+        let err = V3Keystore::from_json(r#"{"version": 1, "id": "", "crypto": {}}"#)
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "invalid_version");
+    }
+}
@@ -23,8 +23,10 @@ mod keystore;
 mod signer;
 
 pub use keystore::{
-    key_hash_from_mnemonic, key_hash_from_private_key, Account, Address, HdKeystore, Keystore,
-    KeystoreGuard, Metadata, PrivateKeystore, Source,
+    check_mnemonic_language, eth_address, key_hash_from_mnemonic,
+    key_hash_from_mnemonic_with_passphrase, key_hash_from_private_key, Account, Address,
+    HdKeystore, Keystore, KeystoreGuard, Metadata, PrivateKeystore, Slip39Group, Source,
+    V3Keystore,
 };
 
 pub use signer::{ChainSigner, MessageSigner, TransactionSigner};
@@ -0,0 +1,365 @@
+//! A command line wallet built on the tcx handlers.
+//! Every subcommand is translated to a json param of `call_tcx_api` and run by
+//! `tcx::json_transport::call_json`, so the results are the json form of the api messages.
+//! Passwords and mnemonics are prompted when stdin is a TTY, otherwise they are read from
+//! stdin line by line, before any input file read from stdin.
+#[macro_use]
+extern crate failure;
+
+use std::fs;
+use std::io::{self, BufRead, Read};
+use std::process;
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use serde_json::{json, Value};
+
+use tcx::json_transport::call_json;
+use tcx::TokenCore;
+
+type Result<T> = std::result::Result<T, failure::Error>;
+
+fn app() -> App<'static, 'static> {
+    let id_arg = Arg::with_name("id")
+        .long("id")
+        .takes_value(true)
+        .required(true)
+        .help("wallet id");
+    let name_arg = Arg::with_name("name")
+        .long("name")
+        .takes_value(true)
+        .required(true)
+        .help("wallet name");
+    let hint_arg = Arg::with_name("hint")
+        .long("hint")
+        .takes_value(true)
+        .default_value("")
+        .help("password hint");
+    let overwrite_arg = Arg::with_name("overwrite")
+        .long("overwrite")
+        .help("replace the wallet of the same key");
+    let chain_arg = Arg::with_name("chain")
+        .long("chain")
+        .takes_value(true)
+        .required(true)
+        .help("chain type, e.g. BITCOINCASH, LITECOIN, TRON");
+    let network_arg = Arg::with_name("network")
+        .long("network")
+        .takes_value(true)
+        .default_value("")
+        .help("MAINNET or TESTNET");
+
+    App::new("tcx-cli")
+        .about("Manage the keystores of a tcx wallet dir")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name("wallet-dir")
+                .long("wallet-dir")
+                .takes_value(true)
+                .env("TCX_WALLET_DIR")
+                .help("the dir of the keystore files, default to ~/.tcx/wallets"),
+        )
+        .subcommand(
+            SubCommand::with_name("create")
+                .about("Create a wallet with a new mnemonic")
+                .arg(name_arg.clone())
+                .arg(hint_arg.clone()),
+        )
+        .subcommand(
+            SubCommand::with_name("import")
+                .about("Import a wallet")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("mnemonic")
+                        .about("Import a wallet from a mnemonic")
+                        .arg(name_arg.clone())
+                        .arg(hint_arg.clone())
+                        .arg(overwrite_arg.clone()),
+                )
+                .subcommand(
+                    SubCommand::with_name("private-key")
+                        .about("Import a wallet from a private key")
+                        .arg(name_arg)
+                        .arg(hint_arg)
+                        .arg(overwrite_arg)
+                        .arg(
+                            Arg::with_name("encoding")
+                                .long("encoding")
+                                .takes_value(true)
+                                .default_value("")
+                                .help("TEZOS for a tezos private key"),
+                        ),
+                ),
+        )
+        .subcommand(SubCommand::with_name("list").about("List the wallets and their accounts"))
+        .subcommand(
+            SubCommand::with_name("derive")
+                .about("Derive an account of a chain")
+                .arg(id_arg.clone())
+                .arg(chain_arg.clone())
+                .arg(network_arg.clone())
+                .arg(
+                    Arg::with_name("path")
+                        .long("path")
+                        .takes_value(true)
+                        .required(true)
+                        .help("derivation path, e.g. m/44'/195'/0'/0/0"),
+                )
+                .arg(
+                    Arg::with_name("seg-wit")
+                        .long("seg-wit")
+                        .takes_value(true)
+                        .default_value("")
+                        .help("NONE or P2WPKH for the bitcoin forks"),
+                )
+                .arg(
+                    Arg::with_name("curve")
+                        .long("curve")
+                        .takes_value(true)
+                        .default_value(""),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("export")
+                .about("Export the secret of a wallet")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("mnemonic")
+                        .about("Export the mnemonic")
+                        .arg(id_arg.clone()),
+                )
+                .subcommand(
+                    SubCommand::with_name("private-key")
+                        .about("Export the private key of an account")
+                        .arg(id_arg.clone())
+                        .arg(chain_arg.clone())
+                        .arg(network_arg)
+                        .arg(
+                            Arg::with_name("address")
+                                .long("address")
+                                .takes_value(true)
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::with_name("path")
+                                .long("path")
+                                .takes_value(true)
+                                .default_value(""),
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("sign")
+                .about("Sign a transaction offline")
+                .arg(id_arg)
+                .arg(chain_arg)
+                .arg(
+                    Arg::with_name("address")
+                        .long("address")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("input")
+                        .long("input")
+                        .takes_value(true)
+                        .default_value("-")
+                        .help("the json tx input file, - for stdin"),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .takes_value(true)
+                        .help("write the signed tx to the file instead of stdout"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("call")
+                .about("Call any api method with a json param")
+                .arg(Arg::with_name("method").required(true))
+                .arg(
+                    Arg::with_name("param")
+                        .default_value("-")
+                        .help("the json param file, - for stdin"),
+                ),
+        )
+}
+
+fn main() {
+    let matches = app().get_matches();
+    match run(&matches) {
+        Ok(ret) => println!("{}", to_pretty_json(&ret)),
+        Err(err) => {
+            let rsp = json!({ "isSuccess": false, "error": err.to_string() });
+            eprintln!("{}", to_pretty_json(&rsp));
+            process::exit(1);
+        }
+    }
+}
+
+fn run(matches: &ArgMatches) -> Result<Value> {
+    let wallet_dir = match matches.value_of("wallet-dir") {
+        Some(dir) => dir.to_string(),
+        None => dirs::home_dir()
+            .ok_or_else(|| format_err!("home_dir_not_found"))?
+            .join(".tcx/wallets")
+            .to_string_lossy()
+            .to_string(),
+    };
+    fs::create_dir_all(&wallet_dir)?;
+    let token_core = TokenCore::open(&wallet_dir)?;
+
+    match matches.subcommand() {
+        ("create", Some(m)) => {
+            let password = read_new_password()?;
+            call_json(
+                &token_core,
+                "hd_store_create",
+                json!({
+                    "password": password,
+                    "passwordHint": m.value_of("hint"),
+                    "name": m.value_of("name"),
+                }),
+            )
+        }
+        ("import", Some(m)) => match m.subcommand() {
+            ("mnemonic", Some(m)) => {
+                let mnemonic = read_secret("Mnemonic: ")?;
+                let password = read_new_password()?;
+                call_json(
+                    &token_core,
+                    "hd_store_import",
+                    json!({
+                        "mnemonic": mnemonic,
+                        "password": password,
+                        "source": "MNEMONIC",
+                        "name": m.value_of("name"),
+                        "passwordHint": m.value_of("hint"),
+                        "overwrite": m.is_present("overwrite"),
+                    }),
+                )
+            }
+            ("private-key", Some(m)) => {
+                let private_key = read_secret("Private key: ")?;
+                let password = read_new_password()?;
+                call_json(
+                    &token_core,
+                    "private_key_store_import",
+                    json!({
+                        "privateKey": private_key,
+                        "password": password,
+                        "name": m.value_of("name"),
+                        "passwordHint": m.value_of("hint"),
+                        "overwrite": m.is_present("overwrite"),
+                        "encoding": m.value_of("encoding"),
+                    }),
+                )
+            }
+            _ => unreachable!(),
+        },
+        ("list", Some(_)) => Ok(serde_json::to_value(token_core.list_wallets()?)?),
+        ("derive", Some(m)) => {
+            let password = read_secret("Password: ")?;
+            call_json(
+                &token_core,
+                "keystore_common_derive",
+                json!({
+                    "id": m.value_of("id"),
+                    "password": password,
+                    "derivations": [{
+                        "chainType": m.value_of("chain"),
+                        "path": m.value_of("path"),
+                        "network": m.value_of("network"),
+                        "segWit": m.value_of("seg-wit"),
+                        "curve": m.value_of("curve"),
+                    }],
+                }),
+            )
+        }
+        ("export", Some(m)) => match m.subcommand() {
+            ("mnemonic", Some(m)) => {
+                let password = read_secret("Password: ")?;
+                call_json(
+                    &token_core,
+                    "export_mnemonic",
+                    json!({ "id": m.value_of("id"), "password": password }),
+                )
+            }
+            ("private-key", Some(m)) => {
+                let password = read_secret("Password: ")?;
+                call_json(
+                    &token_core,
+                    "export_private_key",
+                    json!({
+                        "id": m.value_of("id"),
+                        "password": password,
+                        "chainType": m.value_of("chain"),
+                        "network": m.value_of("network"),
+                        "mainAddress": m.value_of("address"),
+                        "path": m.value_of("path"),
+                    }),
+                )
+            }
+            _ => unreachable!(),
+        },
+        ("sign", Some(m)) => {
+            let password = read_secret("Password: ")?;
+            let input = read_json_file(m.value_of("input").unwrap())?;
+            let signed_tx = call_json(
+                &token_core,
+                "sign_tx",
+                json!({
+                    "id": m.value_of("id"),
+                    "key": { "password": password },
+                    "chainType": m.value_of("chain"),
+                    "address": m.value_of("address"),
+                    "input": input,
+                }),
+            )?;
+            match m.value_of("output") {
+                Some(output) => {
+                    fs::write(output, to_pretty_json(&signed_tx))?;
+                    Ok(json!({ "isSuccess": true, "error": "" }))
+                }
+                None => Ok(signed_tx),
+            }
+        }
+        ("call", Some(m)) => {
+            let param = read_json_file(m.value_of("param").unwrap())?;
+            call_json(&token_core, m.value_of("method").unwrap(), param)
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn to_pretty_json(value: &Value) -> String {
+    serde_json::to_string_pretty(value).expect("json")
+}
+
+fn read_json_file(path: &str) -> Result<Value> {
+    let content = if path == "-" {
+        let mut content = String::new();
+        io::stdin().read_to_string(&mut content)?;
+        content
+    } else {
+        fs::read_to_string(path)?
+    };
+    Ok(serde_json::from_str(&content)?)
+}
+
+fn read_secret(prompt: &str) -> Result<String> {
+    if atty::is(atty::Stream::Stdin) {
+        Ok(rpassword::read_password_from_tty(Some(prompt))?)
+    } else {
+        let mut line = String::new();
+        io::stdin().lock().read_line(&mut line)?;
+        Ok(line.trim_end_matches(&['\r', '\n'][..]).to_string())
+    }
+}
+
+fn read_new_password() -> Result<String> {
+    let password = read_secret("Password: ")?;
+    if atty::is(atty::Stream::Stdin) && read_secret("Repeat password: ")? != password {
+        return Err(format_err!("password_not_match"));
+    }
+    Ok(password)
+}
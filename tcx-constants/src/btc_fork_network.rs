@@ -1,65 +1,82 @@
-use crate::CoinInfo;
+use crate::{CoinInfo, Result};
+use failure::format_err;
 use parking_lot::RwLock;
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BtcForkNetwork {
-    pub coin: &'static str,
-    pub network: &'static str,
-    pub seg_wit: &'static str,
-    pub hrp: &'static str,
+    pub coin: String,
+    pub network: String,
+    pub seg_wit: String,
+    pub hrp: String,
     pub p2pkh_prefix: u8,
     pub p2sh_prefix: u8,
     pub private_prefix: u8,
     pub xpub_prefix: [u8; 4],
     pub xprv_prefix: [u8; 4],
+    /// The smallest output the network relays, in the smallest unit of the coin
+    pub dust_threshold: u64,
+    /// The largest fee signed unless the tx input allows an absurd fee, in the smallest unit
+    pub max_fee: u64,
 }
 
+/// The bitcoin dust of a p2pkh output at the default relay fee of 3 satoshis per byte
+pub const DEFAULT_DUST_THRESHOLD: u64 = 546;
+
+/// 0.1 coin, the `-maxtxfee` default of bitcoin core and the forks following it
+pub const DEFAULT_MAX_FEE: u64 = 10_000_000;
+
 pub struct HdVersion {
-    pub_version: [u8; 4],
-    prv_version: [u8; 4],
+    pub(crate) pub_version: [u8; 4],
+    pub(crate) prv_version: [u8; 4],
 }
 
 lazy_static! {
-    static ref BTC_FORK_NETWORKS: RwLock<Vec<BtcForkNetwork>> = {
+    pub(crate) static ref BTC_FORK_NETWORKS: RwLock<Vec<BtcForkNetwork>> = {
         let mut networks = Vec::new();
         networks.push(BtcForkNetwork {
-            coin: "LITECOIN",
-            network: "MAINNET",
-            seg_wit: "NONE",
-            hrp: "",
+            coin: "LITECOIN".to_string(),
+            network: "MAINNET".to_string(),
+            seg_wit: "NONE".to_string(),
+            hrp: "".to_string(),
             p2pkh_prefix: 0x30,
             p2sh_prefix: 0x32,
             private_prefix: 0xb0,
             xpub_prefix: [0x04, 0x88, 0xB2, 0x1E],
             xprv_prefix: [0x04, 0x88, 0xAD, 0xE4],
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            max_fee: DEFAULT_MAX_FEE,
         });
         networks.push(BtcForkNetwork {
-            coin: "LITECOIN",
-            network: "MAINNET",
-            seg_wit: "P2WPKH",
-            hrp: "",
+            coin: "LITECOIN".to_string(),
+            network: "MAINNET".to_string(),
+            seg_wit: "P2WPKH".to_string(),
+            hrp: "".to_string(),
             p2pkh_prefix: 0x30,
             p2sh_prefix: 0x32,
             private_prefix: 0xb0,
             xpub_prefix: [0x04, 0x88, 0xB2, 0x1E],
             xprv_prefix: [0x04, 0x88, 0xAD, 0xE4],
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            max_fee: DEFAULT_MAX_FEE,
         });
         networks.push(BtcForkNetwork {
-            coin: "LITECOIN",
-            network: "MAINNET",
-            seg_wit: "SEGWIT",
-            hrp: "ltc",
+            coin: "LITECOIN".to_string(),
+            network: "MAINNET".to_string(),
+            seg_wit: "SEGWIT".to_string(),
+            hrp: "ltc".to_string(),
             p2pkh_prefix: 0x30,
             p2sh_prefix: 0x32,
             private_prefix: 0xb0,
             xpub_prefix: [0x04, 0x88, 0xB2, 0x1E],
             xprv_prefix: [0x04, 0x88, 0xAD, 0xE4],
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            max_fee: DEFAULT_MAX_FEE,
         });
         networks.push(BtcForkNetwork {
-            coin: "LITECOIN",
-            network: "TESTNET",
-            seg_wit: "NONE",
-            hrp: "",
+            coin: "LITECOIN".to_string(),
+            network: "TESTNET".to_string(),
+            seg_wit: "NONE".to_string(),
+            hrp: "".to_string(),
             p2pkh_prefix: 0x6f,
             p2sh_prefix: 0x3a,
             private_prefix: 0xef,
@@ -67,100 +84,175 @@ lazy_static! {
             //            04358394
             xpub_prefix: [0x04, 0x35, 0x87, 0xCF],
             xprv_prefix: [0x04, 0x35, 0x83, 0x94],
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            max_fee: DEFAULT_MAX_FEE,
         });
         networks.push(BtcForkNetwork {
-            coin: "LITECOIN",
-            network: "TESTNET",
-            seg_wit: "P2WPKH",
-            hrp: "",
+            coin: "LITECOIN".to_string(),
+            network: "TESTNET".to_string(),
+            seg_wit: "P2WPKH".to_string(),
+            hrp: "".to_string(),
             p2pkh_prefix: 0x6f,
             p2sh_prefix: 0x3a,
             private_prefix: 0xef,
             xpub_prefix: [0x04, 0x35, 0x87, 0xCF],
             xprv_prefix: [0x04, 0x35, 0x83, 0x94],
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            max_fee: DEFAULT_MAX_FEE,
         });
         networks.push(BtcForkNetwork {
-            coin: "BITCOIN",
-            network: "MAINNET",
-            seg_wit: "NONE",
-            hrp: "",
+            coin: "BITCOIN".to_string(),
+            network: "MAINNET".to_string(),
+            seg_wit: "NONE".to_string(),
+            hrp: "".to_string(),
             p2pkh_prefix: 0x0,
             p2sh_prefix: 0x05,
             private_prefix: 0x80,
             xpub_prefix: [0x04, 0x88, 0xB2, 0x1E],
             xprv_prefix: [0x04, 0x88, 0xAD, 0xE4],
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            max_fee: DEFAULT_MAX_FEE,
         });
         networks.push(BtcForkNetwork {
-            coin: "BITCOIN",
-            network: "MAINNET",
-            seg_wit: "P2WPKH",
-            hrp: "",
+            coin: "BITCOIN".to_string(),
+            network: "MAINNET".to_string(),
+            seg_wit: "P2WPKH".to_string(),
+            hrp: "".to_string(),
             p2pkh_prefix: 0x0,
             p2sh_prefix: 0x05,
             private_prefix: 0x80,
             xpub_prefix: [0x04, 0x88, 0xB2, 0x1E],
             xprv_prefix: [0x04, 0x88, 0xAD, 0xE4],
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            max_fee: DEFAULT_MAX_FEE,
         });
         networks.push(BtcForkNetwork {
-            coin: "BITCOIN",
-            network: "MAINNET",
-            seg_wit: "SEGWIT",
-            hrp: "bc",
+            coin: "BITCOIN".to_string(),
+            network: "MAINNET".to_string(),
+            seg_wit: "SEGWIT".to_string(),
+            hrp: "bc".to_string(),
             p2pkh_prefix: 0x0,
             p2sh_prefix: 0x05,
             private_prefix: 0x80,
             xpub_prefix: [0x04, 0x88, 0xB2, 0x1E],
             xprv_prefix: [0x04, 0x88, 0xAD, 0xE4],
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            max_fee: DEFAULT_MAX_FEE,
         });
         networks.push(BtcForkNetwork {
-            coin: "BITCOIN",
-            network: "TESTNET",
-            seg_wit: "NONE",
-            hrp: "",
+            coin: "BITCOIN".to_string(),
+            network: "TESTNET".to_string(),
+            seg_wit: "NONE".to_string(),
+            hrp: "".to_string(),
             p2pkh_prefix: 0x6f,
             p2sh_prefix: 0xc4,
             private_prefix: 0xef,
             xpub_prefix: [0x04, 0x35, 0x87, 0xCF],
             xprv_prefix: [0x04, 0x35, 0x83, 0x94],
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            max_fee: DEFAULT_MAX_FEE,
         });
         networks.push(BtcForkNetwork {
-            coin: "BITCOIN",
-            network: "TESTNET",
-            seg_wit: "P2WPKH",
-            hrp: "",
+            coin: "BITCOIN".to_string(),
+            network: "TESTNET".to_string(),
+            seg_wit: "P2WPKH".to_string(),
+            hrp: "".to_string(),
             p2pkh_prefix: 0x6f,
             p2sh_prefix: 0xc4,
             private_prefix: 0xef,
             xpub_prefix: [0x04, 0x35, 0x87, 0xCF],
             xprv_prefix: [0x04, 0x35, 0x83, 0x94],
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            max_fee: DEFAULT_MAX_FEE,
         });
         //Definition of BitcoinCash networks https://github.com/bitpay/bitcore/blob/master/packages/bitcore-lib-cash/lib/networks.js#L168
         networks.push(BtcForkNetwork {
-            coin: "BITCOINCASH",
-            network: "MAINNET",
-            seg_wit: "NONE",
-            hrp: "bitcoincash",
+            coin: "BITCOINCASH".to_string(),
+            network: "MAINNET".to_string(),
+            seg_wit: "NONE".to_string(),
+            hrp: "bitcoincash".to_string(),
             p2pkh_prefix: 0x0,
             p2sh_prefix: 0x05,
             private_prefix: 0x80,
             xpub_prefix: [0x04, 0x88, 0xB2, 0x1E],
             xprv_prefix: [0x04, 0x88, 0xAD, 0xE4],
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            max_fee: DEFAULT_MAX_FEE,
         });
         networks.push(BtcForkNetwork {
-            coin: "BITCOINCASH",
-            network: "TESTNET",
-            seg_wit: "NONE",
-            hrp: "bitcoincash",
+            coin: "BITCOINCASH".to_string(),
+            network: "TESTNET".to_string(),
+            seg_wit: "NONE".to_string(),
+            hrp: "bitcoincash".to_string(),
             p2pkh_prefix: 0x6f,
             p2sh_prefix: 0xc4,
             private_prefix: 0xef,
             xpub_prefix: [0x04, 0x35, 0x87, 0xCF],
             xprv_prefix: [0x04, 0x35, 0x83, 0x94],
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            max_fee: DEFAULT_MAX_FEE,
+        });
+        // Dogecoin and Dash have no segwit, https://github.com/dogecoin/dogecoin/blob/master/src/chainparams.cpp
+        // https://github.com/dashpay/dash/blob/master/src/chainparams.cpp
+        // the dogecoin dust is 0.01 DOGE, https://github.com/dogecoin/dogecoin/blob/master/doc/fee-recommendation.md
+        // dash core computes the dust the same way as bitcoin core, 546 duffs for p2pkh
+        // dogecoin core caps the fee at 100 DOGE, dash at 0.1 DASH like bitcoin
+        networks.push(BtcForkNetwork {
+            coin: "DOGECOIN".to_string(),
+            network: "MAINNET".to_string(),
+            seg_wit: "NONE".to_string(),
+            hrp: "".to_string(),
+            p2pkh_prefix: 0x1e,
+            p2sh_prefix: 0x16,
+            private_prefix: 0x9e,
+            xpub_prefix: [0x02, 0xFA, 0xCA, 0xFD],
+            xprv_prefix: [0x02, 0xFA, 0xC3, 0x98],
+            dust_threshold: 1_000_000,
+            max_fee: 10_000_000_000,
+        });
+        networks.push(BtcForkNetwork {
+            coin: "DOGECOIN".to_string(),
+            network: "TESTNET".to_string(),
+            seg_wit: "NONE".to_string(),
+            hrp: "".to_string(),
+            p2pkh_prefix: 0x71,
+            p2sh_prefix: 0xc4,
+            private_prefix: 0xf1,
+            xpub_prefix: [0x04, 0x35, 0x87, 0xCF],
+            xprv_prefix: [0x04, 0x35, 0x83, 0x94],
+            dust_threshold: 1_000_000,
+            max_fee: 10_000_000_000,
+        });
+        networks.push(BtcForkNetwork {
+            coin: "DASH".to_string(),
+            network: "MAINNET".to_string(),
+            seg_wit: "NONE".to_string(),
+            hrp: "".to_string(),
+            p2pkh_prefix: 0x4c,
+            p2sh_prefix: 0x10,
+            private_prefix: 0xcc,
+            xpub_prefix: [0x04, 0x88, 0xB2, 0x1E],
+            xprv_prefix: [0x04, 0x88, 0xAD, 0xE4],
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            max_fee: DEFAULT_MAX_FEE,
+        });
+        networks.push(BtcForkNetwork {
+            coin: "DASH".to_string(),
+            network: "TESTNET".to_string(),
+            seg_wit: "NONE".to_string(),
+            hrp: "".to_string(),
+            p2pkh_prefix: 0x8c,
+            p2sh_prefix: 0x13,
+            private_prefix: 0xef,
+            xpub_prefix: [0x04, 0x35, 0x87, 0xCF],
+            xprv_prefix: [0x04, 0x35, 0x83, 0x94],
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            max_fee: DEFAULT_MAX_FEE,
         });
         RwLock::new(networks)
     };
 
-    static ref HD_VERSIONS: RwLock<Vec<HdVersion>> = {
+    pub(crate) static ref HD_VERSIONS: RwLock<Vec<HdVersion>> = {
         let mut versions = Vec::new();
         versions.push(HdVersion {
             pub_version: [0x04, 0x88, 0xB2, 0x1E],
@@ -170,6 +262,10 @@ lazy_static! {
             pub_version: [0x04, 0x35, 0x87, 0xCF],
             prv_version: [0x04, 0x35, 0x83, 0x94],
         });
+        versions.push(HdVersion {
+            pub_version: [0x02, 0xFA, 0xCA, 0xFD],
+            prv_version: [0x02, 0xFA, 0xC3, 0x98],
+        });
         RwLock::new(versions)
     };
 }
@@ -209,6 +305,15 @@ pub fn network_form_hrp(hrp: &str) -> Option<BtcForkNetwork> {
     ret.pop()
 }
 
+/// The first network whose p2pkh or p2sh prefix is the version byte of a base58 address
+pub fn network_from_base58_prefix(prefix: u8) -> Option<BtcForkNetwork> {
+    let networks = BTC_FORK_NETWORKS.read();
+    networks
+        .iter()
+        .find(|x| x.p2pkh_prefix == prefix || x.p2sh_prefix == prefix)
+        .cloned()
+}
+
 pub fn coin_from_xpub_prefix(prefix: &[u8]) -> Option<String> {
     let networks = BTC_FORK_NETWORKS.read();
     networks
@@ -225,3 +330,48 @@ pub fn pub_version_from_prv_version(prefix: &[u8]) -> Option<[u8; 4]> {
         version
     })
 }
+
+/// Whether the network is missing from `networks`. The same network again is not, one with the
+/// chain type, network and seg wit of a known one but other prefixes is refused. So are a hrp or a
+/// base58 prefix of another coin or network, since addresses are matched to their network by them
+pub(crate) fn is_new_btc_fork_network(
+    networks: &[BtcForkNetwork],
+    network: &BtcForkNetwork,
+) -> Result<bool> {
+    let exist = networks.iter().find(|x| {
+        x.coin == network.coin && x.network == network.network && x.seg_wit == network.seg_wit
+    });
+    match exist {
+        Some(exist) if exist == network => return Ok(false),
+        Some(_) => return Err(format_err!("btc_fork_network_conflict")),
+        None => {}
+    }
+    if !network.hrp.is_empty()
+        && networks
+            .iter()
+            .any(|x| x.hrp == network.hrp && x.coin != network.coin)
+    {
+        return Err(format_err!("btc_fork_hrp_conflict"));
+    }
+    let prefixes = [network.p2pkh_prefix, network.p2sh_prefix];
+    if networks.iter().any(|x| {
+        (x.coin != network.coin || x.network != network.network)
+            && (prefixes.contains(&x.p2pkh_prefix) || prefixes.contains(&x.p2sh_prefix))
+    }) {
+        return Err(format_err!("btc_fork_prefix_conflict"));
+    }
+    Ok(true)
+}
+
+/// Whether the chain type has a bitcoin fork network, built in or registered
+pub fn is_btc_fork(chain_type: &str) -> bool {
+    BTC_FORK_NETWORKS
+        .read()
+        .iter()
+        .any(|x| x.coin == chain_type.to_uppercase())
+}
+
+/// Every supported network, the built in ones first
+pub fn btc_fork_networks() -> Vec<BtcForkNetwork> {
+    BTC_FORK_NETWORKS.read().clone()
+}
@@ -7,7 +7,7 @@ use parking_lot::RwLock;
 /// Blockchain basic config
 ///
 /// NOTE: Unique key field is `symbol`
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct CoinInfo {
     pub coin: String,
     pub derivation_path: String,
@@ -17,7 +17,7 @@ pub struct CoinInfo {
 }
 
 lazy_static! {
-    static ref COIN_INFOS: RwLock<Vec<CoinInfo>> = {
+    pub(crate) static ref COIN_INFOS: RwLock<Vec<CoinInfo>> = {
         let mut coin_infos = Vec::new();
         coin_infos.push(CoinInfo {
             coin: "BITCOIN".to_string(),
@@ -89,6 +89,34 @@ lazy_static! {
             network: "TESTNET".to_string(),
             seg_wit: "P2WPKH".to_string(),
         });
+        coin_infos.push(CoinInfo {
+            coin: "DOGECOIN".to_string(),
+            derivation_path: "m/44'/3'/0'/0/0".to_string(),
+            curve: CurveType::SECP256k1,
+            network: "MAINNET".to_string(),
+            seg_wit: "NONE".to_string(),
+        });
+        coin_infos.push(CoinInfo {
+            coin: "DOGECOIN".to_string(),
+            derivation_path: "m/44'/1'/0'/0/0".to_string(),
+            curve: CurveType::SECP256k1,
+            network: "TESTNET".to_string(),
+            seg_wit: "NONE".to_string(),
+        });
+        coin_infos.push(CoinInfo {
+            coin: "DASH".to_string(),
+            derivation_path: "m/44'/5'/0'/0/0".to_string(),
+            curve: CurveType::SECP256k1,
+            network: "MAINNET".to_string(),
+            seg_wit: "NONE".to_string(),
+        });
+        coin_infos.push(CoinInfo {
+            coin: "DASH".to_string(),
+            derivation_path: "m/44'/1'/0'/0/0".to_string(),
+            curve: CurveType::SECP256k1,
+            network: "TESTNET".to_string(),
+            seg_wit: "NONE".to_string(),
+        });
         coin_infos.push(CoinInfo {
             coin: "TRON".to_string(),
             derivation_path: "m/44'/195'/0'/0/0".to_string(),
@@ -164,14 +192,83 @@ lazy_static! {
     };
 }
 
-pub fn coin_info_from_param(
+/// The values `coin_info_from_param` takes for the params left empty, an empty value has no
+/// default
+struct CoinDefault {
+    coin: &'static str,
+    network: &'static str,
+    seg_wit: &'static str,
+    curve: &'static str,
+}
+
+const COIN_DEFAULTS: [CoinDefault; 8] = [
+    CoinDefault {
+        coin: "BITCOIN",
+        network: "MAINNET",
+        seg_wit: "NONE",
+        curve: "",
+    },
+    CoinDefault {
+        coin: "BITCOINCASH",
+        network: "MAINNET",
+        seg_wit: "NONE",
+        curve: "",
+    },
+    CoinDefault {
+        coin: "LITECOIN",
+        network: "MAINNET",
+        seg_wit: "NONE",
+        curve: "",
+    },
+    CoinDefault {
+        coin: "DOGECOIN",
+        network: "MAINNET",
+        seg_wit: "NONE",
+        curve: "",
+    },
+    CoinDefault {
+        coin: "DASH",
+        network: "MAINNET",
+        seg_wit: "NONE",
+        curve: "",
+    },
+    CoinDefault {
+        coin: "NERVOS",
+        network: "MAINNET",
+        seg_wit: "",
+        curve: "",
+    },
+    CoinDefault {
+        coin: "TEZOS",
+        network: "MAINNET",
+        seg_wit: "",
+        curve: "",
+    },
+    CoinDefault {
+        coin: "FILECOIN",
+        network: "MAINNET",
+        seg_wit: "",
+        curve: "SECP256k1",
+    },
+];
+
+fn or_default<'a>(value: &'a str, default: &'a str) -> &'a str {
+    if value.is_empty() {
+        default
+    } else {
+        value
+    }
+}
+
+/// Every coin matching the params, an empty param matches any value
+pub fn coin_infos_from_param(
     chain_type: &str,
     network: &str,
     seg_wit: &str,
     curve: &str,
-) -> Result<CoinInfo> {
+) -> Vec<CoinInfo> {
     let coin_infos = COIN_INFOS.read();
-    let mut coins = coin_infos
+    coin_infos
         .iter()
         .filter(|x| {
             x.coin.as_str() == chain_type
@@ -179,12 +276,52 @@ pub fn coin_info_from_param(
                 && (x.seg_wit.as_str() == seg_wit || seg_wit.is_empty())
                 && (x.curve.as_str() == curve || curve.is_empty())
         })
-        .map(|x| x.clone())
-        .collect::<Vec<CoinInfo>>();
+        .cloned()
+        .collect()
+}
 
-    if coins.is_empty() {
-        Err(format_err!("unsupported_chain"))
-    } else {
-        Ok(coins.pop().expect("coin_info_from_param"))
+/// The coin matching the params, an empty param takes the default of the chain when it has one.
+/// Fails with `coin_info_ambiguous` when more than one coin still matches
+pub fn coin_info_from_param(
+    chain_type: &str,
+    network: &str,
+    seg_wit: &str,
+    curve: &str,
+) -> Result<CoinInfo> {
+    let mut coins = match COIN_DEFAULTS.iter().find(|x| x.coin == chain_type) {
+        Some(default) => coin_infos_from_param(
+            chain_type,
+            or_default(network, default.network),
+            or_default(seg_wit, default.seg_wit),
+            or_default(curve, default.curve),
+        ),
+        None => coin_infos_from_param(chain_type, network, seg_wit, curve),
+    };
+
+    match coins.len() {
+        0 => Err(format_err!("unsupported_chain")),
+        1 => Ok(coins.pop().expect("coin_info_from_param")),
+        _ => Err(format_err!("coin_info_ambiguous")),
     }
 }
+
+/// Whether the coin is missing from `coin_infos`. The same coin again is not, a coin with the
+/// chain type, network, seg wit and curve of a known one but another path is refused
+pub(crate) fn is_new_coin_info(coin_infos: &[CoinInfo], coin_info: &CoinInfo) -> Result<bool> {
+    let exist = coin_infos.iter().find(|x| {
+        x.coin == coin_info.coin
+            && x.network == coin_info.network
+            && x.seg_wit == coin_info.seg_wit
+            && x.curve == coin_info.curve
+    });
+    match exist {
+        Some(exist) if exist == coin_info => Ok(false),
+        Some(_) => Err(format_err!("coin_info_conflict")),
+        None => Ok(true),
+    }
+}
+
+/// Every supported coin, the built in ones first
+pub fn coin_infos() -> Vec<CoinInfo> {
+    COIN_INFOS.read().clone()
+}
@@ -1,4 +1,7 @@
+use crate::Result;
+use failure::format_err;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum CurveType {
@@ -24,3 +27,21 @@ impl CurveType {
         }
     }
 }
+
+impl FromStr for CurveType {
+    type Err = failure::Error;
+
+    /// The names of `as_str`
+    fn from_str(name: &str) -> Result<Self> {
+        match name {
+            "SECP256k1" => Ok(CurveType::SECP256k1),
+            "ED25519" => Ok(CurveType::ED25519),
+            "ED25519Blake2bNano" => Ok(CurveType::ED25519Blake2bNano),
+            "SubSr25519" => Ok(CurveType::SubSr25519),
+            "Curve25519" => Ok(CurveType::Curve25519),
+            "NIST256p1" => Ok(CurveType::NIST256p1),
+            "BLS" => Ok(CurveType::BLS),
+            _ => Err(format_err!("invalid_curve_type")),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! The proto3 json mapping of int64 and uint64, the values are written as json strings so js
+//! hosts don't lose the precision above 2^53. Json numbers are still accepted
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber<T> {
+    String(String),
+    Number(T),
+}
+
+pub fn serialize<T, S>(value: &T, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    T: FromStr + Deserialize<'de>,
+    T::Err: Display,
+    D: Deserializer<'de>,
+{
+    match StringOrNumber::<T>::deserialize(deserializer)? {
+        StringOrNumber::String(value) => value.parse().map_err(D::Error::custom),
+        StringOrNumber::Number(value) => Ok(value),
+    }
+}
@@ -1,13 +1,17 @@
 pub mod btc_fork_network;
 pub mod coin_info;
 pub mod curve;
+pub mod json_int64;
+pub mod registry;
 
 pub use btc_fork_network::{
-    coin_from_xpub_prefix, network_form_hrp, network_from_coin, pub_version_from_prv_version,
-    BtcForkNetwork,
+    btc_fork_networks, coin_from_xpub_prefix, is_btc_fork, network_form_hrp,
+    network_from_base58_prefix, network_from_coin, pub_version_from_prv_version, BtcForkNetwork,
+    DEFAULT_DUST_THRESHOLD, DEFAULT_MAX_FEE,
 };
-pub use coin_info::{coin_info_from_param, CoinInfo};
+pub use coin_info::{coin_info_from_param, coin_infos, coin_infos_from_param, CoinInfo};
 pub use curve::CurveType;
+pub use registry::add_coins;
 
 pub type Result<T> = std::result::Result<T, failure::Error>;
 
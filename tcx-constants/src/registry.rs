@@ -0,0 +1,46 @@
+//! The coins and bitcoin fork networks added at runtime. Like the built in ones they are process
+//! wide, every thread and every token core instance sees the same registry
+use crate::btc_fork_network::{is_new_btc_fork_network, HdVersion, BTC_FORK_NETWORKS, HD_VERSIONS};
+use crate::coin_info::{is_new_coin_info, COIN_INFOS};
+use crate::{BtcForkNetwork, CoinInfo, Result};
+
+/// Adds the coins and networks next to the built in ones. The registry stays write locked while
+/// every entry is checked against it and against the other entries, so either all of them are
+/// added or none is
+pub fn add_coins(coin_infos: Vec<CoinInfo>, networks: Vec<BtcForkNetwork>) -> Result<()> {
+    let mut registered_coins = COIN_INFOS.write();
+    let mut registered_networks = BTC_FORK_NETWORKS.write();
+    let mut versions = HD_VERSIONS.write();
+
+    let mut new_coins: Vec<CoinInfo> = vec![];
+    for coin_info in coin_infos {
+        if is_new_coin_info(&registered_coins, &coin_info)?
+            && is_new_coin_info(&new_coins, &coin_info)?
+        {
+            new_coins.push(coin_info);
+        }
+    }
+    let mut new_networks: Vec<BtcForkNetwork> = vec![];
+    for network in networks {
+        if is_new_btc_fork_network(&registered_networks, &network)?
+            && is_new_btc_fork_network(&new_networks, &network)?
+        {
+            new_networks.push(network);
+        }
+    }
+
+    registered_coins.extend(new_coins);
+    for network in new_networks {
+        if !versions
+            .iter()
+            .any(|x| x.prv_version == network.xprv_prefix)
+        {
+            versions.push(HdVersion {
+                pub_version: network.xpub_prefix,
+                prv_version: network.xprv_prefix,
+            });
+        }
+        registered_networks.push(network);
+    }
+    Ok(())
+}
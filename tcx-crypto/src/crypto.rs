@@ -5,6 +5,7 @@ use crate::Result;
 use bitcoin_hashes::hex::{FromHex, ToHex};
 use serde::{Deserialize, Serialize};
 use std::env;
+use zeroize::{Zeroize, Zeroizing};
 
 const CREDENTIAL_LEN: usize = 64usize;
 
@@ -24,6 +25,14 @@ pub enum Key {
     DerivedKey(String),
 }
 
+impl Drop for Key {
+    fn drop(&mut self) {
+        match self {
+            Key::Password(key) | Key::DerivedKey(key) => key.zeroize(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EncPair {
@@ -40,6 +49,8 @@ struct CipherParams {
 pub trait KdfParams: Default {
     fn kdf_key() -> String;
     fn validate(&self) -> Result<()>;
+    /// The work factor, the rounds of pbkdf2 or the n of scrypt
+    fn cost(&self) -> u32;
     fn generate_derived_key(&self, password: &[u8], out: &mut [u8]);
     fn set_salt(&mut self, salt: &str);
 }
@@ -64,6 +75,15 @@ impl Default for Pbkdf2Params {
     }
 }
 
+impl Pbkdf2Params {
+    pub fn with_rounds(c: u32) -> Pbkdf2Params {
+        Pbkdf2Params {
+            c,
+            ..Pbkdf2Params::default()
+        }
+    }
+}
+
 impl KdfParams for Pbkdf2Params {
     fn kdf_key() -> String {
         "pbkdf2".to_owned()
@@ -77,6 +97,10 @@ impl KdfParams for Pbkdf2Params {
         }
     }
 
+    fn cost(&self) -> u32 {
+        self.c
+    }
+
     fn generate_derived_key(&self, password: &[u8], out: &mut [u8]) {
         let salt_bytes: Vec<u8> = FromHex::from_hex(&self.salt).unwrap();
         pbkdf2::pbkdf2::<hmac::Hmac<sha2::Sha256>>(password, &salt_bytes, self.c as usize, out);
@@ -109,6 +133,15 @@ impl Default for SCryptParams {
     }
 }
 
+impl SCryptParams {
+    pub fn with_n(n: u32) -> SCryptParams {
+        SCryptParams {
+            n,
+            ..SCryptParams::default()
+        }
+    }
+}
+
 impl KdfParams for SCryptParams {
     fn kdf_key() -> String {
         "scrypt".to_owned()
@@ -122,6 +155,10 @@ impl KdfParams for SCryptParams {
         }
     }
 
+    fn cost(&self) -> u32 {
+        self.n
+    }
+
     fn generate_derived_key(&self, password: &[u8], out: &mut [u8]) {
         let salt_bytes: Vec<u8> = FromHex::from_hex(&self.salt).unwrap();
         let log_n = (self.n as f64).log2().round();
@@ -136,17 +173,25 @@ impl KdfParams for SCryptParams {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone)]
 struct CacheDerivedKey {
     hashed_key: String,
-    derived_key: Vec<u8>,
+    derived_key: Zeroizing<Vec<u8>>,
+}
+
+impl std::fmt::Debug for CacheDerivedKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheDerivedKey")
+            .field("hashed_key", &self.hashed_key)
+            .finish()
+    }
 }
 
 impl CacheDerivedKey {
     pub fn new(key: &str, derived_key: &[u8]) -> CacheDerivedKey {
         CacheDerivedKey {
             hashed_key: Self::hash(key),
-            derived_key: derived_key.to_vec(),
+            derived_key: Zeroizing::new(derived_key.to_vec()),
         }
     }
 
@@ -154,7 +199,7 @@ impl CacheDerivedKey {
         hex_dsha256(key)
     }
 
-    pub fn get_derived_key(&self, key: &str) -> Result<Vec<u8>> {
+    pub fn get_derived_key(&self, key: &str) -> Result<Zeroizing<Vec<u8>>> {
         if self.hashed_key == Self::hash(key) {
             Ok(self.derived_key.clone())
         } else {
@@ -181,7 +226,12 @@ where
     T: KdfParams,
 {
     pub fn new(password: &str, origin: &[u8]) -> Crypto<T> {
-        let mut param = T::default();
+        Self::new_with_kdf_params(password, origin, T::default())
+    }
+
+    /// Encrypt `origin` with the given kdf params, the salt is always regenerated
+    pub fn new_with_kdf_params(password: &str, origin: &[u8], kdfparams: T) -> Crypto<T> {
+        let mut param = kdfparams;
         param.set_salt(&numberic_util::random_iv(32).to_hex());
         let iv = numberic_util::random_iv(16);
 
@@ -206,21 +256,22 @@ where
         crypto
     }
 
-    pub fn generate_derived_key(&self, key: &str) -> Result<Vec<u8>> {
+    /// The derived key is wiped when the returned value is dropped
+    pub fn generate_derived_key(&self, key: &str) -> Result<Zeroizing<Vec<u8>>> {
         if let Some(ckd) = &self.cached_derived_key {
             ckd.get_derived_key(key)
         } else {
-            let mut derived_key: Credential = [0u8; CREDENTIAL_LEN];
+            let mut derived_key: Zeroizing<Credential> = Zeroizing::new([0u8; CREDENTIAL_LEN]);
             self.kdfparams
-                .generate_derived_key(key.as_bytes(), &mut derived_key);
-            if &self.mac != "" && !self.verify_derived_key(&derived_key) {
+                .generate_derived_key(key.as_bytes(), &mut *derived_key);
+            if &self.mac != "" && !self.verify_derived_key(&*derived_key) {
                 return Err(Error::PasswordIncorrect.into());
             }
-            Ok(derived_key.to_vec())
+            Ok(Zeroizing::new(derived_key.to_vec()))
         }
     }
 
-    pub fn decrypt(&self, key: Key) -> Result<Vec<u8>> {
+    pub fn decrypt(&self, key: Key) -> Result<Zeroizing<Vec<u8>>> {
         let encrypted: Vec<u8> = FromHex::from_hex(&self.ciphertext).expect("ciphertext");
         let iv: Vec<u8> = FromHex::from_hex(&self.cipherparams.iv).expect("iv");
         self.decrypt_data(key, &encrypted, &iv)
@@ -245,7 +296,7 @@ where
         })
     }
 
-    pub fn decrypt_enc_pair(&self, key: Key, enc_pair: &EncPair) -> Result<Vec<u8>> {
+    pub fn decrypt_enc_pair(&self, key: Key, enc_pair: &EncPair) -> Result<Zeroizing<Vec<u8>>> {
         let encrypted: Vec<u8> = FromHex::from_hex(&enc_pair.enc_str).unwrap();
         let iv: Vec<u8> = FromHex::from_hex(&enc_pair.nonce).unwrap();
         self.decrypt_data(key, &encrypted, &iv)
@@ -268,10 +319,10 @@ where
         super::aes::ctr::encrypt_nopadding(origin, key, &iv)
     }
 
-    fn decrypt_data(&self, key: Key, encrypted: &[u8], iv: &[u8]) -> Result<Vec<u8>> {
-        let derived_key: Vec<u8> = match key {
+    fn decrypt_data(&self, key: Key, encrypted: &[u8], iv: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        let derived_key: Zeroizing<Vec<u8>> = match &key {
             Key::Password(password) => {
-                let dk = self.generate_derived_key(&password)?;
+                let dk = self.generate_derived_key(password)?;
                 if !self.verify_derived_key(&dk) {
                     return Err(Error::PasswordIncorrect.into());
                 } else {
@@ -282,7 +333,7 @@ where
                 if !(cfg!(feature = "cache_dk")) {
                     return Err(Error::CachedDkFeatureNotSupport.into());
                 } else {
-                    let dk = hex::decode(dk)?;
+                    let dk = Zeroizing::new(hex::decode(dk)?);
                     if !self.verify_derived_key(&dk) {
                         return Err(Error::DerivedKeyNotMatched.into());
                     } else {
@@ -293,7 +344,7 @@ where
         };
 
         let key = &derived_key[0..16];
-        super::aes::ctr::decrypt_nopadding(encrypted, key, &iv)
+        super::aes::ctr::decrypt_nopadding(encrypted, key, &iv).map(Zeroizing::new)
     }
 
     pub fn verify_derived_key(&self, dk: &[u8]) -> bool {
@@ -303,7 +354,7 @@ where
     }
 
     fn generate_mac(derived_key: &[u8], ciphertext: &[u8]) -> Vec<u8> {
-        let result = [&derived_key[16..32], ciphertext].concat();
+        let result = Zeroizing::new([&derived_key[16..32], ciphertext].concat());
         let keccak256 = tiny_keccak::keccak256(&result);
         keccak256.to_vec()
     }
@@ -336,6 +387,22 @@ mod tests {
         assert_eq!(default, param);
     }
 
+    #[test]
+    pub fn new_with_kdf_params_test() {
+        let crypto: Crypto<Pbkdf2Params> = Crypto::new_with_kdf_params(
+            TEST_PASSWORD,
+            "TokenCoreX".as_bytes(),
+            Pbkdf2Params::with_rounds(1024),
+        );
+        assert_eq!(crypto.kdfparams.c, 1024);
+        assert!(!crypto.kdfparams.salt.is_empty());
+
+        let decrypted = crypto
+            .decrypt(Key::Password(TEST_PASSWORD.to_owned()))
+            .unwrap();
+        assert_eq!("TokenCoreX", String::from_utf8(decrypted.to_vec()).unwrap());
+    }
+
     #[test]
     pub fn new_crypto() {
         let crypto: Crypto<Pbkdf2Params> = Crypto::new(TEST_PASSWORD, "TokenCoreX".as_bytes());
@@ -353,7 +420,10 @@ mod tests {
         let cipher_bytes = crypto
             .decrypt(Key::Password(TEST_PASSWORD.to_owned()))
             .expect("cipher bytes");
-        assert_eq!("TokenCoreX", String::from_utf8(cipher_bytes).unwrap());
+        assert_eq!(
+            "TokenCoreX",
+            String::from_utf8(cipher_bytes.to_vec()).unwrap()
+        );
 
         let ret = crypto.decrypt(Key::Password("WrongPassword".to_owned()));
         assert!(ret.is_err());
@@ -377,7 +447,7 @@ mod tests {
         let decrypted_bytes = crypto
             .decrypt_enc_pair(Key::Password(TEST_PASSWORD.to_owned()), &enc_pair)
             .unwrap();
-        let decrypted = String::from_utf8(decrypted_bytes).unwrap();
+        let decrypted = String::from_utf8(decrypted_bytes.to_vec()).unwrap();
 
         assert_eq!("TokenCoreX", decrypted);
 
@@ -475,7 +545,7 @@ mod tests {
         let result = crypto
             .decrypt(Key::Password("Insecure Pa55w0rd".to_owned()))
             .unwrap();
-        let wif = String::from_utf8(result).unwrap();
+        let wif = String::from_utf8(result.to_vec()).unwrap();
         assert_eq!("L2hfzPyVC1jWH7n2QLTe7tVTb6btg9smp5UVzhEBxLYaSFF7sCZB", wif)
     }
 
@@ -515,6 +585,6 @@ mod tests {
         assert_eq!(format!("{}", ret.err().unwrap()), "password_incorrect");
 
         let ret = cdk.get_derived_key("12345678").unwrap();
-        assert_eq!(hex::encode(ret), "01010101");
+        assert_eq!(hex::encode(ret.as_slice()), "01010101");
     }
 }
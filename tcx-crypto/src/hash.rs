@@ -15,9 +15,13 @@ pub fn str_dsha256(str: &str) -> String {
     hex::encode(dsha256(&key_data))
 }
 
+pub fn keccak256(bytes: &[u8]) -> Vec<u8> {
+    tiny_keccak::keccak256(bytes).to_vec()
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::hash::{dsha256, hex_dsha256, str_dsha256};
+    use crate::hash::{dsha256, hex_dsha256, keccak256, str_dsha256};
 
     #[test]
     fn sha256_test() {
@@ -33,5 +37,9 @@ mod tests {
             str_dsha256("01020304"),
             "26a0f059b048e922a223ff432ce9c87b13df2f25adc8e876a79a15326519fd76"
         );
+        assert_eq!(
+            hex::encode(keccak256(&[])),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
     }
 }
@@ -4,7 +4,7 @@ pub mod hash;
 pub mod numberic_util;
 
 use core::result;
-pub use crypto::{Crypto, EncPair, Key, Pbkdf2Params};
+pub use crypto::{Crypto, EncPair, KdfParams, Key, Pbkdf2Params, SCryptParams};
 use parking_lot::RwLock;
 
 #[macro_use]
@@ -31,9 +31,7 @@ pub enum Error {
 }
 
 lazy_static! {
-    pub static ref XPUB_COMMON_KEY_128: RwLock<String> =
-        RwLock::new("B888D25EC8C12BD5043777B1AC49F872".to_string());
-    pub static ref XPUB_COMMON_IV: RwLock<String> =
-        RwLock::new("9C0C30889CBCC5E01AB5B2BB88715799".to_string());
+    /// The rounds used by `Pbkdf2Params::default`, callers managing several wallets
+    /// should pass explicit params by `Crypto::new_with_kdf_params` instead
     pub static ref KDF_ROUNDS: RwLock<i32> = RwLock::new(262144);
 }
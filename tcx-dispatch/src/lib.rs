@@ -0,0 +1,207 @@
+//! Routes a chain type to the address and transaction types of its chain crate. The ffi library
+//! and the wasm facade both call in here so they support the same set of chains
+use core::result;
+
+use prost::Message;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use tcx_bch::{BchAddress, BchTransaction};
+use tcx_btc_fork::{
+    BtcForkAddress, BtcForkSegWitTransaction, BtcForkSignedTxOutput, BtcForkTransaction,
+    BtcForkTxInput,
+};
+use tcx_chain::{Account, Address, Keystore, TransactionSigner};
+use tcx_ckb::{CkbAddress, CkbTxInput, CkbTxOutput};
+use tcx_constants::coin_info::coin_info_from_param;
+use tcx_constants::{is_btc_fork, CoinInfo};
+#[cfg(feature = "filecoin")]
+use tcx_filecoin::{FilecoinAddress, SignedMessage, UnsignedMessage};
+use tcx_primitive::TypedPublicKey;
+use tcx_substrate::{SubstrateAddress, SubstrateRawTxIn, SubstrateTxOut};
+use tcx_tezos::address::TezosAddress;
+use tcx_tezos::transaction::{TezosRawTxIn, TezosTxOut};
+use tcx_tron::transaction::{TronTxInput, TronTxOutput};
+use tcx_tron::TrxAddress;
+
+#[macro_use]
+extern crate failure;
+
+pub type Result<T> = result::Result<T, failure::Error>;
+
+/// The tx input and output messages are generated by prost with the serde derives, so a codec
+/// can read and write them as either protobuf or json
+pub trait TxMessage: Message + Default + Serialize + DeserializeOwned {}
+
+impl<T: Message + Default + Serialize + DeserializeOwned> TxMessage for T {}
+
+/// Reads the tx input and writes the signed tx in the caller's wire format, protobuf for the ffi
+/// library and json for the wasm facade
+pub trait TxCodec {
+    type Output;
+
+    fn decode<I: TxMessage>(&self) -> Result<I>;
+
+    fn encode<O: TxMessage>(&self, output: O) -> Result<Self::Output>;
+}
+
+pub fn derive_account(
+    keystore: &mut Keystore,
+    chain_type: &str,
+    coin_info: &CoinInfo,
+) -> Result<Account> {
+    match chain_type {
+        "BITCOINCASH" => keystore.derive_coin::<BchAddress>(coin_info),
+        "TRON" => keystore.derive_coin::<TrxAddress>(coin_info),
+        "NERVOS" => keystore.derive_coin::<CkbAddress>(coin_info),
+        "POLKADOT" | "KUSAMA" => keystore.derive_coin::<SubstrateAddress>(coin_info),
+        "TEZOS" => keystore.derive_coin::<TezosAddress>(coin_info),
+        #[cfg(feature = "filecoin")]
+        "FILECOIN" => keystore.derive_coin::<FilecoinAddress>(coin_info),
+        chain_type if is_btc_fork(chain_type) => keystore.derive_coin::<BtcForkAddress>(coin_info),
+        _ => Err(format_err!("unsupported_chain")),
+    }
+}
+
+pub fn address_from_public_key(
+    chain_type: &str,
+    public_key: &TypedPublicKey,
+    coin_info: &CoinInfo,
+) -> Result<String> {
+    match chain_type {
+        "BITCOINCASH" => BchAddress::from_public_key(public_key, coin_info),
+        "TRON" => TrxAddress::from_public_key(public_key, coin_info),
+        "NERVOS" => CkbAddress::from_public_key(public_key, coin_info),
+        "POLKADOT" | "KUSAMA" => SubstrateAddress::from_public_key(public_key, coin_info),
+        "TEZOS" => TezosAddress::from_public_key(public_key, coin_info),
+        #[cfg(feature = "filecoin")]
+        "FILECOIN" => FilecoinAddress::from_public_key(public_key, coin_info),
+        chain_type if is_btc_fork(chain_type) => {
+            BtcForkAddress::from_public_key(public_key, coin_info)
+        }
+        _ => Err(format_err!("unsupported_chain")),
+    }
+}
+
+pub fn sign_tx<C: TxCodec>(
+    keystore: &mut Keystore,
+    chain_type: &str,
+    address: &str,
+    codec: &C,
+) -> Result<C::Output> {
+    match chain_type {
+        "TRON" => sign::<TronTxInput, TronTxOutput, C>(keystore, chain_type, address, codec),
+        "NERVOS" => sign::<CkbTxInput, CkbTxOutput, C>(keystore, chain_type, address, codec),
+        "POLKADOT" | "KUSAMA" => {
+            sign::<SubstrateRawTxIn, SubstrateTxOut, C>(keystore, chain_type, address, codec)
+        }
+        "TEZOS" => sign::<TezosRawTxIn, TezosTxOut, C>(keystore, chain_type, address, codec),
+        #[cfg(feature = "filecoin")]
+        "FILECOIN" => {
+            sign::<UnsignedMessage, SignedMessage, C>(keystore, chain_type, address, codec)
+        }
+        chain_type if is_btc_fork(chain_type) => {
+            let signed_tx =
+                sign_btc_fork_transaction(keystore, chain_type, address, codec.decode()?)?;
+            codec.encode(signed_tx)
+        }
+        _ => Err(format_err!("unsupported_chain")),
+    }
+}
+
+fn sign<I, O, C>(
+    keystore: &mut Keystore,
+    chain_type: &str,
+    address: &str,
+    codec: &C,
+) -> Result<C::Output>
+where
+    I: TxMessage,
+    O: TxMessage,
+    C: TxCodec,
+    Keystore: TransactionSigner<I, O>,
+{
+    let input: I = codec.decode()?;
+    let signed_tx: O = keystore.sign_transaction(chain_type, address, &input)?;
+    codec.encode(signed_tx)
+}
+
+pub fn sign_btc_fork_transaction(
+    keystore: &mut Keystore,
+    chain_type: &str,
+    address: &str,
+    input: BtcForkTxInput,
+) -> Result<BtcForkSignedTxOutput> {
+    let coin = coin_info_from_param(chain_type, &input.network, &input.seg_wit, "")?;
+
+    if chain_type == "BITCOINCASH" {
+        if !BchAddress::is_valid(&input.to, &coin) {
+            return Err(format_err!("address_invalid"));
+        }
+        let tran = BchTransaction::new(input, coin);
+        keystore.sign_transaction(chain_type, address, &tran)
+    } else if coin.seg_wit != "NONE" {
+        if !BtcForkAddress::is_valid(&input.to, &coin) {
+            return Err(format_err!("address_invalid"));
+        }
+        let tran = BtcForkSegWitTransaction::new(input, coin);
+        keystore.sign_transaction(chain_type, address, &tran)
+    } else {
+        if !BtcForkAddress::is_valid(&input.to, &coin) {
+            return Err(format_err!("address_invalid"));
+        }
+        let tran = BtcForkTransaction::new(input, coin);
+        keystore.sign_transaction(chain_type, address, &tran)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sign_btc_fork_transaction;
+    use tcx_btc_fork::{BtcForkTxInput, Utxo};
+    use tcx_chain::Keystore;
+
+    #[test]
+    fn test_sign_btc_fork_default_seg_wit() {
+        let keystore_json = r#"
+        {"id":"ae45d424-31d8-49f7-a601-1272b40c566d","version":11000,"keyHash":"512115eca3ae86646aeb06861d551e403b543509","crypto":{"cipher":"aes-128-ctr","cipherparams":{"iv":"588233984e9576f058bd7bae018eaa38"},"ciphertext":"8a5451c57fed478c7d45f5391659a6fb5fc85a347f1f7aaead450ad4ef4fe434d042d57aa990d850165293609aa746c715c805b236c3d54d86e7dea7d938ce55fcb2684e0eb7e0e6cc7d","kdf":"pbkdf2","kdfparams":{"c":1024,"prf":"hmac-sha256","dklen":32,"salt":"ee656af962155e4e6e763b0883ed0d8cc37c2fa21a7ef01b1d3b18f352f74c69"},"mac":"a661aa444869aac9ea33f066676c6bfb49d079ab986d0ee755f8a1747b2b7f17"},"activeAccounts":[{"address":"mkeNU5nVnozJiaACDELLCsVUc8Wxoh1rQN","derivationPath":"m/44'/1'/0'/0/0","curve":"SECP256k1","coin":"LITECOIN","network":"TESTNET","segWit":"NONE","extPubKey":"036c2b38ad8000000023332f38a77023d3c1a450499c8aeb3db2e666aa2cc6fff7db6797c5d2aef8fc036663443d71127b332c68cd6bffb6c2b5eb4dc6861404ed055dc36a25b8c18020"}],"imTokenMeta":{"name":"LTC-Wallet-1","passwordHint":"","timestamp":1576561805,"source":"MNEMONIC"}}
+        "#;
+        let mut keystore = Keystore::from_json(keystore_json).unwrap();
+        keystore.unlock_by_password("imtoken1").unwrap();
+
+        // an empty seg wit is the legacy default of litecoin, the same tx as tcx-btc-fork's
+        // test_sign_ltc_spend_change
+        let tx_input = BtcForkTxInput {
+            to: "mmuf77YiGckWgfvd32viaj7EKfrUN1FdAz".to_string(),
+            amount: 500000,
+            unspents: vec![Utxo {
+                tx_hash: "c8d5b9bda8a43a8cc04b1271c2fd5d92d45ed00b2a64193f531ee0f05f3afe96"
+                    .to_string(),
+                vout: 1,
+                amount: 894098,
+                address: "mka6ZzaxDdD49uzxMrtz9H8NVxCEoVMHu3".to_string(),
+                script_pub_key: "76a9143770c8c6671d27e2a9f4502d74932bf740c1ff8688ac".to_string(),
+                derived_path: "1/1".to_string(),
+                sequence: 0,
+            }],
+            fee: 5902,
+            change_address_index: 2u32,
+            change_address: "".to_string(),
+            network: "TESTNET".to_string(),
+            seg_wit: "".to_string(),
+            max_fee: 0,
+            max_fee_ratio: 0,
+            allow_absurd_fee: false,
+        };
+        let actual = sign_btc_fork_transaction(
+            &mut keystore,
+            "LITECOIN",
+            "mkeNU5nVnozJiaACDELLCsVUc8Wxoh1rQN",
+            tx_input,
+        )
+        .unwrap();
+        assert_eq!(actual.signature, "010000000196fe3a5ff0e01e533f19642a0bd05ed4925dfdc271124bc08c3aa4a8bdb9d5c8010000006a47304402205ea72018cb1e4e12baf30cbe099524907425053a66f1c8ecfe66e904ee0b51c7022053972e0111e553048fcd73a5f9a6a64253250b0d3dd8b659c5a59c9c889189b0012102397b033dcc33ccd53c13697cc2dd7785cc526c217327f1bb66b08aea171b069dffffffff0220a10700000000001976a914461bf9360ec1bc9fe438df19ef36c7c2bb26ef8288ac64ec0500000000001976a9144cbdedfd376b834eae69decc49c6c877aaaaff1188ac00000000");
+        assert_eq!(actual.change_address, "mnWjBBB4MxY8j4HNynvZP3Z8TSDXw3inPT");
+        assert_eq!(actual.change_address_path, "m/44'/1'/0'/1/2");
+    }
+}
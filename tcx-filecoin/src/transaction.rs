@@ -1,37 +1,57 @@
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct UnsignedMessage {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub to: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub from: std::string::String,
     #[prost(uint64, tag = "3")]
+    #[serde(default)]
+    #[serde(with = "tcx_constants::json_int64")]
     pub nonce: u64,
     #[prost(string, tag = "4")]
+    #[serde(default)]
     pub value: std::string::String,
     #[prost(int64, tag = "5")]
+    #[serde(default)]
+    #[serde(with = "tcx_constants::json_int64")]
     pub gas_limit: i64,
     #[prost(string, tag = "6")]
+    #[serde(default)]
     pub gas_fee_cap: std::string::String,
     #[prost(string, tag = "7")]
+    #[serde(default)]
     pub gas_premium: std::string::String,
     #[prost(uint64, tag = "8")]
+    #[serde(default)]
+    #[serde(with = "tcx_constants::json_int64")]
     pub method: u64,
     #[prost(string, tag = "9")]
+    #[serde(default)]
     pub params: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Signature {
     #[prost(uint32, tag = "1")]
+    #[serde(default)]
     pub r#type: u32,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub data: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SignedMessage {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub cid: std::string::String,
     #[prost(message, optional, tag = "2")]
+    #[serde(default)]
     pub message: ::std::option::Option<UnsignedMessage>,
     #[prost(message, optional, tag = "3")]
+    #[serde(default)]
     pub signature: ::std::option::Option<Signature>,
 }
@@ -3,7 +3,11 @@ use super::Result;
 use crate::constant::SECP256K1_ENGINE;
 use crate::ecc::{DeterministicPrivateKey, DeterministicPublicKey, KeyError};
 
-use crate::{Derive, FromHex, Secp256k1PrivateKey, Secp256k1PublicKey, Ss58Codec, ToHex};
+use crate::secp256k1::zeroize_secret_key;
+use crate::{
+    mnemonic_to_seed_with_passphrase, Derive, DerivePath, FromHex, Secp256k1PrivateKey,
+    Secp256k1PublicKey, Ss58Codec, ToHex,
+};
 use bitcoin::util::key::PublicKey;
 
 use bitcoin::util::base58;
@@ -14,8 +18,8 @@ use bitcoin::util::bip32::{
 use bitcoin::Network;
 use byteorder::BigEndian;
 use byteorder::ByteOrder;
-
-use bip39::{Language, Mnemonic};
+use std::convert::TryInto;
+use tcx_constants::CurveType;
 
 pub struct Bip32DeterministicPrivateKey(ExtendedPrivKey);
 
@@ -42,25 +46,37 @@ impl Bip32DeterministicPrivateKey {
     }
 
     pub fn from_mnemonic(mnemonic: &str) -> Result<Self> {
-        let mn = Mnemonic::from_phrase(mnemonic, Language::English)?;
-        let seed = bip39::Seed::new(&mn, "");
-        let epk = ExtendedPrivKey::new_master(Network::Bitcoin, seed.as_ref())?;
+        Self::from_mnemonic_with_passphrase(mnemonic, "")
+    }
+
+    pub fn from_mnemonic_with_passphrase(mnemonic: &str, passphrase: &str) -> Result<Self> {
+        let seed = mnemonic_to_seed_with_passphrase(mnemonic, passphrase)?;
+        let epk = ExtendedPrivKey::new_master(Network::Bitcoin, &seed)?;
         Ok(Bip32DeterministicPrivateKey(epk))
     }
 }
 
+impl Drop for Bip32DeterministicPrivateKey {
+    fn drop(&mut self) {
+        zeroize_secret_key(&mut self.0.private_key.key);
+        unsafe {
+            std::ptr::write_volatile(&mut self.0.chain_code, ChainCode::from(&[0u8; 32][..]));
+        }
+    }
+}
+
+fn child_numbers(path: &str) -> Result<Vec<ChildNumber>> {
+    DerivePath::parse(CurveType::SECP256k1, path)?
+        .into_iter()
+        .map(TryInto::try_into)
+        .collect()
+}
+
 impl Derive for Bip32DeterministicPrivateKey {
     fn derive(&self, path: &str) -> Result<Self> {
         let extended_key = self.0.clone();
 
-        let mut parts = path.split('/').peekable();
-        if *parts.peek().unwrap() == "m" {
-            parts.next();
-        }
-
-        let children_nums = parts
-            .map(str::parse)
-            .collect::<std::result::Result<Vec<ChildNumber>, Bip32Error>>()?;
+        let children_nums = child_numbers(path)?;
         let child_key = extended_key.derive_priv(&SECP256K1_ENGINE, &children_nums)?;
 
         Ok(Bip32DeterministicPrivateKey(child_key))
@@ -71,14 +87,7 @@ impl Derive for Bip32DeterministicPublicKey {
     fn derive(&self, path: &str) -> Result<Self> {
         let extended_key = self.0.clone();
 
-        let mut parts = path.split('/').peekable();
-        if *parts.peek().unwrap() == "m" {
-            parts.next();
-        }
-
-        let children_nums = parts
-            .map(str::parse)
-            .collect::<std::result::Result<Vec<ChildNumber>, Bip32Error>>()?;
+        let children_nums = child_numbers(path)?;
         let child_key = extended_key.derive_pub(&SECP256K1_ENGINE, &children_nums)?;
 
         Ok(Bip32DeterministicPublicKey(child_key))
@@ -94,12 +103,8 @@ impl DeterministicPrivateKey for Bip32DeterministicPrivateKey {
         Ok(Bip32DeterministicPrivateKey(esk))
     }
 
-    fn from_mnemonic(mnemonic: &str) -> Result<Self> {
-        let mn = Mnemonic::from_phrase(mnemonic, Language::English)?;
-        let seed = bip39::Seed::new(&mn, "");
-        let esk = ExtendedPrivKey::new_master(Network::Bitcoin, seed.as_bytes())?;
-
-        Ok(Bip32DeterministicPrivateKey(esk))
+    fn from_mnemonic_with_passphrase(mnemonic: &str, passphrase: &str) -> Result<Self> {
+        Self::from_mnemonic_with_passphrase(mnemonic, passphrase)
     }
 
     fn private_key(&self) -> Self::PrivateKey {
@@ -1,6 +1,6 @@
 use crate::ecc::{KeyError, PrivateKey as TraitPrivateKey, PublicKey as TraitPublicKey};
 use crate::Result;
-use bls_signatures::{PrivateKey, PublicKey, Serialize};
+use bls_signatures::{PrivateKey, PublicKey, Serialize, Signature};
 
 #[derive(Clone)]
 pub struct BLSPublicKey(PublicKey);
@@ -52,6 +52,15 @@ impl TraitPublicKey for BLSPublicKey {
     fn to_bytes(&self) -> Vec<u8> {
         self.0.as_bytes()
     }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<bool> {
+        let signature = Signature::from_bytes(signature)?;
+        Ok(bls_signatures::verify(
+            &signature,
+            &[bls_signatures::hash(message)],
+            &[self.0],
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -1,39 +1,32 @@
-use bip39::{Language, Mnemonic};
 use num_bigint::BigUint;
 
 use super::Result;
 use crate::bls::{BLSPrivateKey, BLSPublicKey};
 use crate::ecc::KeyError;
-use crate::{Derive, DeterministicPrivateKey, DeterministicPublicKey, FromHex, PrivateKey, ToHex};
+use crate::{
+    mnemonic_to_seed_with_passphrase, Derive, DeriveJunction, DerivePath, DeterministicPrivateKey,
+    DeterministicPublicKey, FromHex, PrivateKey, PublicKey, ToHex,
+};
 use num_traits::{FromPrimitive, Num, Pow};
 use sha2::digest::FixedOutput;
 use sha2::{Digest, Sha256};
+use tcx_constants::CurveType;
 
 #[derive(Clone)]
 pub struct BLSDeterministicPrivateKey(pub BigUint);
 
+/// The public key of an EIP-2333 key, it carries no chain code since no child can be derived
+/// from it
 #[derive(Clone)]
-pub struct BLSDeterministicPublicKey();
+pub struct BLSDeterministicPublicKey(BLSPublicKey);
 
 impl Derive for BLSDeterministicPrivateKey {
     fn derive(&self, path: &str) -> Result<Self> {
-        let mut parts = path.split('/').peekable();
-        if *parts.peek().unwrap() == "m" {
-            parts.next();
-        }
-
-        let result = parts
-            .map(str::parse)
-            .collect::<std::result::Result<Vec<BigUint>, _>>();
-        if result.is_err() {
-            return Err(KeyError::InvalidDerivationPathFormat.into());
-        }
-
-        let children_nums = result.unwrap();
-
         let mut children_key = self.0.clone();
-        for index in children_nums {
-            children_key = derive_child(children_key, index);
+        for junction in &DerivePath::parse(CurveType::BLS, path)? {
+            if let DeriveJunction::Soft(index) = junction {
+                children_key = derive_child(children_key, BigUint::from(index));
+            }
         }
 
         Ok(BLSDeterministicPrivateKey(children_key))
@@ -53,10 +46,9 @@ impl DeterministicPrivateKey for BLSDeterministicPrivateKey {
         Ok(BLSDeterministicPrivateKey(master_sk.unwrap()))
     }
 
-    fn from_mnemonic(mnemonic: &str) -> Result<Self> {
-        let mn = Mnemonic::from_phrase(mnemonic, Language::English)?;
-        let seed = bip39::Seed::new(&mn, "");
-        BLSDeterministicPrivateKey::from_seed(seed.as_bytes())
+    fn from_mnemonic_with_passphrase(mnemonic: &str, passphrase: &str) -> Result<Self> {
+        let seed = mnemonic_to_seed_with_passphrase(mnemonic, passphrase)?;
+        BLSDeterministicPrivateKey::from_seed(&seed)
     }
 
     fn private_key(&self) -> Self::PrivateKey {
@@ -64,21 +56,28 @@ impl DeterministicPrivateKey for BLSDeterministicPrivateKey {
     }
 
     fn deterministic_public_key(&self) -> Self::DeterministicPublicKey {
-        panic!("not supported")
+        BLSDeterministicPublicKey(self.private_key().public_key())
     }
 }
 
-impl Derive for BLSDeterministicPublicKey {}
+/// EIP-2333 only has hardened children, none of them can be derived from a public key
+impl Derive for BLSDeterministicPublicKey {
+    fn derive(&self, _path: &str) -> Result<Self> {
+        Err(KeyError::UnsupportBLSPubkeyDerivation.into())
+    }
+}
 
 impl FromHex for BLSDeterministicPublicKey {
-    fn from_hex(_: &str) -> Result<Self> {
-        panic!("not supported")
+    fn from_hex(hex: &str) -> Result<Self> {
+        let bytes = hex::decode(hex).map_err(|_| KeyError::InvalidBLSKey)?;
+        let public_key = BLSPublicKey::from_slice(&bytes).map_err(|_| KeyError::InvalidBLSKey)?;
+        Ok(BLSDeterministicPublicKey(public_key))
     }
 }
 
 impl ToHex for BLSDeterministicPublicKey {
     fn to_hex(&self) -> String {
-        panic!("not supported")
+        hex::encode(self.0.to_bytes())
     }
 }
 
@@ -86,7 +85,7 @@ impl DeterministicPublicKey for BLSDeterministicPublicKey {
     type PublicKey = BLSPublicKey;
 
     fn public_key(&self) -> Self::PublicKey {
-        panic!("not supported")
+        self.0.clone()
     }
 }
 
@@ -184,8 +183,11 @@ pub fn derive_master_sk(seed: &[u8]) -> Result<BigUint> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bls_derive::BLSDeterministicPrivateKey;
-    use crate::{Derive, DeterministicPrivateKey, PrivateKey};
+    use crate::bls_derive::{BLSDeterministicPrivateKey, BLSDeterministicPublicKey};
+    use crate::{
+        Derive, DeterministicPrivateKey, DeterministicPublicKey, FromHex, PrivateKey, PublicKey,
+        ToHex,
+    };
     use hex;
     use num_bigint::BigUint;
     use num_traits::{FromPrimitive, Num};
@@ -261,4 +263,23 @@ mod tests {
             "3a5542a9fef97a0f6b776fbe5e8edb0e087457be81223b1e1f40836834e31d1a"
         );
     }
+
+    #[test]
+    fn test_bls_deterministic_public_key() {
+        let dsk = BLSDeterministicPrivateKey::from_seed(
+            &hex::decode("c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04").unwrap()).unwrap();
+        let dpk = dsk.deterministic_public_key();
+        assert_eq!(
+            dpk.public_key().to_bytes(),
+            dsk.private_key().public_key().to_bytes()
+        );
+
+        let parsed = BLSDeterministicPublicKey::from_hex(&dpk.to_hex()).unwrap();
+        assert_eq!(parsed.to_hex(), dpk.to_hex());
+
+        let err = dpk.derive("m/0").err().unwrap();
+        assert_eq!(format!("{}", err), "unsupport_bls_pubkey_derivation");
+        let err = BLSDeterministicPublicKey::from_hex("zz").err().unwrap();
+        assert_eq!(format!("{}", err), "invalid_bls_key");
+    }
 }
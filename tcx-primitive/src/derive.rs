@@ -2,10 +2,14 @@ use crate::ecc::KeyError;
 use crate::Result;
 
 use bitcoin::util::bip32::ChildNumber;
+use tcx_constants::CurveType;
 
 use std::convert::TryInto;
+use std::fmt;
 use std::str::FromStr;
 
+const HARDENED_BIT: u32 = 1 << 31;
+
 pub fn get_account_path(path: &str) -> Result<String> {
     // example: m/44'/60'/0'/0/0
     let _ = bitcoin::util::bip32::DerivationPath::from_str(path)?;
@@ -19,10 +23,34 @@ pub fn get_account_path(path: &str) -> Result<String> {
     Ok(children.join("/"))
 }
 
+/// The part of `path` up to its last hardened junction, the extended public key there derives
+/// the rest of the path. `//polkadot//imToken/0` gives `//polkadot//imToken`
+pub fn get_hardened_path(curve: CurveType, path: &str) -> Result<String> {
+    let derive_path = DerivePath::parse(curve, path)?;
+    let len = derive_path
+        .0
+        .iter()
+        .rposition(DeriveJunction::is_hard)
+        .map_or(0, |index| index + 1);
+    let junctions = derive_path.0[..len].iter().map(ToString::to_string);
+    Ok(match curve {
+        CurveType::SubSr25519 => junctions.collect(),
+        _ => std::iter::once("m".to_string())
+            .chain(junctions)
+            .collect::<Vec<String>>()
+            .join("/"),
+    })
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum DeriveJunction {
     Soft(u32),
     Hard(u32),
+    /// A Substrate junction as written, without its leading slashes
+    Named {
+        name: String,
+        hard: bool,
+    },
 }
 
 pub trait Derive: Sized {
@@ -33,7 +61,6 @@ pub trait Derive: Sized {
     }
 }
 
-// TODO add parity string derivation path
 #[allow(dead_code)]
 impl DeriveJunction {
     pub fn soft(index: u32) -> Self {
@@ -47,6 +74,7 @@ impl DeriveJunction {
     pub fn is_soft(&self) -> bool {
         match *self {
             DeriveJunction::Soft(_) => true,
+            DeriveJunction::Named { hard, .. } => !hard,
             _ => false,
         }
     }
@@ -54,11 +82,23 @@ impl DeriveJunction {
     pub fn is_hard(&self) -> bool {
         match *self {
             DeriveJunction::Hard(_) => true,
+            DeriveJunction::Named { hard, .. } => hard,
             _ => false,
         }
     }
 }
 
+impl fmt::Display for DeriveJunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeriveJunction::Soft(index) => write!(f, "{}", index),
+            DeriveJunction::Hard(index) => write!(f, "{}'", index),
+            DeriveJunction::Named { name, hard: true } => write!(f, "//{}", name),
+            DeriveJunction::Named { name, hard: false } => write!(f, "/{}", name),
+        }
+    }
+}
+
 impl FromStr for DeriveJunction {
     type Err = failure::Error;
 
@@ -87,6 +127,7 @@ impl TryInto<ChildNumber> for DeriveJunction {
         if let Ok(num) = match self {
             DeriveJunction::Soft(index) => ChildNumber::from_normal_idx(index),
             DeriveJunction::Hard(index) => ChildNumber::from_hardened_idx(index),
+            DeriveJunction::Named { .. } => return Err(KeyError::InvalidChildNumber.into()),
         } {
             Ok(num)
         } else {
@@ -98,6 +139,94 @@ impl TryInto<ChildNumber> for DeriveJunction {
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct DerivePath(Vec<DeriveJunction>);
 
+impl DerivePath {
+    /// Parses `path` in the syntax `curve` derives with: BIP-32 `m/44'/0'/0'/0/0` for secp256k1
+    /// and NIST P-256, hardened only BIP-32 for the SLIP-10 ed25519, EIP-2334 `m/12381/3600/0/0`
+    /// for BLS and `//hard/soft` junctions for sr25519. Errors name the offending segment
+    pub fn parse(curve: CurveType, path: &str) -> Result<Self> {
+        match curve {
+            CurveType::SECP256k1 | CurveType::NIST256p1 => parse_bip32(path),
+            CurveType::ED25519 | CurveType::ED25519Blake2bNano => {
+                let path = parse_bip32(path)?;
+                if let Some(junction) = path.0.iter().find(|junction| junction.is_soft()) {
+                    return Err(KeyError::UnsupportNormalDerivation(junction.to_string()).into());
+                }
+                Ok(path)
+            }
+            CurveType::BLS => parse_eip2334(path),
+            CurveType::SubSr25519 => parse_substrate(path),
+            _ => Err(KeyError::InvalidCurveType.into()),
+        }
+    }
+}
+
+/// Splits off the optional leading `m`, a path without it is relative
+fn path_segments(path: &str) -> impl Iterator<Item = &str> {
+    let mut parts = path.split('/').peekable();
+    if parts.peek() == Some(&"m") {
+        parts.next();
+    }
+    parts
+}
+
+fn parse_bip32(path: &str) -> Result<DerivePath> {
+    let junctions = path_segments(path)
+        .map(|segment| -> Result<DeriveJunction> {
+            match segment.parse::<DeriveJunction>() {
+                Ok(DeriveJunction::Soft(index)) if index < HARDENED_BIT => {
+                    Ok(DeriveJunction::Soft(index))
+                }
+                Ok(DeriveJunction::Hard(index)) if index < HARDENED_BIT => {
+                    Ok(DeriveJunction::Hard(index))
+                }
+                _ => Err(KeyError::InvalidDerivationPathSegment(segment.to_string()).into()),
+            }
+        })
+        .collect::<Result<_>>()?;
+    Ok(DerivePath(junctions))
+}
+
+/// EIP-2333 derives every child hardened, its paths carry plain indices without a marker
+fn parse_eip2334(path: &str) -> Result<DerivePath> {
+    let junctions = path_segments(path)
+        .map(|segment| -> Result<DeriveJunction> {
+            if segment.is_empty() || !segment.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(KeyError::InvalidDerivationPathSegment(segment.to_string()).into());
+            }
+            segment
+                .parse()
+                .map(DeriveJunction::Soft)
+                .map_err(|_| KeyError::InvalidDerivationPathSegment(segment.to_string()).into())
+        })
+        .collect::<Result<_>>()?;
+    Ok(DerivePath(junctions))
+}
+
+fn parse_substrate(path: &str) -> Result<DerivePath> {
+    let mut junctions = vec![];
+    let mut rest = path;
+    while !rest.is_empty() {
+        let (hard, tail) = if rest.starts_with("//") {
+            (true, &rest[2..])
+        } else if rest.starts_with('/') {
+            (false, &rest[1..])
+        } else {
+            let end = rest.find('/').unwrap_or_else(|| rest.len());
+            return Err(KeyError::InvalidDerivationPathSegment(rest[..end].to_string()).into());
+        };
+        let end = tail.find('/').unwrap_or_else(|| tail.len());
+        if end == 0 {
+            return Err(KeyError::InvalidDerivationPathSegment(rest.to_string()).into());
+        }
+        junctions.push(DeriveJunction::Named {
+            name: tail[..end].to_string(),
+            hard,
+        });
+        rest = &tail[end..];
+    }
+    Ok(DerivePath(junctions))
+}
+
 impl FromStr for DerivePath {
     type Err = failure::Error;
 
@@ -130,12 +259,13 @@ impl AsRef<[DeriveJunction]> for DerivePath {
 #[cfg(test)]
 mod tests {
     use super::DerivePath;
-    use crate::derive::get_account_path;
+    use crate::derive::{get_account_path, get_hardened_path};
     use crate::generate_mnemonic;
     use crate::DeriveJunction;
     use bitcoin::util::bip32::ChildNumber;
     use std::convert::TryInto;
     use std::str::FromStr;
+    use tcx_constants::CurveType;
 
     #[test]
     fn derive_path_from_root() {
@@ -244,4 +374,85 @@ mod tests {
             assert!(ret.is_err());
         }
     }
+
+    #[test]
+    fn hardened_path() {
+        let path = get_hardened_path(CurveType::SubSr25519, "//polkadot//imToken/0").unwrap();
+        assert_eq!(path, "//polkadot//imToken");
+        let path = get_hardened_path(CurveType::SubSr25519, "/0/1").unwrap();
+        assert_eq!(path, "");
+        let path = get_hardened_path(CurveType::SECP256k1, "m/44'/0'/0'/0/0").unwrap();
+        assert_eq!(path, "m/44'/0'/0'");
+        let path = get_hardened_path(CurveType::ED25519, "m/44'/1729'/0'/0'").unwrap();
+        assert_eq!(path, "m/44'/1729'/0'/0'");
+    }
+
+    #[test]
+    fn derive_path_for_curve() {
+        let path = DerivePath::parse(CurveType::SECP256k1, "m/44'/0'/0'/0/0").unwrap();
+        assert_eq!(path.as_ref()[0], DeriveJunction::hard(44));
+        assert_eq!(path.as_ref()[4], DeriveJunction::soft(0));
+        assert!(DerivePath::parse(CurveType::NIST256p1, "0/1").is_ok());
+        assert!(DerivePath::parse(CurveType::ED25519, "m/44'/1729'/0'/0'").is_ok());
+
+        let path = DerivePath::parse(CurveType::BLS, "m/12381/3600/0/0").unwrap();
+        assert_eq!(path.as_ref()[1], DeriveJunction::soft(3600));
+
+        let path = DerivePath::parse(CurveType::SubSr25519, "//polkadot//imToken/0").unwrap();
+        let junctions = path
+            .as_ref()
+            .iter()
+            .map(|junction| junction.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(junctions, vec!["//polkadot", "//imToken", "/0"]);
+        assert!(path.as_ref()[1].is_hard());
+        assert!(path.as_ref()[2].is_soft());
+        assert!(DerivePath::parse(CurveType::SubSr25519, "")
+            .unwrap()
+            .as_ref()
+            .is_empty());
+
+        let invalid_paths = vec![
+            (
+                CurveType::SECP256k1,
+                "m/44'/a/0",
+                "invalid_derivation_path_segment# a",
+            ),
+            (
+                CurveType::SECP256k1,
+                "m//0",
+                "invalid_derivation_path_segment# ",
+            ),
+            (
+                CurveType::SECP256k1,
+                "m/2147483648",
+                "invalid_derivation_path_segment# 2147483648",
+            ),
+            (
+                CurveType::ED25519,
+                "m/44'/1729'/0'/0",
+                "unsupport_normal_derivation# 0",
+            ),
+            (
+                CurveType::BLS,
+                "m/12381'/3600",
+                "invalid_derivation_path_segment# 12381'",
+            ),
+            (
+                CurveType::SubSr25519,
+                "m/44'/354'",
+                "invalid_derivation_path_segment# m",
+            ),
+            (
+                CurveType::SubSr25519,
+                "//polkadot///0",
+                "invalid_derivation_path_segment# ///0",
+            ),
+            (CurveType::Curve25519, "m/0", "invalid_curve_type"),
+        ];
+        for (curve, path, err) in invalid_paths {
+            let ret = DerivePath::parse(curve, path);
+            assert_eq!(format!("{}", ret.err().unwrap()), err, "{}", path);
+        }
+    }
 }
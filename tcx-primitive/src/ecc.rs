@@ -4,11 +4,17 @@ use crate::{
     Secp256k1PrivateKey, Secp256k1PublicKey, ToHex,
 };
 
+#[cfg(feature = "bls")]
 use crate::bls::{BLSPrivateKey, BLSPublicKey};
-use crate::bls_derive::BLSDeterministicPrivateKey;
-use crate::ecc::TypedDeterministicPrivateKey::{Bip32Ed25519, Bip32Sepc256k1, SubSr25519};
+#[cfg(feature = "bls")]
+use crate::bls_derive::{BLSDeterministicPrivateKey, BLSDeterministicPublicKey};
+use crate::ecc::TypedDeterministicPrivateKey::{Bip32Sepc256k1, Slip10Ed25519, SubSr25519};
 use crate::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
-use crate::ed25519_bip32::{Ed25519DeterministicPrivateKey, Ed25519DeterministicPublicKey};
+use crate::ed25519_slip10::{Ed25519DeterministicPrivateKey, Ed25519DeterministicPublicKey};
+use crate::nist256p1::{
+    Nist256p1DeterministicPrivateKey, Nist256p1DeterministicPublicKey, Nist256p1PrivateKey,
+    Nist256p1PublicKey,
+};
 use crate::sr25519::{Sr25519PrivateKey, Sr25519PublicKey};
 use sp_core::Pair;
 use tcx_constants::CurveType;
@@ -52,8 +58,14 @@ pub enum KeyError {
     InvalidEd25519Key,
     #[fail(display = "unsupport_ed25519_pubkey_derivation")]
     UnsupportEd25519PubkeyDerivation,
-    #[fail(display = "unsupport_normal_derivation")]
-    UnsupportNormalDerivation,
+    #[fail(display = "invalid_bls_key")]
+    InvalidBLSKey,
+    #[fail(display = "unsupport_bls_pubkey_derivation")]
+    UnsupportBLSPubkeyDerivation,
+    #[fail(display = "unsupport_normal_derivation# {}", _0)]
+    UnsupportNormalDerivation(String),
+    #[fail(display = "invalid_derivation_path_segment# {}", _0)]
+    InvalidDerivationPathSegment(String),
     #[fail(display = "not_implement")]
     NotImplement,
 }
@@ -62,6 +74,10 @@ pub trait PublicKey: Sized {
     fn from_slice(data: &[u8]) -> Result<Self>;
 
     fn to_bytes(&self) -> Vec<u8>;
+
+    /// Whether `signature` is a signature of `data` made by this key, malformed signatures are
+    /// errors. `data` is what was passed to `PrivateKey::sign`
+    fn verify(&self, data: &[u8], signature: &[u8]) -> Result<bool>;
 }
 
 pub trait PrivateKey: Sized {
@@ -90,7 +106,12 @@ pub trait DeterministicPrivateKey: Derive {
 
     fn from_seed(seed: &[u8]) -> Result<Self>;
 
-    fn from_mnemonic(mnemonic: &str) -> Result<Self>;
+    fn from_mnemonic(mnemonic: &str) -> Result<Self> {
+        Self::from_mnemonic_with_passphrase(mnemonic, "")
+    }
+
+    /// `passphrase` is the BIP-39 passphrase, empty for none
+    fn from_mnemonic_with_passphrase(mnemonic: &str, passphrase: &str) -> Result<Self>;
 
     fn private_key(&self) -> Self::PrivateKey;
 
@@ -105,6 +126,8 @@ pub enum TypedPrivateKey {
     Secp256k1(Secp256k1PrivateKey),
     Sr25519(Sr25519PrivateKey),
     Ed25519(Ed25519PrivateKey),
+    Nist256p1(Nist256p1PrivateKey),
+    #[cfg(feature = "bls")]
     BLS(BLSPrivateKey),
 }
 
@@ -114,6 +137,8 @@ impl TypedPrivateKey {
             TypedPrivateKey::Secp256k1(_) => CurveType::SECP256k1,
             TypedPrivateKey::Sr25519(_) => CurveType::SubSr25519,
             TypedPrivateKey::Ed25519(_) => CurveType::ED25519,
+            TypedPrivateKey::Nist256p1(_) => CurveType::NIST256p1,
+            #[cfg(feature = "bls")]
             TypedPrivateKey::BLS(_) => CurveType::BLS,
         }
     }
@@ -129,6 +154,10 @@ impl TypedPrivateKey {
             CurveType::ED25519 => Ok(TypedPrivateKey::Ed25519(Ed25519PrivateKey::from_slice(
                 data,
             )?)),
+            CurveType::NIST256p1 => Ok(TypedPrivateKey::Nist256p1(
+                Nist256p1PrivateKey::from_slice(data)?,
+            )),
+            #[cfg(feature = "bls")]
             CurveType::BLS => Ok(TypedPrivateKey::BLS(BLSPrivateKey::from_slice(data)?)),
             _ => Err(KeyError::InvalidCurveType.into()),
         }
@@ -146,6 +175,8 @@ impl TypedPrivateKey {
             TypedPrivateKey::Secp256k1(sk) => sk.to_bytes(),
             TypedPrivateKey::Sr25519(sk) => sk.to_bytes(),
             TypedPrivateKey::Ed25519(sk) => sk.to_bytes(),
+            TypedPrivateKey::Nist256p1(sk) => sk.to_bytes(),
+            #[cfg(feature = "bls")]
             TypedPrivateKey::BLS(sk) => sk.to_bytes(),
         }
     }
@@ -155,6 +186,8 @@ impl TypedPrivateKey {
             TypedPrivateKey::Secp256k1(sk) => TypedPublicKey::Secp256k1(sk.public_key()),
             TypedPrivateKey::Sr25519(sk) => TypedPublicKey::Sr25519(sk.public_key()),
             TypedPrivateKey::Ed25519(sk) => TypedPublicKey::Ed25519(sk.public_key()),
+            TypedPrivateKey::Nist256p1(sk) => TypedPublicKey::Nist256p1(sk.public_key()),
+            #[cfg(feature = "bls")]
             TypedPrivateKey::BLS(sk) => TypedPublicKey::BLS(sk.public_key()),
         }
     }
@@ -164,6 +197,8 @@ impl TypedPrivateKey {
             TypedPrivateKey::Secp256k1(sk) => sk.sign(data),
             TypedPrivateKey::Sr25519(sk) => sk.sign(data),
             TypedPrivateKey::Ed25519(sk) => sk.sign(data),
+            TypedPrivateKey::Nist256p1(sk) => sk.sign(data),
+            #[cfg(feature = "bls")]
             TypedPrivateKey::BLS(sk) => sk.sign(data),
         }
     }
@@ -173,6 +208,8 @@ impl TypedPrivateKey {
             TypedPrivateKey::Secp256k1(sk) => sk.sign_recoverable(data),
             TypedPrivateKey::Sr25519(sk) => sk.sign_recoverable(data),
             TypedPrivateKey::Ed25519(sk) => sk.sign_recoverable(data),
+            TypedPrivateKey::Nist256p1(sk) => sk.sign_recoverable(data),
+            #[cfg(feature = "bls")]
             TypedPrivateKey::BLS(sk) => sk.sign_recoverable(data),
         }
     }
@@ -182,6 +219,8 @@ pub enum TypedPublicKey {
     Secp256k1(Secp256k1PublicKey),
     Sr25519(Sr25519PublicKey),
     Ed25519(Ed25519PublicKey),
+    Nist256p1(Nist256p1PublicKey),
+    #[cfg(feature = "bls")]
     BLS(BLSPublicKey),
 }
 
@@ -191,6 +230,8 @@ impl TypedPublicKey {
             TypedPublicKey::Secp256k1(_) => CurveType::SECP256k1,
             TypedPublicKey::Sr25519(_) => CurveType::SubSr25519,
             TypedPublicKey::Ed25519(_) => CurveType::ED25519,
+            TypedPublicKey::Nist256p1(_) => CurveType::NIST256p1,
+            #[cfg(feature = "bls")]
             TypedPublicKey::BLS(_) => CurveType::BLS,
         }
     }
@@ -204,6 +245,10 @@ impl TypedPublicKey {
                 Ok(TypedPublicKey::Sr25519(Sr25519PublicKey::from_slice(data)?))
             }
             CurveType::ED25519 => Ok(TypedPublicKey::Ed25519(Ed25519PublicKey::from_slice(data)?)),
+            CurveType::NIST256p1 => Ok(TypedPublicKey::Nist256p1(Nist256p1PublicKey::from_slice(
+                data,
+            )?)),
+            #[cfg(feature = "bls")]
             CurveType::BLS => Ok(TypedPublicKey::BLS(BLSPublicKey::from_slice(data)?)),
 
             _ => Err(KeyError::InvalidCurveType.into()),
@@ -215,6 +260,8 @@ impl TypedPublicKey {
             TypedPublicKey::Secp256k1(pk) => pk.to_bytes(),
             TypedPublicKey::Sr25519(pk) => pk.to_bytes(),
             TypedPublicKey::Ed25519(pk) => pk.to_bytes(),
+            TypedPublicKey::Nist256p1(pk) => pk.to_bytes(),
+            #[cfg(feature = "bls")]
             TypedPublicKey::BLS(pk) => pk.to_bytes(),
         }
     }
@@ -225,12 +272,36 @@ impl TypedPublicKey {
             _ => Err(format_err!("not support")),
         }
     }
+
+    pub fn verify(&self, data: &[u8], signature: &[u8]) -> Result<bool> {
+        match self {
+            TypedPublicKey::Secp256k1(pk) => pk.verify(data, signature),
+            TypedPublicKey::Sr25519(pk) => pk.verify(data, signature),
+            TypedPublicKey::Ed25519(pk) => pk.verify(data, signature),
+            TypedPublicKey::Nist256p1(pk) => pk.verify(data, signature),
+            #[cfg(feature = "bls")]
+            TypedPublicKey::BLS(pk) => pk.verify(data, signature),
+        }
+    }
+
+    /// The signer of a recoverable signature, only secp256k1 signatures carry a recovery id
+    pub fn recover(curve_type: CurveType, data: &[u8], signature: &[u8]) -> Result<TypedPublicKey> {
+        match curve_type {
+            CurveType::SECP256k1 => Ok(TypedPublicKey::Secp256k1(Secp256k1PublicKey::recover(
+                data, signature,
+            )?)),
+            _ => Err(KeyError::InvalidCurveType.into()),
+        }
+    }
 }
 
 pub enum TypedDeterministicPublicKey {
     Bip32Sepc256k1(Bip32DeterministicPublicKey),
     SubSr25519(Sr25519PublicKey), //    SubstrateSr25519()
-    Bip32Ed25519(Ed25519DeterministicPublicKey),
+    Slip10Ed25519(Ed25519DeterministicPublicKey),
+    Nist256p1(Nist256p1DeterministicPublicKey),
+    #[cfg(feature = "bls")]
+    BLS(BLSDeterministicPublicKey),
 }
 
 impl TypedDeterministicPublicKey {
@@ -238,7 +309,10 @@ impl TypedDeterministicPublicKey {
         match self {
             TypedDeterministicPublicKey::Bip32Sepc256k1(_) => CurveType::SECP256k1,
             TypedDeterministicPublicKey::SubSr25519(_) => CurveType::SubSr25519,
-            TypedDeterministicPublicKey::Bip32Ed25519(_) => CurveType::ED25519,
+            TypedDeterministicPublicKey::Slip10Ed25519(_) => CurveType::ED25519,
+            TypedDeterministicPublicKey::Nist256p1(_) => CurveType::NIST256p1,
+            #[cfg(feature = "bls")]
+            TypedDeterministicPublicKey::BLS(_) => CurveType::BLS,
         }
     }
 
@@ -250,9 +324,14 @@ impl TypedDeterministicPublicKey {
             TypedDeterministicPublicKey::SubSr25519(epk) => {
                 TypedPublicKey::Sr25519(epk.public_key())
             }
-            TypedDeterministicPublicKey::Bip32Ed25519(epk) => {
+            TypedDeterministicPublicKey::Slip10Ed25519(epk) => {
                 TypedPublicKey::Ed25519(epk.public_key())
             }
+            TypedDeterministicPublicKey::Nist256p1(epk) => {
+                TypedPublicKey::Nist256p1(epk.public_key())
+            }
+            #[cfg(feature = "bls")]
+            TypedDeterministicPublicKey::BLS(epk) => TypedPublicKey::BLS(epk.public_key()),
         }
     }
 }
@@ -262,7 +341,10 @@ impl ToString for TypedDeterministicPublicKey {
         match self {
             TypedDeterministicPublicKey::Bip32Sepc256k1(epk) => epk.to_string(),
             TypedDeterministicPublicKey::SubSr25519(epk) => epk.to_string(),
-            TypedDeterministicPublicKey::Bip32Ed25519(epk) => epk.to_string(),
+            TypedDeterministicPublicKey::Slip10Ed25519(epk) => epk.to_string(),
+            TypedDeterministicPublicKey::Nist256p1(epk) => epk.to_string(),
+            #[cfg(feature = "bls")]
+            TypedDeterministicPublicKey::BLS(epk) => epk.to_hex(),
         }
     }
 }
@@ -276,8 +358,15 @@ impl Derive for TypedDeterministicPublicKey {
             TypedDeterministicPublicKey::SubSr25519(epk) => {
                 Ok(TypedDeterministicPublicKey::SubSr25519(epk.derive(path)?))
             }
-            TypedDeterministicPublicKey::Bip32Ed25519(epk) => {
-                Ok(TypedDeterministicPublicKey::Bip32Ed25519(epk.derive(path)?))
+            TypedDeterministicPublicKey::Slip10Ed25519(epk) => Ok(
+                TypedDeterministicPublicKey::Slip10Ed25519(epk.derive(path)?),
+            ),
+            TypedDeterministicPublicKey::Nist256p1(epk) => {
+                Ok(TypedDeterministicPublicKey::Nist256p1(epk.derive(path)?))
+            }
+            #[cfg(feature = "bls")]
+            TypedDeterministicPublicKey::BLS(epk) => {
+                Ok(TypedDeterministicPublicKey::BLS(epk.derive(path)?))
             }
         }
     }
@@ -286,7 +375,9 @@ impl Derive for TypedDeterministicPublicKey {
 pub enum TypedDeterministicPrivateKey {
     Bip32Sepc256k1(Bip32DeterministicPrivateKey),
     SubSr25519(Sr25519PrivateKey),
-    Bip32Ed25519(Ed25519DeterministicPrivateKey),
+    Slip10Ed25519(Ed25519DeterministicPrivateKey),
+    Nist256p1(Nist256p1DeterministicPrivateKey),
+    #[cfg(feature = "bls")]
     BLS(BLSDeterministicPrivateKey),
 }
 
@@ -295,7 +386,9 @@ impl TypedDeterministicPrivateKey {
         match self {
             TypedDeterministicPrivateKey::Bip32Sepc256k1(_) => CurveType::SECP256k1,
             TypedDeterministicPrivateKey::SubSr25519(_) => CurveType::SubSr25519,
-            TypedDeterministicPrivateKey::Bip32Ed25519(_) => CurveType::ED25519,
+            TypedDeterministicPrivateKey::Slip10Ed25519(_) => CurveType::ED25519,
+            TypedDeterministicPrivateKey::Nist256p1(_) => CurveType::NIST256p1,
+            #[cfg(feature = "bls")]
             TypedDeterministicPrivateKey::BLS(_) => CurveType::BLS,
         }
     }
@@ -303,17 +396,56 @@ impl TypedDeterministicPrivateKey {
     pub fn from_mnemonic(
         curve_type: CurveType,
         mnemonic: &str,
+    ) -> Result<TypedDeterministicPrivateKey> {
+        Self::from_mnemonic_with_passphrase(curve_type, mnemonic, "")
+    }
+
+    pub fn from_mnemonic_with_passphrase(
+        curve_type: CurveType,
+        mnemonic: &str,
+        passphrase: &str,
     ) -> Result<TypedDeterministicPrivateKey> {
         match curve_type {
             CurveType::SECP256k1 => Ok(TypedDeterministicPrivateKey::Bip32Sepc256k1(
-                Bip32DeterministicPrivateKey::from_mnemonic(mnemonic)?,
+                Bip32DeterministicPrivateKey::from_mnemonic_with_passphrase(mnemonic, passphrase)?,
+            )),
+            CurveType::SubSr25519 => Ok(SubSr25519(
+                Sr25519PrivateKey::from_mnemonic_with_passphrase(mnemonic, passphrase)?,
             )),
-            CurveType::SubSr25519 => Ok(SubSr25519(Sr25519PrivateKey::from_mnemonic(mnemonic)?)),
-            CurveType::ED25519 => Ok(Bip32Ed25519(Ed25519DeterministicPrivateKey::from_mnemonic(
-                mnemonic,
+            CurveType::ED25519 => Ok(Slip10Ed25519(
+                Ed25519DeterministicPrivateKey::from_mnemonic_with_passphrase(
+                    mnemonic, passphrase,
+                )?,
+            )),
+            CurveType::NIST256p1 => Ok(TypedDeterministicPrivateKey::Nist256p1(
+                Nist256p1DeterministicPrivateKey::from_mnemonic_with_passphrase(
+                    mnemonic, passphrase,
+                )?,
+            )),
+            #[cfg(feature = "bls")]
+            CurveType::BLS => Ok(TypedDeterministicPrivateKey::BLS(
+                BLSDeterministicPrivateKey::from_mnemonic_with_passphrase(mnemonic, passphrase)?,
+            )),
+            _ => Err(KeyError::InvalidCurveType.into()),
+        }
+    }
+
+    /// The root key of a BIP-32 seed. Substrate derives sr25519 keys from the mnemonic entropy
+    /// rather than the seed, so there is no sr25519 key of a seed
+    pub fn from_seed(curve_type: CurveType, seed: &[u8]) -> Result<TypedDeterministicPrivateKey> {
+        match curve_type {
+            CurveType::SECP256k1 => Ok(TypedDeterministicPrivateKey::Bip32Sepc256k1(
+                Bip32DeterministicPrivateKey::from_seed(seed)?,
+            )),
+            CurveType::ED25519 => Ok(Slip10Ed25519(Ed25519DeterministicPrivateKey::from_seed(
+                seed,
             )?)),
+            CurveType::NIST256p1 => Ok(TypedDeterministicPrivateKey::Nist256p1(
+                Nist256p1DeterministicPrivateKey::from_seed(seed)?,
+            )),
+            #[cfg(feature = "bls")]
             CurveType::BLS => Ok(TypedDeterministicPrivateKey::BLS(
-                BLSDeterministicPrivateKey::from_mnemonic(mnemonic)?,
+                BLSDeterministicPrivateKey::from_seed(seed)?,
             )),
             _ => Err(KeyError::InvalidCurveType.into()),
         }
@@ -327,9 +459,13 @@ impl TypedDeterministicPrivateKey {
             TypedDeterministicPrivateKey::SubSr25519(dsk) => {
                 TypedPrivateKey::Sr25519(dsk.private_key())
             }
-            TypedDeterministicPrivateKey::Bip32Ed25519(dsk) => {
+            TypedDeterministicPrivateKey::Slip10Ed25519(dsk) => {
                 TypedPrivateKey::Ed25519(dsk.private_key())
             }
+            TypedDeterministicPrivateKey::Nist256p1(dsk) => {
+                TypedPrivateKey::Nist256p1(dsk.private_key())
+            }
+            #[cfg(feature = "bls")]
             TypedDeterministicPrivateKey::BLS(dsk) => TypedPrivateKey::BLS(dsk.private_key()),
         }
     }
@@ -342,10 +478,16 @@ impl TypedDeterministicPrivateKey {
             TypedDeterministicPrivateKey::SubSr25519(sk) => {
                 TypedDeterministicPublicKey::SubSr25519(sk.deterministic_public_key())
             }
-            TypedDeterministicPrivateKey::Bip32Ed25519(sk) => {
-                TypedDeterministicPublicKey::Bip32Ed25519(sk.deterministic_public_key())
+            TypedDeterministicPrivateKey::Slip10Ed25519(sk) => {
+                TypedDeterministicPublicKey::Slip10Ed25519(sk.deterministic_public_key())
+            }
+            TypedDeterministicPrivateKey::Nist256p1(sk) => {
+                TypedDeterministicPublicKey::Nist256p1(sk.deterministic_public_key())
+            }
+            #[cfg(feature = "bls")]
+            TypedDeterministicPrivateKey::BLS(sk) => {
+                TypedDeterministicPublicKey::BLS(sk.deterministic_public_key())
             }
-            TypedDeterministicPrivateKey::BLS(_) => panic!("not support"),
         }
     }
 }
@@ -355,7 +497,9 @@ impl ToString for TypedDeterministicPrivateKey {
         match self {
             TypedDeterministicPrivateKey::Bip32Sepc256k1(sk) => sk.to_string(),
             TypedDeterministicPrivateKey::SubSr25519(sk) => hex::encode(sk.0.to_raw_vec()),
-            TypedDeterministicPrivateKey::Bip32Ed25519(sk) => sk.to_string(),
+            TypedDeterministicPrivateKey::Slip10Ed25519(sk) => sk.to_string(),
+            TypedDeterministicPrivateKey::Nist256p1(sk) => sk.to_string(),
+            #[cfg(feature = "bls")]
             TypedDeterministicPrivateKey::BLS(sk) => hex::encode(sk.0.to_string()),
         }
     }
@@ -370,9 +514,16 @@ impl TypedDeterministicPublicKey {
             CurveType::SubSr25519 => Ok(TypedDeterministicPublicKey::SubSr25519(
                 Sr25519PublicKey::from_hex(hex)?,
             )),
-            CurveType::ED25519 => Ok(TypedDeterministicPublicKey::Bip32Ed25519(
+            CurveType::ED25519 => Ok(TypedDeterministicPublicKey::Slip10Ed25519(
                 Ed25519DeterministicPublicKey::from_hex(hex)?,
             )),
+            CurveType::NIST256p1 => Ok(TypedDeterministicPublicKey::Nist256p1(
+                Nist256p1DeterministicPublicKey::from_hex(hex)?,
+            )),
+            #[cfg(feature = "bls")]
+            CurveType::BLS => Ok(TypedDeterministicPublicKey::BLS(
+                BLSDeterministicPublicKey::from_hex(hex)?,
+            )),
             _ => Err(KeyError::InvalidCurveType.into()),
         }
     }
@@ -383,7 +534,10 @@ impl ToHex for TypedDeterministicPublicKey {
         match self {
             TypedDeterministicPublicKey::Bip32Sepc256k1(epk) => epk.to_hex(),
             TypedDeterministicPublicKey::SubSr25519(epk) => epk.to_hex(),
-            TypedDeterministicPublicKey::Bip32Ed25519(epk) => epk.to_hex(),
+            TypedDeterministicPublicKey::Slip10Ed25519(epk) => epk.to_hex(),
+            TypedDeterministicPublicKey::Nist256p1(epk) => epk.to_hex(),
+            #[cfg(feature = "bls")]
+            TypedDeterministicPublicKey::BLS(epk) => epk.to_hex(),
         }
     }
 }
@@ -397,9 +551,13 @@ impl Derive for TypedDeterministicPrivateKey {
             TypedDeterministicPrivateKey::SubSr25519(dsk) => {
                 Ok(TypedDeterministicPrivateKey::SubSr25519(dsk.derive(path)?))
             }
-            TypedDeterministicPrivateKey::Bip32Ed25519(dsk) => Ok(
-                TypedDeterministicPrivateKey::Bip32Ed25519(dsk.derive(path)?),
+            TypedDeterministicPrivateKey::Slip10Ed25519(dsk) => Ok(
+                TypedDeterministicPrivateKey::Slip10Ed25519(dsk.derive(path)?),
             ),
+            TypedDeterministicPrivateKey::Nist256p1(dsk) => {
+                Ok(TypedDeterministicPrivateKey::Nist256p1(dsk.derive(path)?))
+            }
+            #[cfg(feature = "bls")]
             TypedDeterministicPrivateKey::BLS(dsk) => {
                 Ok(TypedDeterministicPrivateKey::BLS(dsk.derive(path)?))
             }
@@ -489,4 +647,52 @@ mod tests {
         );
         assert_eq!(pk.curve_type(), CurveType::SECP256k1);
     }
+
+    #[test]
+    fn test_verify_signature() {
+        let sr25519_key = hex::decode("00ea01b0116da6ca425c477521fd49cc763988ac403ab560f4022936a18a4341016e7df1f5020068c9b150e0722fea65a264d5fbb342d4af4ddf2f1cdbddf1fd").unwrap();
+        #[allow(unused_mut)]
+        let mut keys = vec![
+            TypedPrivateKey::from_slice(CurveType::SECP256k1, &default_private_key()).unwrap(),
+            TypedPrivateKey::from_slice(CurveType::ED25519, &default_private_key()).unwrap(),
+            TypedPrivateKey::from_slice(CurveType::SubSr25519, &sr25519_key).unwrap(),
+            TypedPrivateKey::from_slice(CurveType::NIST256p1, &default_private_key()).unwrap(),
+        ];
+        #[cfg(feature = "bls")]
+        keys.push(
+            TypedPrivateKey::from_slice(
+                CurveType::BLS,
+                &hex::decode("0ef71710671a9f1cfc4bd441c017c9b6db68491929facc68ab072a9676e9e23c")
+                    .unwrap(),
+            )
+            .unwrap(),
+        );
+
+        let data = [0x11u8; 32];
+        for sk in keys.iter() {
+            let pk = sk.public_key();
+            let signature = sk.sign(&data).unwrap();
+            assert!(pk.verify(&data, &signature).unwrap());
+            assert!(!pk.verify(&[0x22u8; 32], &signature).unwrap());
+            assert!(pk.verify(&data, &signature[1..]).is_err());
+        }
+
+        let sk = &keys[0];
+        let signature = sk.sign_recoverable(&data).unwrap();
+        assert!(sk.public_key().verify(&data, &signature).unwrap());
+        let recovered = TypedPublicKey::recover(CurveType::SECP256k1, &data, &signature).unwrap();
+        assert_eq!(recovered.to_bytes(), sk.public_key().to_bytes());
+
+        // ethereum style recovery id
+        let mut eth_signature = signature.clone();
+        eth_signature[64] += 27;
+        let recovered =
+            TypedPublicKey::recover(CurveType::SECP256k1, &data, &eth_signature).unwrap();
+        assert_eq!(hex::encode(recovered.to_bytes()), PUB_KEY_HEX);
+
+        let err = TypedPublicKey::recover(CurveType::ED25519, &data, &signature)
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "invalid_curve_type");
+    }
 }
@@ -1,6 +1,6 @@
 use crate::ecc::{KeyError, PrivateKey as TraitPrivateKey, PublicKey as TraitPublicKey};
 use crate::{FromHex, Result, ToHex};
-use sp_core::ed25519::{Pair, Public};
+use sp_core::ed25519::{Pair, Public, Signature};
 use sp_core::{Pair as TraitPair, Public as TraitPublic};
 
 #[derive(Clone)]
@@ -63,6 +63,17 @@ impl TraitPublicKey for Ed25519PublicKey {
     fn to_bytes(&self) -> Vec<u8> {
         self.0.to_vec()
     }
+
+    fn verify(&self, data: &[u8], signature: &[u8]) -> Result<bool> {
+        if signature.len() != 64 {
+            return Err(KeyError::InvalidSignature.into());
+        }
+        Ok(Pair::verify(
+            &Signature::from_slice(signature),
+            data,
+            &self.0,
+        ))
+    }
 }
 
 impl ToHex for Ed25519PublicKey {
@@ -0,0 +1,220 @@
+use iop_keyvault::ed25519::{Ed25519, EdExtPrivateKey, EdPublicKey};
+use iop_keyvault::{
+    ExtendedPrivateKey, KeyDerivationCrypto, PrivateKey as iop_keyvault_private_key, Seed,
+};
+
+use super::Result;
+use crate::ecc::KeyError;
+use crate::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
+use crate::{
+    mnemonic_to_seed_with_passphrase, Derive, DeriveJunction, DerivePath, DeterministicPrivateKey,
+    DeterministicPublicKey, FromHex, PrivateKey, PublicKey, ToHex,
+};
+use tcx_constants::CurveType;
+
+/// A SLIP-10 ed25519 extended private key, the hardened only derivation used by Solana, Stellar,
+/// Aptos and NEAR wallets. The Cardano BIP32-Ed25519 scheme is not implemented
+pub struct Ed25519DeterministicPrivateKey(EdExtPrivateKey);
+
+pub struct Ed25519DeterministicPublicKey(EdPublicKey);
+
+impl Ed25519DeterministicPrivateKey {
+    pub fn from_seed(seed: &[u8]) -> Result<Self> {
+        let seed_obj = Seed::from_bytes(&seed).unwrap();
+        let master = Ed25519::master(&seed_obj);
+        Ok(Ed25519DeterministicPrivateKey(master))
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Result<Self> {
+        Self::from_mnemonic_with_passphrase(mnemonic, "")
+    }
+
+    pub fn from_mnemonic_with_passphrase(mnemonic: &str, passphrase: &str) -> Result<Self> {
+        let seed = mnemonic_to_seed_with_passphrase(mnemonic, passphrase)?;
+        Ok(Self::from_seed(&seed)?)
+    }
+}
+
+impl Derive for Ed25519DeterministicPrivateKey {
+    fn derive(&self, path: &str) -> Result<Self> {
+        let mut extended_key = self.0.clone();
+
+        // the path only holds hardened indices below 2^31
+        for junction in &DerivePath::parse(CurveType::ED25519, path)? {
+            if let DeriveJunction::Hard(index) = junction {
+                extended_key = extended_key.derive_hardened_child(index as _).unwrap();
+            }
+        }
+
+        Ok(Ed25519DeterministicPrivateKey(extended_key))
+    }
+}
+
+/// SLIP-10 ed25519 only has hardened children, none of them can be derived from a public key
+impl Derive for Ed25519DeterministicPublicKey {
+    fn derive(&self, _path: &str) -> Result<Self> {
+        Err(KeyError::UnsupportEd25519PubkeyDerivation.into())
+    }
+}
+
+impl DeterministicPrivateKey for Ed25519DeterministicPrivateKey {
+    type DeterministicPublicKey = Ed25519DeterministicPublicKey;
+    type PrivateKey = Ed25519PrivateKey;
+
+    fn from_seed(seed: &[u8]) -> Result<Self> {
+        let seed_obj = Seed::from_bytes(&seed).unwrap();
+        let master = Ed25519::master(&seed_obj);
+        Ok(Ed25519DeterministicPrivateKey(master))
+    }
+
+    fn from_mnemonic_with_passphrase(mnemonic: &str, passphrase: &str) -> Result<Self> {
+        Self::from_mnemonic_with_passphrase(mnemonic, passphrase)
+    }
+
+    fn private_key(&self) -> Self::PrivateKey {
+        Ed25519PrivateKey::from_slice(self.0.private_key().to_bytes().as_slice()).unwrap()
+    }
+
+    fn deterministic_public_key(&self) -> Self::DeterministicPublicKey {
+        Ed25519DeterministicPublicKey(self.0.private_key().public_key())
+    }
+}
+
+impl DeterministicPublicKey for Ed25519DeterministicPublicKey {
+    type PublicKey = Ed25519PublicKey;
+
+    fn public_key(&self) -> Self::PublicKey {
+        Ed25519PublicKey::from_slice(self.0.to_bytes().as_slice()).unwrap()
+    }
+}
+
+impl ToString for Ed25519DeterministicPrivateKey {
+    fn to_string(&self) -> String {
+        hex::encode(self.0.private_key().to_bytes())
+    }
+}
+
+impl ToString for Ed25519DeterministicPublicKey {
+    fn to_string(&self) -> String {
+        hex::encode(self.0.to_bytes())
+    }
+}
+
+impl ToHex for Ed25519DeterministicPublicKey {
+    fn to_hex(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl FromHex for Ed25519DeterministicPublicKey {
+    fn from_hex(_hex: &str) -> Result<Self> {
+        Err(KeyError::UnsupportEd25519PubkeyDerivation.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ed25519_slip10::Ed25519DeterministicPrivateKey;
+    use crate::{Derive, DeterministicPrivateKey, PrivateKey as _, PublicKey};
+    use bip39::{Language, Mnemonic, Seed};
+    use hex;
+    use iop_keyvault::{ExtendedPrivateKey, PrivateKey};
+
+    fn default_seed() -> Seed {
+        let mn = Mnemonic::from_phrase(
+            "inject kidney empty canal shadow pact comfort wife crush horse wife sketch",
+            Language::English,
+        )
+        .unwrap();
+        Seed::new(&mn, "")
+    }
+
+    #[test]
+    fn slip10_test_vector_1() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let esk = Ed25519DeterministicPrivateKey::from_seed(&seed).unwrap();
+        assert_eq!(
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+            hex::encode(esk.0.private_key().to_bytes())
+        );
+        assert_eq!(
+            "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+            hex::encode(esk.0.chain_code().to_bytes())
+        );
+        // the vectors list the public keys with a leading 00 byte
+        assert_eq!(
+            "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed",
+            hex::encode(esk.private_key().public_key().to_bytes())
+        );
+
+        let derived = esk.derive("m/0'").unwrap();
+        assert_eq!(
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+            hex::encode(derived.0.private_key().to_bytes())
+        );
+        assert_eq!(
+            "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+            hex::encode(derived.0.chain_code().to_bytes())
+        );
+        assert_eq!(
+            "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c",
+            hex::encode(derived.private_key().public_key().to_bytes())
+        );
+
+        let err = esk.derive("m/0'/1").err().unwrap();
+        assert_eq!(format!("{}", err), "unsupport_normal_derivation# 1");
+    }
+
+    #[test]
+    fn slip10_test_vector_2() {
+        let seed = hex::decode("fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542").unwrap();
+        let esk = Ed25519DeterministicPrivateKey::from_seed(&seed).unwrap();
+        let cases = vec![
+            (
+                "m",
+                "171cb88b1b3c1db25add599712e36245d75bc65a1a5c9e18d76f9f2b1eab4012",
+                "ef70a74db9c3a5af931b5fe73ed8e1a53464133654fd55e7a66f8570b8e33c3b",
+                "8fe9693f8fa62a4305a140b9764c5ee01e455963744fe18204b4fb948249308a",
+            ),
+            (
+                "m/0'",
+                "1559eb2bbec5790b0c65d8693e4d0875b1747f4970ae8b650486ed7470845635",
+                "0b78a3226f915c082bf118f83618a618ab6dec793752624cbeb622acb562862d",
+                "86fab68dcb57aa196c77c5f264f215a112c22a912c10d123b0d03c3c28ef1037",
+            ),
+            (
+                "m/0'/2147483647'",
+                "ea4f5bfe8694d8bb74b7b59404632fd5968b774ed545e810de9c32a4fb4192f4",
+                "138f0b2551bcafeca6ff2aa88ba8ed0ed8de070841f0c4ef0165df8181eaad7f",
+                "5ba3b9ac6e90e83effcd25ac4e58a1365a9e35a3d3ae5eb07b9e4d90bcf7506d",
+            ),
+            (
+                "m/0'/2147483647'/1'",
+                "3757c7577170179c7868353ada796c839135b3d30554bbb74a4b1e4a5a58505c",
+                "73bd9fff1cfbde33a1b846c27085f711c0fe2d66fd32e139d3ebc28e5a4a6b90",
+                "2e66aa57069c86cc18249aecf5cb5a9cebbfd6fadeab056254763874a9352b45",
+            ),
+            (
+                "m/0'/2147483647'/1'/2147483646'",
+                "5837736c89570de861ebc173b1086da4f505d4adb387c6a1b1342d5e4ac9ec72",
+                "0902fe8a29f9140480a00ef244bd183e8a13288e4412d8389d140aac1794825a",
+                "e33c0f7d81d843c572275f287498e8d408654fdf0d1e065b84e2e6f157aab09b",
+            ),
+            (
+                "m/0'/2147483647'/1'/2147483646'/2'",
+                "551d333177df541ad876a60ea71f00447931c0a9da16f227c11ea080d7391b8d",
+                "5d70af781f3a37b829f0d060924d5e960bdc02e85423494afc0b1a41bbe196d4",
+                "47150c75db263559a70d5778bf36abbab30fb061ad69f69ece61a72b0cfa4fc0",
+            ),
+        ];
+        for (path, private_key, chain_code, public_key) in cases {
+            let derived = esk.derive(path).unwrap();
+            assert_eq!(private_key, hex::encode(derived.0.private_key().to_bytes()));
+            assert_eq!(chain_code, hex::encode(derived.0.chain_code().to_bytes()));
+            assert_eq!(
+                public_key,
+                hex::encode(derived.private_key().public_key().to_bytes())
+            );
+        }
+    }
+}
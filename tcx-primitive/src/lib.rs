@@ -2,15 +2,20 @@
 extern crate failure;
 
 mod bip32;
+#[cfg(feature = "bls")]
 mod bls;
+#[cfg(feature = "bls")]
 mod bls_derive;
 mod constant;
 mod derive;
 mod ecc;
 mod ed25519;
-mod ed25519_bip32;
+mod ed25519_slip10;
+mod mnemonic;
+mod nist256p1;
 mod rand;
 mod secp256k1;
+mod seed;
 mod sr25519;
 mod subkey;
 
@@ -19,17 +24,28 @@ use core::result;
 pub type Result<T> = result::Result<T, failure::Error>;
 
 pub use crate::bip32::{Bip32DeterministicPrivateKey, Bip32DeterministicPublicKey};
-pub use crate::derive::{get_account_path, Derive, DeriveJunction, DerivePath};
+pub use crate::derive::{get_account_path, get_hardened_path, Derive, DeriveJunction, DerivePath};
 pub use crate::ecc::{
     DeterministicPrivateKey, DeterministicPublicKey, PrivateKey, PublicKey,
     TypedDeterministicPrivateKey, TypedDeterministicPublicKey, TypedPrivateKey,
     TypedPrivateKeyDisplay, TypedPublicKey,
 };
 pub use crate::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
-pub use crate::rand::generate_mnemonic;
+pub use crate::mnemonic::{
+    check_entropy, language_code, language_from_code, mnemonic_candidates, mnemonic_from_entropy,
+    normalize_mnemonic, validate_mnemonic, MnemonicError, LANGUAGES, MISSING_WORD,
+};
+pub use crate::nist256p1::{
+    Nist256p1DeterministicPrivateKey, Nist256p1DeterministicPublicKey, Nist256p1PrivateKey,
+    Nist256p1PublicKey,
+};
+pub use crate::rand::{
+    generate_mnemonic, generate_mnemonic_with_language, generate_mnemonic_with_word_count,
+};
 pub use crate::secp256k1::{
     private_key_without_version, verify_private_key, Secp256k1PrivateKey, Secp256k1PublicKey,
 };
+pub use crate::seed::{mnemonic_to_seed, mnemonic_to_seed_with_passphrase};
 pub use crate::sr25519::{Sr25519PrivateKey, Sr25519PublicKey};
 
 /// Key that can be encoded to/from SS58.
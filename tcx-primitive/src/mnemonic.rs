@@ -0,0 +1,386 @@
+use bip39::{ErrorKind, Language, Mnemonic, MnemonicType};
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+use super::Result;
+
+/// The wordlists tried by auto-detection, in this order. The chinese wordlists share most of
+/// their characters, a phrase valid in both is taken as simplified chinese
+pub const LANGUAGES: [Language; 8] = [
+    Language::English,
+    Language::ChineseSimplified,
+    Language::ChineseTraditional,
+    Language::Japanese,
+    Language::Korean,
+    Language::Spanish,
+    Language::French,
+    Language::Italian,
+];
+
+#[derive(Fail, Debug, PartialEq)]
+pub enum MnemonicError {
+    #[fail(display = "mnemonic_language_unsupported")]
+    LanguageUnsupported,
+    #[fail(display = "mnemonic_language_unknown")]
+    LanguageUnknown,
+    #[fail(display = "mnemonic_language_mismatch")]
+    LanguageMismatch,
+    #[fail(display = "mnemonic_length_invalid")]
+    LengthInvalid,
+    #[fail(display = "entropy_length_invalid")]
+    EntropyLengthInvalid,
+    #[fail(display = "entropy_too_weak")]
+    EntropyTooWeak,
+    #[fail(display = "mnemonic_too_many_invalid_words")]
+    TooManyInvalidWords,
+}
+
+/// The language of a BIP-39 language code, e.g. `en`, `zh-hans`, `zh-hant`, `ja`, `ko` or `es`
+pub fn language_from_code(code: &str) -> Result<Language> {
+    Language::from_language_code(&code.to_lowercase())
+        .ok_or_else(|| MnemonicError::LanguageUnsupported.into())
+}
+
+pub fn language_code(language: Language) -> &'static str {
+    match language {
+        Language::English => "en",
+        Language::ChineseSimplified => "zh-hans",
+        Language::ChineseTraditional => "zh-hant",
+        Language::French => "fr",
+        Language::Italian => "it",
+        Language::Japanese => "ja",
+        Language::Korean => "ko",
+        Language::Spanish => "es",
+    }
+}
+
+/// The NFKD form of the words joined by single spaces, which is how the wordlists are stored and
+/// what BIP-39 hashes into the seed. Japanese phrases are often separated by ideographic spaces
+pub fn normalize_mnemonic(mnemonic: &str) -> Zeroizing<String> {
+    Zeroizing::new(
+        mnemonic
+            .split_whitespace()
+            .map(|word| word.nfkd().collect::<String>())
+            .collect::<Vec<String>>()
+            .join(" "),
+    )
+}
+
+/// Validate a normalized mnemonic against `language`, or detect its language when `None`.
+///
+/// The bip39 errors are kept for phrases of a known language so the checksum and length errors
+/// stay the same as for english.
+pub fn validate_mnemonic(mnemonic: &str, language: Option<Language>) -> Result<Language> {
+    match language {
+        Some(language) => match Mnemonic::validate(mnemonic, language) {
+            Ok(()) => Ok(language),
+            Err(err) if is_invalid_word(&err) => match detect_language(mnemonic) {
+                Ok(_) => Err(MnemonicError::LanguageMismatch.into()),
+                Err(_) => Err(err),
+            },
+            Err(err) => Err(err),
+        },
+        None => detect_language(mnemonic),
+    }
+}
+
+/// The mnemonic of caller supplied entropy of 16, 20, 24, 28 or 32 bytes, i.e. 12 to 24 words
+pub fn mnemonic_from_entropy(entropy: &[u8], language: Language) -> Result<Zeroizing<String>> {
+    check_entropy(entropy)?;
+    let mnemonic = Mnemonic::from_entropy(entropy, language)?;
+    Ok(Zeroizing::new(mnemonic.phrase().to_owned()))
+}
+
+/// Reject entropy that is obviously not random: too few distinct bytes, a repeated pattern or a
+/// constant step like `00 01 02 ..`. This is no randomness test, it catches typos and placeholders
+pub fn check_entropy(entropy: &[u8]) -> Result<()> {
+    let len = entropy.len();
+    if len < 16 || len > 32 || len % 4 != 0 {
+        return Err(MnemonicError::EntropyLengthInvalid.into());
+    }
+
+    let mut distinct = entropy.to_vec();
+    distinct.sort();
+    distinct.dedup();
+    let repeated =
+        (1..=len / 2).any(|period| (period..len).all(|i| entropy[i] == entropy[i - period]));
+    let step = entropy[1].wrapping_sub(entropy[0]);
+    let stepped = entropy
+        .windows(2)
+        .all(|pair| pair[1].wrapping_sub(pair[0]) == step);
+    if distinct.len() * 2 < len || repeated || stepped {
+        return Err(MnemonicError::EntropyTooWeak.into());
+    }
+    Ok(())
+}
+
+/// The placeholder of a missing word at a known position for `mnemonic_candidates`
+pub const MISSING_WORD: &str = "?";
+
+/// The phrases satisfying the checksum for a normalized mnemonic with one invalid or missing word.
+///
+/// An invalid word is replaced by the words starting with it, or by every word when none does, so
+/// a truncated `kidn` has one candidate. A missing word is marked by `?` or left out, in which
+/// case it's tried at every position.
+pub fn mnemonic_candidates(mnemonic: &str, language: Option<Language>) -> Result<Vec<String>> {
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+    let language = match language {
+        Some(language) => language,
+        None => match most_known_language(&words) {
+            (language, known) if known * 2 > words.len() => language,
+            _ => return Err(MnemonicError::LanguageUnknown.into()),
+        },
+    };
+    let wordmap = language.wordmap();
+    let invalid: Vec<usize> = (0..words.len())
+        .filter(|i| wordmap.get_bits(words[*i]).is_err())
+        .collect();
+
+    let templates: Vec<(Vec<&str>, usize)> = match invalid.as_slice() {
+        [] => {
+            if MnemonicType::for_word_count(words.len() + 1).is_err() {
+                // no word missing, the phrase is either valid or has another mistake
+                let phrase = words.join(" ");
+                validate_mnemonic(&phrase, Some(language))?;
+                return Ok(vec![phrase]);
+            }
+            (0..=words.len())
+                .map(|position| {
+                    let mut template = words.clone();
+                    template.insert(position, MISSING_WORD);
+                    (template, position)
+                })
+                .collect()
+        }
+        [position] => {
+            MnemonicType::for_word_count(words.len()).map_err(|_| MnemonicError::LengthInvalid)?;
+            vec![(words.clone(), *position)]
+        }
+        _ => return Err(MnemonicError::TooManyInvalidWords.into()),
+    };
+
+    let wordlist = language.wordlist();
+    let all_words: Vec<&str> = (0..2048u16)
+        .map(|bits| wordlist.get_word(bits.into()))
+        .collect();
+    let mut candidates: Vec<String> = vec![];
+    for (mut template, position) in templates {
+        let prefixed: Vec<&str> = all_words
+            .iter()
+            .filter(|word| word.starts_with(template[position]))
+            .cloned()
+            .collect();
+        let choices = if prefixed.is_empty() {
+            &all_words
+        } else {
+            &prefixed
+        };
+        for word in choices {
+            template[position] = *word;
+            let phrase = template.join(" ");
+            // inserting a word next to the same word gives the same phrase twice
+            if Mnemonic::validate(&phrase, language).is_ok() && !candidates.contains(&phrase) {
+                candidates.push(phrase);
+            }
+        }
+    }
+    Ok(candidates)
+}
+
+fn detect_language(mnemonic: &str) -> Result<Language> {
+    let mut known_words_err = None;
+    for language in LANGUAGES.iter() {
+        match Mnemonic::validate(mnemonic, *language) {
+            Ok(()) => return Ok(*language),
+            // every word is in this wordlist, the checksum or the length is wrong
+            Err(err) if !is_invalid_word(&err) => {
+                known_words_err.get_or_insert(err);
+            }
+            Err(_) => {}
+        }
+    }
+    if let Some(err) = known_words_err {
+        return Err(err);
+    }
+
+    // a misspelled word if most words are in one wordlist
+    let words: Vec<&str> = mnemonic.split(' ').collect();
+    let (_, most_known) = most_known_language(&words);
+    if most_known * 2 > words.len() {
+        Err(ErrorKind::InvalidWord.into())
+    } else {
+        Err(MnemonicError::LanguageUnknown.into())
+    }
+}
+
+/// The language knowing the most words and how many it knows, the first of `LANGUAGES` on a tie
+fn most_known_language(words: &[&str]) -> (Language, usize) {
+    let mut most_known = (LANGUAGES[0], 0);
+    for language in LANGUAGES.iter() {
+        let wordmap = language.wordmap();
+        let known = words
+            .iter()
+            .filter(|word| wordmap.get_bits(word).is_ok())
+            .count();
+        if known > most_known.1 {
+            most_known = (*language, known);
+        }
+    }
+    most_known
+}
+
+fn is_invalid_word(err: &failure::Error) -> bool {
+    match err.downcast_ref::<ErrorKind>() {
+        Some(ErrorKind::InvalidWord) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_entropy, language_code, language_from_code, mnemonic_candidates,
+        mnemonic_from_entropy, normalize_mnemonic, validate_mnemonic,
+    };
+    use bip39::{ErrorKind, Language};
+    use tcx_constants::TEST_MNEMONIC;
+
+    static JAPANESE_MNEMONIC: &str = "そつう　れきだい　ほんやく　わかす　りくつ　ばいか　ろせん　やちん　そつう　れきだい　ほんやく　わかめ";
+    static CHINESE_MNEMONIC: &str = "枪 疫 霉 尝 俩 闹 饿 贤 枪 疫 霉 卿";
+    static SPANISH_MNEMONIC: &str =
+        "ligero vista talar yogur venta queso yacer trozo ligero vista talar zafiro";
+
+    #[test]
+    fn test_detect_language() {
+        let cases = vec![
+            (TEST_MNEMONIC, Language::English),
+            (JAPANESE_MNEMONIC, Language::Japanese),
+            (CHINESE_MNEMONIC, Language::ChineseSimplified),
+            (SPANISH_MNEMONIC, Language::Spanish),
+        ];
+        for (mnemonic, language) in cases {
+            let normalized = normalize_mnemonic(mnemonic);
+            assert_eq!(validate_mnemonic(&normalized, None).unwrap(), language);
+            assert_eq!(
+                validate_mnemonic(&normalized, Some(language)).unwrap(),
+                language
+            );
+        }
+        // the composed kana are not in the wordlist until normalized
+        assert!(validate_mnemonic(JAPANESE_MNEMONIC, None).is_err());
+    }
+
+    #[test]
+    fn test_language_errors() {
+        let err = validate_mnemonic(
+            &normalize_mnemonic(SPANISH_MNEMONIC),
+            Some(Language::English),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(format!("{}", err), "mnemonic_language_mismatch");
+
+        let err = validate_mnemonic("foo bar baz qux quux corge", None)
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "mnemonic_language_unknown");
+
+        let misspelled = CHINESE_MNEMONIC.replace("卿", "dog");
+        let err = validate_mnemonic(&misspelled, None).err().unwrap();
+        assert!(matches!(
+            err.downcast_ref::<ErrorKind>(),
+            Some(ErrorKind::InvalidWord)
+        ));
+
+        let bad_checksum = CHINESE_MNEMONIC.replace("卿", "枪");
+        let err = validate_mnemonic(&bad_checksum, None).err().unwrap();
+        assert!(matches!(
+            err.downcast_ref::<ErrorKind>(),
+            Some(ErrorKind::InvalidChecksum)
+        ));
+
+        assert_eq!(
+            language_from_code("ZH-HANT").unwrap(),
+            Language::ChineseTraditional
+        );
+        assert_eq!(language_code(Language::Korean), "ko");
+        let err = language_from_code("klingon").err().unwrap();
+        assert_eq!(format!("{}", err), "mnemonic_language_unsupported");
+    }
+
+    #[test]
+    fn test_mnemonic_from_entropy() {
+        let entropy = hex::decode("7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7e").unwrap();
+        assert_eq!(
+            format!("{}", check_entropy(&entropy).err().unwrap()),
+            "entropy_too_weak"
+        );
+
+        let entropy = hex::decode("9e885d952ad362caeb4efe34a8e91bd2").unwrap();
+        let mnemonic = mnemonic_from_entropy(&entropy, Language::English).unwrap();
+        assert_eq!(
+            mnemonic.as_str(),
+            "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic"
+        );
+        let entropy =
+            hex::decode("68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c")
+                .unwrap();
+        let mnemonic = mnemonic_from_entropy(&entropy, Language::English).unwrap();
+        assert_eq!(mnemonic.split(' ').count(), 24);
+        assert!(mnemonic.starts_with("hamster diagram private dutch cause delay"));
+
+        let weak = vec![
+            "00000000000000000000000000000000",
+            "000102030405060708090a0b0c0d0e0f",
+            "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0",
+            "deadbeefdeadbeefdeadbeefdeadbeef",
+            "01010101020202020303030304040404",
+        ];
+        for entropy in weak {
+            let err = check_entropy(&hex::decode(entropy).unwrap()).err().unwrap();
+            assert_eq!(format!("{}", err), "entropy_too_weak");
+        }
+        for len in &[0, 15, 17, 33] {
+            let err = check_entropy(&vec![0x5a; *len]).err().unwrap();
+            assert_eq!(format!("{}", err), "entropy_length_invalid");
+        }
+    }
+
+    #[test]
+    fn test_mnemonic_candidates() {
+        let truncated = TEST_MNEMONIC.replace("kidney", "kidn");
+        assert_eq!(
+            mnemonic_candidates(&truncated, None).unwrap(),
+            vec![TEST_MNEMONIC.to_string()]
+        );
+
+        let unknown = TEST_MNEMONIC.replace("kidney", "?");
+        let candidates = mnemonic_candidates(&unknown, Some(Language::English)).unwrap();
+        assert!(candidates.len() > 1);
+        assert!(candidates.contains(&TEST_MNEMONIC.to_string()));
+        for candidate in &candidates {
+            assert!(validate_mnemonic(candidate, Some(Language::English)).is_ok());
+        }
+
+        let missing = TEST_MNEMONIC.replace("shadow ", "");
+        let candidates = mnemonic_candidates(&missing, None).unwrap();
+        assert!(candidates.contains(&TEST_MNEMONIC.to_string()));
+
+        let chinese = CHINESE_MNEMONIC.replace("俩", "?");
+        let candidates = mnemonic_candidates(&chinese, None).unwrap();
+        assert!(candidates.contains(&CHINESE_MNEMONIC.to_string()));
+
+        assert_eq!(
+            mnemonic_candidates(TEST_MNEMONIC, None).unwrap(),
+            vec![TEST_MNEMONIC.to_string()]
+        );
+
+        let err = mnemonic_candidates(&truncated.replace("shadow", "?"), None)
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "mnemonic_too_many_invalid_words");
+
+        let err = mnemonic_candidates("foo bar baz qux", None).err().unwrap();
+        assert_eq!(format!("{}", err), "mnemonic_language_unknown");
+    }
+}
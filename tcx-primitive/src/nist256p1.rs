@@ -0,0 +1,383 @@
+use crate::ecc::{KeyError, PrivateKey as TraitPrivateKey, PublicKey as TraitPublicKey};
+use crate::{
+    mnemonic_to_seed_with_passphrase, Derive, DeriveJunction, DerivePath, DeterministicPrivateKey,
+    DeterministicPublicKey, FromHex, Result, ToHex,
+};
+
+use hmac::{Hmac, Mac, NewMac};
+use p256::ecdsa::signature::{Signer, Verifier};
+use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use p256::elliptic_curve::ff::PrimeField;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use p256::{FieldBytes, ProjectivePoint, PublicKey, Scalar, SecretKey};
+use sha2::Sha512;
+use std::convert::TryFrom;
+use tcx_constants::CurveType;
+use zeroize::Zeroizing;
+
+/// The SLIP-10 hmac key of the master node
+const MASTER_SECRET: &[u8] = b"Nist256p1 seed";
+
+const HARDENED_BIT: u32 = 0x8000_0000;
+
+#[derive(Clone)]
+pub struct Nist256p1PublicKey(pub PublicKey);
+
+#[derive(Clone)]
+pub struct Nist256p1PrivateKey(pub SecretKey);
+
+impl From<PublicKey> for Nist256p1PublicKey {
+    fn from(pk: PublicKey) -> Self {
+        Nist256p1PublicKey(pk)
+    }
+}
+
+impl From<SecretKey> for Nist256p1PrivateKey {
+    fn from(sk: SecretKey) -> Self {
+        Nist256p1PrivateKey(sk)
+    }
+}
+
+impl TraitPrivateKey for Nist256p1PrivateKey {
+    type PublicKey = Nist256p1PublicKey;
+
+    fn from_slice(data: &[u8]) -> Result<Self> {
+        let sk = SecretKey::from_bytes(data).map_err(|_| KeyError::InvalidPrivateKey)?;
+        Ok(Nist256p1PrivateKey(sk))
+    }
+
+    fn public_key(&self) -> Self::PublicKey {
+        Nist256p1PublicKey(self.0.public_key())
+    }
+
+    /// ECDSA of the SHA-256 of `data` as in ES256, DER encoded. Unlike secp256k1 `data` is the
+    /// message itself rather than its hash
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let signature: Signature = SigningKey::from(self.0.clone()).sign(data);
+        Ok(signature.to_asn1().as_bytes().to_vec())
+    }
+
+    fn sign_recoverable(&self, _: &[u8]) -> Result<Vec<u8>> {
+        Err(KeyError::NotImplement.into())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes().to_vec()
+    }
+}
+
+impl TraitPublicKey for Nist256p1PublicKey {
+    fn from_slice(data: &[u8]) -> Result<Self> {
+        let pk = PublicKey::from_sec1_bytes(data).map_err(|_| KeyError::InvalidPublicKey)?;
+        Ok(Nist256p1PublicKey(pk))
+    }
+
+    /// The 33 bytes compressed SEC1 encoding
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_encoded_point(true).as_bytes().to_vec()
+    }
+
+    /// `signature` is DER encoded or 64 bytes `r || s`
+    fn verify(&self, data: &[u8], signature: &[u8]) -> Result<bool> {
+        let signature = if signature.len() == 64 {
+            Signature::try_from(signature)
+        } else {
+            Signature::from_asn1(signature)
+        }
+        .map_err(|_| KeyError::InvalidSignature)?;
+        Ok(VerifyingKey::from(&self.0).verify(data, &signature).is_ok())
+    }
+}
+
+impl ToHex for Nist256p1PublicKey {
+    fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+}
+
+impl FromHex for Nist256p1PublicKey {
+    fn from_hex(hex: &str) -> Result<Self> {
+        Nist256p1PublicKey::from_slice(&hex::decode(hex)?)
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> Zeroizing<Vec<u8>> {
+    let mut mac = Hmac::<Sha512>::new_varkey(key).expect("hmac accepts keys of any size");
+    mac.update(data);
+    Zeroizing::new(mac.finalize().into_bytes().to_vec())
+}
+
+/// `IL` of a SLIP-10 step as a scalar, `None` when it's not below the curve order
+fn parse_scalar(bytes: &[u8]) -> Option<Scalar> {
+    Scalar::from_repr(FieldBytes::clone_from_slice(bytes))
+}
+
+fn child_index(junction: DeriveJunction) -> Result<u32> {
+    match junction {
+        DeriveJunction::Soft(index) if index < HARDENED_BIT => Ok(index),
+        DeriveJunction::Hard(index) if index < HARDENED_BIT => Ok(index | HARDENED_BIT),
+        _ => Err(KeyError::InvalidChildNumber.into()),
+    }
+}
+
+/// A SLIP-10 extended private key of NIST P-256
+#[derive(Clone)]
+pub struct Nist256p1DeterministicPrivateKey {
+    private_key: SecretKey,
+    chain_code: Zeroizing<Vec<u8>>,
+}
+
+/// A SLIP-10 extended public key of NIST P-256, only normal children can be derived from it
+#[derive(Clone)]
+pub struct Nist256p1DeterministicPublicKey {
+    public_key: PublicKey,
+    chain_code: Vec<u8>,
+}
+
+impl Nist256p1DeterministicPrivateKey {
+    pub fn from_seed(seed: &[u8]) -> Result<Self> {
+        let mut i = hmac_sha512(MASTER_SECRET, seed);
+        // an invalid master key is retried with the hmac of the previous result
+        loop {
+            if let Ok(private_key) = SecretKey::from_bytes(&i[..32]) {
+                return Ok(Nist256p1DeterministicPrivateKey {
+                    private_key,
+                    chain_code: Zeroizing::new(i[32..].to_vec()),
+                });
+            }
+            i = hmac_sha512(MASTER_SECRET, &i);
+        }
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Result<Self> {
+        Self::from_mnemonic_with_passphrase(mnemonic, "")
+    }
+
+    pub fn from_mnemonic_with_passphrase(mnemonic: &str, passphrase: &str) -> Result<Self> {
+        let seed = mnemonic_to_seed_with_passphrase(mnemonic, passphrase)?;
+        Self::from_seed(&seed)
+    }
+
+    fn derive_child(&self, index: u32) -> Self {
+        let mut data = Zeroizing::new(vec![]);
+        if index & HARDENED_BIT == 0 {
+            data.extend_from_slice(
+                self.private_key
+                    .public_key()
+                    .to_encoded_point(true)
+                    .as_bytes(),
+            );
+        } else {
+            data.push(0);
+            data.extend_from_slice(&self.private_key.to_bytes());
+        }
+        data.extend_from_slice(&index.to_be_bytes());
+
+        loop {
+            let i = hmac_sha512(&self.chain_code, &data);
+            let parent = *self.private_key.secret_scalar().as_ref();
+            let child = parse_scalar(&i[..32])
+                .and_then(|il| SecretKey::from_bytes((il + parent).to_repr()).ok());
+            if let Some(private_key) = child {
+                return Nist256p1DeterministicPrivateKey {
+                    private_key,
+                    chain_code: Zeroizing::new(i[32..].to_vec()),
+                };
+            }
+            // IL is not below the order or the child key is zero
+            data.clear();
+            data.push(1);
+            data.extend_from_slice(&i[32..]);
+            data.extend_from_slice(&index.to_be_bytes());
+        }
+    }
+}
+
+impl Nist256p1DeterministicPublicKey {
+    fn derive_child(&self, index: u32) -> Result<Self> {
+        if index & HARDENED_BIT != 0 {
+            return Err(KeyError::CannotDeriveFromHardenedKey.into());
+        }
+        let mut data = self.public_key.to_encoded_point(true).as_bytes().to_vec();
+        data.extend_from_slice(&index.to_be_bytes());
+
+        loop {
+            let i = hmac_sha512(&self.chain_code, &data);
+            let child = parse_scalar(&i[..32]).and_then(|il| {
+                let point = ProjectivePoint::generator() * il + self.public_key.to_projective();
+                PublicKey::from_affine(point.to_affine()).ok()
+            });
+            if let Some(public_key) = child {
+                return Ok(Nist256p1DeterministicPublicKey {
+                    public_key,
+                    chain_code: i[32..].to_vec(),
+                });
+            }
+            data.clear();
+            data.push(1);
+            data.extend_from_slice(&i[32..]);
+            data.extend_from_slice(&index.to_be_bytes());
+        }
+    }
+}
+
+impl Derive for Nist256p1DeterministicPrivateKey {
+    fn derive(&self, path: &str) -> Result<Self> {
+        let mut extended_key = self.clone();
+        for junction in &DerivePath::parse(CurveType::NIST256p1, path)? {
+            extended_key = extended_key.derive_child(child_index(junction)?);
+        }
+        Ok(extended_key)
+    }
+}
+
+impl Derive for Nist256p1DeterministicPublicKey {
+    fn derive(&self, path: &str) -> Result<Self> {
+        let mut extended_key = self.clone();
+        for junction in &DerivePath::parse(CurveType::NIST256p1, path)? {
+            extended_key = extended_key.derive_child(child_index(junction)?)?;
+        }
+        Ok(extended_key)
+    }
+}
+
+impl DeterministicPrivateKey for Nist256p1DeterministicPrivateKey {
+    type DeterministicPublicKey = Nist256p1DeterministicPublicKey;
+    type PrivateKey = Nist256p1PrivateKey;
+
+    fn from_seed(seed: &[u8]) -> Result<Self> {
+        Self::from_seed(seed)
+    }
+
+    fn from_mnemonic_with_passphrase(mnemonic: &str, passphrase: &str) -> Result<Self> {
+        Self::from_mnemonic_with_passphrase(mnemonic, passphrase)
+    }
+
+    fn private_key(&self) -> Self::PrivateKey {
+        Nist256p1PrivateKey(self.private_key.clone())
+    }
+
+    fn deterministic_public_key(&self) -> Self::DeterministicPublicKey {
+        Nist256p1DeterministicPublicKey {
+            public_key: self.private_key.public_key(),
+            chain_code: self.chain_code.to_vec(),
+        }
+    }
+}
+
+impl DeterministicPublicKey for Nist256p1DeterministicPublicKey {
+    type PublicKey = Nist256p1PublicKey;
+
+    fn public_key(&self) -> Self::PublicKey {
+        Nist256p1PublicKey(self.public_key)
+    }
+}
+
+impl ToString for Nist256p1DeterministicPrivateKey {
+    fn to_string(&self) -> String {
+        hex::encode(self.private_key.to_bytes())
+    }
+}
+
+impl ToString for Nist256p1DeterministicPublicKey {
+    fn to_string(&self) -> String {
+        self.to_hex()
+    }
+}
+
+/// The compressed public key followed by the chain code, there is no xpub format for P-256
+impl ToHex for Nist256p1DeterministicPublicKey {
+    fn to_hex(&self) -> String {
+        let mut bytes = self.public_key.to_encoded_point(true).as_bytes().to_vec();
+        bytes.extend_from_slice(&self.chain_code);
+        hex::encode(bytes)
+    }
+}
+
+impl FromHex for Nist256p1DeterministicPublicKey {
+    fn from_hex(hex: &str) -> Result<Self> {
+        let bytes = hex::decode(hex)?;
+        if bytes.len() != 65 {
+            return Err(KeyError::InvalidPublicKey.into());
+        }
+        let public_key =
+            PublicKey::from_sec1_bytes(&bytes[..33]).map_err(|_| KeyError::InvalidPublicKey)?;
+        Ok(Nist256p1DeterministicPublicKey {
+            public_key,
+            chain_code: bytes[33..].to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Nist256p1DeterministicPrivateKey, Nist256p1DeterministicPublicKey};
+    use crate::{Derive, DeterministicPrivateKey, DeterministicPublicKey};
+    use crate::{FromHex, PrivateKey, PublicKey, ToHex};
+
+    #[test]
+    fn test_slip10_vectors() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let master = Nist256p1DeterministicPrivateKey::from_seed(&seed).unwrap();
+        assert_eq!(
+            master.to_string(),
+            "612091aaa12e22dd2abef664f8a01a82cae99ad7441b7ef8110424915c268bc2"
+        );
+        assert_eq!(
+            hex::encode(&*master.chain_code),
+            "beeb672fe4621673f722f38529c07392fecaa61015c80c34f29ce8b41b3cb6ea"
+        );
+        assert_eq!(
+            hex::encode(master.private_key().public_key().to_bytes()),
+            "0266874dc6ade47b3ecd096745ca09bcd29638dd52c2c12117b11ed3e458cfa9e8"
+        );
+
+        let child = master.derive("m/0'").unwrap();
+        assert_eq!(
+            child.to_string(),
+            "6939694369114c67917a182c59ddb8cafc3004e63ca5d3b84403ba8613debc0c"
+        );
+        assert_eq!(
+            hex::encode(&*child.chain_code),
+            "3460cea53e6a6bb5fb391eeef3237ffd8724bf0a40e94943c98b83825342ee11"
+        );
+        assert_eq!(
+            hex::encode(child.private_key().public_key().to_bytes()),
+            "0384610f5ecffe8fda089363a41f56a5c7ffc1d81b59a612d0d649b2d22355590c"
+        );
+    }
+
+    #[test]
+    fn test_public_derivation() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let account = Nist256p1DeterministicPrivateKey::from_seed(&seed)
+            .unwrap()
+            .derive("m/44'/0'/0'")
+            .unwrap();
+        let xpub = account.deterministic_public_key();
+        let xpub = Nist256p1DeterministicPublicKey::from_hex(&xpub.to_hex()).unwrap();
+
+        let child_xpub = xpub.derive("0/1").unwrap();
+        let child = account.derive("0/1").unwrap();
+        assert_eq!(
+            child_xpub.public_key().to_bytes(),
+            child.private_key().public_key().to_bytes()
+        );
+
+        let err = xpub.derive("0'").err().unwrap();
+        assert_eq!(format!("{}", err), "cannot_derive_from_hardened_key");
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let sk = Nist256p1DeterministicPrivateKey::from_seed(&seed)
+            .unwrap()
+            .private_key();
+        let pk = sk.public_key();
+        let signature = sk.sign(b"hello p256").unwrap();
+        assert!(pk.verify(b"hello p256", &signature).unwrap());
+        assert!(!pk.verify(b"hello p-256", &signature).unwrap());
+        assert!(sk.sign_recoverable(b"hello p256").is_err());
+    }
+}
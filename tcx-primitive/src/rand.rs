@@ -1,5 +1,38 @@
 use bip39::{Language, Mnemonic, MnemonicType};
 
+use super::Result;
+use crate::mnemonic::MnemonicError;
+
 pub fn generate_mnemonic() -> String {
-    Mnemonic::new(MnemonicType::Words12, Language::English).to_string()
+    generate_mnemonic_with_language(Language::English)
+}
+
+pub fn generate_mnemonic_with_language(language: Language) -> String {
+    Mnemonic::new(MnemonicType::Words12, language).to_string()
+}
+
+/// A new mnemonic of 12, 15, 18, 21 or 24 words
+pub fn generate_mnemonic_with_word_count(language: Language, word_count: usize) -> Result<String> {
+    let mnemonic_type =
+        MnemonicType::for_word_count(word_count).map_err(|_| MnemonicError::LengthInvalid)?;
+    Ok(Mnemonic::new(mnemonic_type, language).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate_mnemonic_with_word_count;
+    use bip39::Language;
+
+    #[test]
+    fn test_generate_mnemonic_with_word_count() {
+        for word_count in &[12, 15, 18, 21, 24] {
+            let mnemonic =
+                generate_mnemonic_with_word_count(Language::English, *word_count).unwrap();
+            assert_eq!(mnemonic.split(' ').count(), *word_count);
+        }
+        let err = generate_mnemonic_with_word_count(Language::English, 13)
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "mnemonic_length_invalid");
+    }
 }
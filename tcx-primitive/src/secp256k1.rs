@@ -9,7 +9,11 @@ use crate::{Result, Ss58Codec};
 use bitcoin::util::base58;
 
 use bitcoin::secp256k1::Message;
+use secp256k1::constants::SECRET_KEY_SIZE;
+use secp256k1::recovery::{RecoverableSignature, RecoveryId};
+use secp256k1::{SecretKey, Signature};
 use tcx_constants::{network_from_coin, CoinInfo};
+use zeroize::{Zeroize, Zeroizing};
 
 #[cfg_attr(tarpaulin, skip)]
 fn transform_secp256k1_error(err: secp256k1::Error) -> KeyError {
@@ -31,6 +35,18 @@ pub struct Secp256k1PublicKey(pub PublicKey);
 #[derive(Clone)]
 pub struct Secp256k1PrivateKey(pub PrivateKey);
 
+/// Overwrite the bytes of `key`, `SecretKey` is `Copy` so it can't wipe itself on drop
+pub(crate) fn zeroize_secret_key(key: &mut SecretKey) {
+    let bytes = unsafe { std::slice::from_raw_parts_mut(key.as_mut_ptr(), SECRET_KEY_SIZE) };
+    bytes.zeroize();
+}
+
+impl Drop for Secp256k1PrivateKey {
+    fn drop(&mut self) {
+        zeroize_secret_key(&mut self.0.key);
+    }
+}
+
 impl From<PublicKey> for Secp256k1PublicKey {
     fn from(pk: PublicKey) -> Self {
         Secp256k1PublicKey(pk)
@@ -51,6 +67,27 @@ impl Secp256k1PublicKey {
     pub fn to_uncompressed(&self) -> Vec<u8> {
         self.0.key.serialize_uncompressed().to_vec()
     }
+
+    /// The signer of a 65 bytes `r || s || v` signature of a 32 bytes hash as made by
+    /// `sign_recoverable`, `v` is the recovery id as 0/1 or as the ethereum 27/28
+    pub fn recover(data: &[u8], signature: &[u8]) -> Result<Self> {
+        if signature.len() != 65 {
+            return Err(KeyError::InvalidSignature.into());
+        }
+        let msg = Message::from_slice(data).map_err(transform_secp256k1_error)?;
+        let v = signature[64];
+        let recovery_id = RecoveryId::from_i32(i32::from(if v >= 27 { v - 27 } else { v }))
+            .map_err(transform_secp256k1_error)?;
+        let signature = RecoverableSignature::from_compact(&signature[..64], recovery_id)
+            .map_err(transform_secp256k1_error)?;
+        let key = SECP256K1_ENGINE
+            .recover(&msg, &signature)
+            .map_err(transform_secp256k1_error)?;
+        Ok(Secp256k1PublicKey(PublicKey {
+            compressed: true,
+            key,
+        }))
+    }
 }
 
 impl Secp256k1PrivateKey {
@@ -109,11 +146,27 @@ impl TraitPublicKey for Secp256k1PublicKey {
     fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// `signature` is DER encoded or 64 bytes `r || s`, optionally followed by a recovery id.
+    /// High-S signatures are accepted
+    fn verify(&self, data: &[u8], signature: &[u8]) -> Result<bool> {
+        let msg = Message::from_slice(data).map_err(transform_secp256k1_error)?;
+        let mut signature = if signature.len() == 65 {
+            Signature::from_compact(&signature[..64])
+        } else {
+            Signature::from_der(signature).or_else(|_| Signature::from_compact(signature))
+        }
+        .map_err(transform_secp256k1_error)?;
+        signature.normalize_s();
+        Ok(SECP256K1_ENGINE
+            .verify(&msg, &signature, &self.0.key)
+            .is_ok())
+    }
 }
 
 impl Ss58Codec for Secp256k1PrivateKey {
     fn from_ss58check_with_version(wif: &str) -> Result<(Self, Vec<u8>)> {
-        let data = base58::from_check(wif)?;
+        let data = Zeroizing::new(base58::from_check(wif)?);
 
         let compressed = match data.len() {
             33 => false,
@@ -133,7 +186,7 @@ impl Ss58Codec for Secp256k1PrivateKey {
     }
 
     fn to_ss58check_with_version(&self, version: &[u8]) -> String {
-        let mut ret = [0; 34];
+        let mut ret = Zeroizing::new([0; 34]);
         ret[0..1].copy_from_slice(&version[0..]);
         ret[1..33].copy_from_slice(&self.0.key[..]);
         if self.0.compressed {
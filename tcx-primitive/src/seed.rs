@@ -0,0 +1,67 @@
+use hmac::Hmac;
+use sha2::Sha512;
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+use crate::mnemonic::{normalize_mnemonic, validate_mnemonic};
+use crate::Result;
+
+const PBKDF2_ROUNDS: u32 = 2048;
+const SEED_LEN: usize = 64;
+
+/// The BIP-39 seed of `mnemonic` with an empty passphrase. It is computed here rather than by
+/// `bip39::Seed`, which keeps the seed in a plain `Vec`, so the bytes are wiped when dropped
+pub fn mnemonic_to_seed(mnemonic: &str) -> Result<Zeroizing<Vec<u8>>> {
+    mnemonic_to_seed_with_passphrase(mnemonic, "")
+}
+
+/// The BIP-39 seed of `mnemonic` salted by `passphrase`, the "25th word". Both are NFKD
+/// normalized and the mnemonic may be of any supported language
+pub fn mnemonic_to_seed_with_passphrase(
+    mnemonic: &str,
+    passphrase: &str,
+) -> Result<Zeroizing<Vec<u8>>> {
+    let mnemonic = normalize_mnemonic(mnemonic);
+    validate_mnemonic(&mnemonic, None)?;
+
+    let salt = Zeroizing::new(format!("mnemonic{}", passphrase.nfkd().collect::<String>()));
+    let mut seed = Zeroizing::new(vec![0u8; SEED_LEN]);
+    pbkdf2::pbkdf2::<Hmac<Sha512>>(
+        mnemonic.as_bytes(),
+        salt.as_bytes(),
+        PBKDF2_ROUNDS,
+        &mut seed[..],
+    );
+    Ok(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mnemonic_to_seed, mnemonic_to_seed_with_passphrase};
+    use bip39::{Language, Mnemonic, Seed};
+    use tcx_constants::TEST_MNEMONIC;
+
+    #[test]
+    fn test_mnemonic_to_seed() {
+        let mn = Mnemonic::from_phrase(TEST_MNEMONIC, Language::English).unwrap();
+        let seed = mnemonic_to_seed(TEST_MNEMONIC).unwrap();
+        assert_eq!(seed.as_slice(), Seed::new(&mn, "").as_bytes());
+
+        let seed = mnemonic_to_seed_with_passphrase(TEST_MNEMONIC, "TREZOR").unwrap();
+        assert_eq!(seed.as_slice(), Seed::new(&mn, "TREZOR").as_bytes());
+
+        // the japanese vector is composed with ideographic spaces, the seed is of the NFKD form
+        let seed = mnemonic_to_seed_with_passphrase(
+            "そつう　れきだい　ほんやく　わかす　りくつ　ばいか　ろせん　やちん　そつう　れきだい　ほんやく　わかめ",
+            "㍍ガバヴァぱばぐゞちぢ十人十色",
+        )
+        .unwrap();
+        assert_eq!(hex::encode(seed.as_slice()), "aee025cbe6ca256862f889e48110a6a382365142f7d16f2b9545285b3af64e542143a577e9c144e101a6bdca18f8d97ec3366ebf5b088b1c1af9bc31346e60d9");
+
+        let err =
+            mnemonic_to_seed("inject kidney empty canal shadow pact comfort wife crush horse")
+                .err()
+                .unwrap();
+        assert!(err.downcast::<bip39::ErrorKind>().is_ok());
+    }
+}
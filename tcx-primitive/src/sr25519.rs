@@ -2,7 +2,7 @@ use crate::ecc::{KeyError, PrivateKey as TraitPrivateKey, PublicKey as TraitPubl
 use crate::{FromHex, Result, ToHex};
 use schnorrkel::SecretKey;
 
-use sp_core::sr25519::{Pair, Public};
+use sp_core::sr25519::{Pair, Public, Signature};
 use sp_core::{Pair as TraitPair, Public as TraitPublic};
 
 //use sp_core::crypto::Ss58Codec;
@@ -69,6 +69,17 @@ impl TraitPublicKey for Sr25519PublicKey {
     fn to_bytes(&self) -> Vec<u8> {
         self.0.to_vec()
     }
+
+    fn verify(&self, data: &[u8], signature: &[u8]) -> Result<bool> {
+        if signature.len() != 64 {
+            return Err(KeyError::InvalidSignature.into());
+        }
+        Ok(Pair::verify(
+            &Signature::from_slice(signature),
+            data,
+            &self.0,
+        ))
+    }
 }
 
 impl ToHex for Sr25519PublicKey {
@@ -1,33 +1,45 @@
 use super::Result;
 
 use crate::ecc::{DeterministicPrivateKey, DeterministicPublicKey};
+use crate::mnemonic::{normalize_mnemonic, validate_mnemonic};
 
+use crate::ecc::KeyError;
 use crate::sr25519::{Sr25519PrivateKey, Sr25519PublicKey};
-use crate::Derive;
-use regex::Regex;
+use crate::{Derive, DeriveJunction, DerivePath};
+use bip39::{Language, Mnemonic};
 use sp_core::crypto::Derive as SpDerive;
-use sp_core::crypto::DeriveJunction;
+use sp_core::crypto::DeriveJunction as SpDeriveJunction;
+use tcx_constants::CurveType;
 
 use sp_core::sr25519::Pair;
 use sp_core::Pair as TraitPair;
 
+fn junctions(path: &str) -> Result<Vec<SpDeriveJunction>> {
+    Ok(DerivePath::parse(CurveType::SubSr25519, path)?
+        .into_iter()
+        .map(|junction| match junction {
+            DeriveJunction::Named { name, hard: true } => {
+                SpDeriveJunction::from(format!("/{}", name))
+            }
+            DeriveJunction::Named { name, hard: false } => SpDeriveJunction::from(name),
+            _ => unreachable!("substrate paths only hold named junctions"),
+        })
+        .collect())
+}
+
 impl Derive for Sr25519PrivateKey {
     fn derive(&self, path: &str) -> Result<Self> {
-        let re_junction = Regex::new(r"/(/?[^/]+)")?;
-        let junctions = re_junction
-            .captures_iter(path)
-            .map(|f| DeriveJunction::from(&f[1]));
-        Ok(Sr25519PrivateKey(self.0.derive(junctions, None).unwrap().0))
+        let pair = self.0.derive(junctions(path)?.into_iter(), None);
+        Ok(Sr25519PrivateKey(pair.unwrap().0))
     }
 }
 
 impl Derive for Sr25519PublicKey {
     fn derive(&self, path: &str) -> Result<Self> {
-        let re_junction = Regex::new(r"/(/?[^/]+)")?;
-        let junctions = re_junction
-            .captures_iter(path)
-            .map(|f| DeriveJunction::from(&f[1]));
-        Ok(Sr25519PublicKey(self.0.derive(junctions).unwrap()))
+        let public = self.0.derive(junctions(path)?.into_iter());
+        Ok(Sr25519PublicKey(
+            public.ok_or(KeyError::CannotDeriveFromHardenedKey)?,
+        ))
     }
 }
 
@@ -40,8 +52,24 @@ impl DeterministicPrivateKey for Sr25519PrivateKey {
         Ok(Sr25519PrivateKey(pair))
     }
 
-    fn from_mnemonic(mnemonic: &str) -> Result<Self> {
-        let pair = Pair::from_phrase(mnemonic, None).map_err(|_| format_err!("mnemonic_error"))?;
+    /// Substrate derives the mini secret key from the entropy of the mnemonic rather than the
+    /// BIP-39 seed, the passphrase salts that derivation the same way. It only reads english
+    /// phrases, so other languages are passed as the english phrase of the same entropy
+    fn from_mnemonic_with_passphrase(mnemonic: &str, passphrase: &str) -> Result<Self> {
+        let mnemonic = normalize_mnemonic(mnemonic);
+        let language = validate_mnemonic(&mnemonic, None)?;
+        let english = Mnemonic::from_entropy(
+            Mnemonic::from_phrase(mnemonic.as_str(), language)?.entropy(),
+            Language::English,
+        )?;
+
+        let password = if passphrase.is_empty() {
+            None
+        } else {
+            Some(passphrase)
+        };
+        let pair = Pair::from_phrase(english.phrase(), password)
+            .map_err(|_| format_err!("mnemonic_error"))?;
         Ok(Sr25519PrivateKey(pair.0))
     }
 
@@ -96,6 +124,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_non_english_mnemonic() {
+        // the same entropy as "legal winner thank year wave sausage worth useful legal winner
+        // thank yellow"
+        let english = Sr25519PrivateKey::from_mnemonic(
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+        )
+        .unwrap();
+        let chinese =
+            Sr25519PrivateKey::from_mnemonic("枪 疫 霉 尝 俩 闹 饿 贤 枪 疫 霉 卿").unwrap();
+        assert_eq!(
+            english.private_key().0.public().to_vec(),
+            chinese.private_key().0.public().to_vec()
+        );
+    }
+
     #[test]
     fn test_private_key_derive() {
         let hd_key: Sr25519PrivateKey = Sr25519PrivateKey::from_mnemonic(TEST_MNEMONIC).unwrap();
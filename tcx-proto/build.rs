@@ -1,18 +1,49 @@
 use std::env;
 extern crate prost_build;
 
+// every message derives serde so `call_tcx_api_json` can map json to the prost messages 1:1,
+// `Any` fields are filled by the json transport according to the method and the chain type
+fn prost_config() -> prost_build::Config {
+    let mut config = prost_build::Config::new();
+    config.type_attribute(".", "#[derive(serde::Serialize, serde::Deserialize)]");
+    config.type_attribute(".", "#[serde(rename_all = \"camelCase\")]");
+    config.field_attribute(".", "#[serde(default)]");
+    config.field_attribute(".api.TcxAction.param", "#[serde(skip)]");
+    config.field_attribute(".api.SignParam.input", "#[serde(skip)]");
+    config.field_attribute(".api.BatchSignResult.SignResult.output", "#[serde(skip)]");
+    // int64 and uint64 are json strings in proto3, js numbers can't hold them
+    for field in &[
+        ".api.BtcForkNetworkConfig.dustThreshold",
+        ".api.BtcForkNetworkConfig.maxFee",
+        ".api.WalletResult.createdAt",
+        ".transaction.Utxo.amount",
+        ".transaction.Utxo.sequence",
+        ".transaction.BtcForkTxInput.amount",
+        ".transaction.BtcForkTxInput.fee",
+        ".transaction.BtcForkTxInput.maxFee",
+        ".transaction.CachedCell.capacity",
+        ".transaction.UnsignedMessage.nonce",
+        ".transaction.UnsignedMessage.gasLimit",
+        ".transaction.UnsignedMessage.method",
+    ] {
+        config.field_attribute(field, "#[serde(with = \"tcx_constants::json_int64\")]");
+    }
+    config
+}
+
 fn main() {
     // tcx-api
     env::set_var("OUT_DIR", "../tcx/src");
-    prost_build::compile_protos(
-        &[
-            "src/api.proto",
-            "src/params.proto",
-            "src/cache_derived_key.proto",
-        ],
-        &["src/"],
-    )
-    .unwrap();
+    prost_config()
+        .compile_protos(
+            &[
+                "src/api.proto",
+                "src/params.proto",
+                "src/cache_derived_key.proto",
+            ],
+            &["src/"],
+        )
+        .unwrap();
 
     //    // tcx-chain
     //    env::set_var("OUT_DIR", "../tcx-chain/src");
@@ -20,22 +51,32 @@ fn main() {
 
     // tcx-tron
     env::set_var("OUT_DIR", "../tcx-tron/src");
-    prost_build::compile_protos(&["src/tron.proto"], &["src/"]).unwrap();
+    prost_config()
+        .compile_protos(&["src/tron.proto"], &["src/"])
+        .unwrap();
 
     // tcx-btc-fork
     env::set_var("OUT_DIR", "../tcx-btc-fork/src");
-    prost_build::compile_protos(&["src/btc_fork.proto"], &["src/"]).unwrap();
+    prost_config()
+        .compile_protos(&["src/btc_fork.proto"], &["src/"])
+        .unwrap();
 
     // tcx-ckb
     env::set_var("OUT_DIR", "../tcx-ckb/src");
-    prost_build::compile_protos(&["src/ckb.proto"], &["src/"]).unwrap();
+    prost_config()
+        .compile_protos(&["src/ckb.proto"], &["src/"])
+        .unwrap();
 
     // tcx-substrate
     env::set_var("OUT_DIR", "../tcx-substrate/src");
-    prost_build::compile_protos(&["src/substrate.proto"], &["src/"]).unwrap();
+    prost_config()
+        .compile_protos(&["src/substrate.proto"], &["src/"])
+        .unwrap();
 
     env::set_var("OUT_DIR", "../tcx-filecoin/src");
-    prost_build::compile_protos(&["src/filecoin.proto"], &["src/"]).unwrap();
+    prost_config()
+        .compile_protos(&["src/filecoin.proto"], &["src/"])
+        .unwrap();
 
     //    let targets = vec!["arm64-v8a", "armeabi-v7a", "x86", "x86_64"];
     //    for target in targets {
@@ -44,5 +85,7 @@ fn main() {
 
     //tcx-tezos
     env::set_var("OUT_DIR", "../tcx-tezos/src");
-    prost_build::compile_protos(&["src/tezos.proto"], &["src/"]).unwrap();
+    prost_config()
+        .compile_protos(&["src/tezos.proto"], &["src/"])
+        .unwrap();
 }
@@ -9,6 +9,7 @@ use byteorder::{ReadBytesExt, WriteBytesExt};
 use regex::Regex;
 use serde::export::{fmt, PhantomData};
 use std::io::Cursor;
+#[cfg(not(target_arch = "wasm32"))]
 use std::time::{SystemTime, UNIX_EPOCH};
 use tcx_constants::{CoinInfo, Result};
 use tcx_crypto::numberic_util::random_iv;
@@ -84,12 +85,19 @@ pub struct SubstrateKeystoreMeta {
     pub when_created: i64,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 fn metadata_default_time() -> i64 {
     let start = SystemTime::now();
     let since_the_epoch = start.duration_since(UNIX_EPOCH).expect("get timestamp");
     since_the_epoch.as_secs() as i64
 }
 
+// `SystemTime::now` panics in the browser
+#[cfg(target_arch = "wasm32")]
+fn metadata_default_time() -> i64 {
+    (js_sys::Date::now() / 1000.0) as i64
+}
+
 impl Default for SubstrateKeystoreMeta {
     fn default() -> Self {
         SubstrateKeystoreMeta {
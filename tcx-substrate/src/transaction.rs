@@ -1,26 +1,37 @@
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SubstrateKeystoreParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub keystore: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub password: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub chain_type: std::string::String,
     #[prost(bool, tag = "4")]
+    #[serde(default)]
     pub overwrite: bool,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ExportSubstrateKeystoreResult {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub keystore: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SubstrateRawTxIn {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub raw_data: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SubstrateTxOut {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub signature: std::string::String,
 }
@@ -4,7 +4,7 @@ use std::env;
 use std::ffi::CString;
 use std::os::raw::c_char;
 use tcx::api::InitTokenCoreXParam;
-use tcx::{call_tcx_api, get_last_err_message};
+use tcx::{call_tcx_api_with_context, get_last_err_message, TokenCore};
 
 pub fn encode_message(msg: impl Message) -> Vec<u8> {
     let mut buf = BytesMut::with_capacity(msg.encoded_len());
@@ -22,15 +22,19 @@ fn main() {
         xpub_common_key: "B888D25EC8C12BD5043777B1AC49F872".to_string(),
         xpub_common_iv: "9C0C30889CBCC5E01AB5B2BB88715799".to_string(),
         is_debug: true,
+        security_policy: None,
+        coins: vec![],
+        btc_fork_networks: vec![],
     };
 
     // let hex_str = hex::encode(encode_message(param))
-    tcx::handler::init_token_core_x(&encode_message(param)).unwrap();
+    let token_core = TokenCore::new("");
+    tcx::handler::init_token_core_x(&token_core, &encode_message(param)).unwrap();
 
     let args: Vec<String> = env::args().collect();
     let hex = &args[1];
     // let bytes = hex::decode(hex).expect("decode hex");
     // let param_buf = wrap_buffer(bytes);
-    unsafe { call_tcx_api(_to_c_char(&hex)) };
+    unsafe { call_tcx_api_with_context(&token_core, _to_c_char(&hex)) };
     unsafe { get_last_err_message() };
 }
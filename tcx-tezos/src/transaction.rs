@@ -1,14 +1,20 @@
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TezosRawTxIn {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub raw_data: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TezosTxOut {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub signature: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub edsig: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub sbytes: std::string::String,
 }
@@ -2,33 +2,43 @@
 // ref: https://developers.tron.network/docs/transaction
 
 /// FUNCTION: sign_tx(SignParam{input: TronTxInput}): TronTxOutput
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TronTxInput {
     /// hex string
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub raw_data: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TronTxOutput {
     /// hex string
     #[prost(string, repeated, tag = "1")]
+    #[serde(default)]
     pub signatures: ::std::vec::Vec<std::string::String>,
 }
 /// FUNCTION: tron_sign_message(SignParam): TronMessageOutput
 ///
 /// This api use the a common struct named `SignParam`, you should
 /// build the `TronMessageInput` and put it in the `input` field
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TronMessageInput {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub value: std::string::String,
     #[prost(bool, tag = "2")]
+    #[serde(default)]
     pub is_hex: bool,
     #[prost(bool, tag = "3")]
+    #[serde(default)]
     pub is_tron_header: bool,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TronMessageOutput {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub signature: std::string::String,
 }
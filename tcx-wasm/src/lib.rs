@@ -0,0 +1,61 @@
+//! TokenCore Wasm
+//! A wasm-bindgen facade of the keystore and signers for browsers. It keeps no state, the keystore
+//! json is passed in by the caller and the updated one is passed back for it to store.
+use wasm_bindgen::prelude::*;
+
+mod wallet;
+
+pub use crate::wallet::{Derivation, DeriveResult};
+
+fn to_js_error(err: failure::Error) -> JsValue {
+    JsValue::from_str(&err.to_string())
+}
+
+/// create a keystore with a new 12 words mnemonic, returns the keystore json
+#[wasm_bindgen(js_name = createKeystore)]
+pub fn create_keystore(password: &str, name: &str, password_hint: &str) -> Result<String, JsValue> {
+    wallet::create_keystore(password, name, password_hint).map_err(to_js_error)
+}
+
+#[wasm_bindgen(js_name = importMnemonic)]
+pub fn import_mnemonic(
+    mnemonic: &str,
+    password: &str,
+    name: &str,
+    password_hint: &str,
+) -> Result<String, JsValue> {
+    wallet::import_mnemonic(mnemonic, password, name, password_hint).map_err(to_js_error)
+}
+
+#[wasm_bindgen(js_name = importPrivateKey)]
+pub fn import_private_key(
+    private_key: &str,
+    password: &str,
+    name: &str,
+    password_hint: &str,
+) -> Result<String, JsValue> {
+    wallet::import_private_key(private_key, password, name, password_hint).map_err(to_js_error)
+}
+
+/// `derivations` is a json array of `{chainType, path, network, segWit, curve}`, returns
+/// `{keystore, accounts}` where `keystore` contains the new accounts
+#[wasm_bindgen(js_name = deriveAccounts)]
+pub fn derive_accounts(
+    keystore: &str,
+    password: &str,
+    derivations: &str,
+) -> Result<String, JsValue> {
+    wallet::derive_accounts(keystore, password, derivations).map_err(to_js_error)
+}
+
+/// `input` is the json form of the tx input in `tcx-proto`, e.g. `{"rawData": "..."}` of TRON
+#[wasm_bindgen(js_name = signTx)]
+pub fn sign_tx(
+    keystore: &str,
+    password: &str,
+    chain_type: &str,
+    address: &str,
+    input: &str,
+) -> Result<String, JsValue> {
+    wallet::sign_tx(keystore, password, chain_type, address, input).map_err(to_js_error)
+}
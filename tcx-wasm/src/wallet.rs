@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use tcx_chain::{Account, HdKeystore, Keystore, KeystoreGuard, Metadata, PrivateKeystore, Source};
+use tcx_constants::coin_info::coin_info_from_param;
+use tcx_dispatch::{TxCodec, TxMessage};
+use tcx_primitive::{generate_mnemonic, private_key_without_version};
+
+pub type Result<T> = std::result::Result<T, failure::Error>;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Derivation {
+    pub chain_type: String,
+    pub path: String,
+    #[serde(default)]
+    pub network: String,
+    #[serde(default)]
+    pub seg_wit: String,
+    #[serde(default)]
+    pub curve: String,
+}
+
+/// The keystore is returned with the derived accounts because the caller owns the storage
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeriveResult {
+    pub keystore: Value,
+    pub accounts: Vec<Account>,
+}
+
+fn new_metadata(name: &str, password_hint: &str, source: Source) -> Metadata {
+    Metadata {
+        name: name.to_owned(),
+        password_hint: password_hint.to_owned(),
+        source,
+        ..Metadata::default()
+    }
+}
+
+pub fn create_keystore(password: &str, name: &str, password_hint: &str) -> Result<String> {
+    let meta = new_metadata(name, password_hint, Source::Mnemonic);
+    let ks = HdKeystore::from_mnemonic(&generate_mnemonic(), password, meta)?;
+    Ok(Keystore::Hd(ks).to_json())
+}
+
+pub fn import_mnemonic(
+    mnemonic: &str,
+    password: &str,
+    name: &str,
+    password_hint: &str,
+) -> Result<String> {
+    let meta = new_metadata(name, password_hint, Source::Mnemonic);
+    let ks = HdKeystore::from_mnemonic(mnemonic, password, meta)?;
+    Ok(Keystore::Hd(ks).to_json())
+}
+
+/// `private_key` is either hex or WIF
+pub fn import_private_key(
+    private_key: &str,
+    password: &str,
+    name: &str,
+    password_hint: &str,
+) -> Result<String> {
+    let key_data = match hex::decode(private_key) {
+        Ok(bytes) => bytes,
+        Err(_) => private_key_without_version(private_key)?,
+    };
+    let meta = new_metadata(name, password_hint, Source::Private);
+    let ks = PrivateKeystore::from_private_key(&hex::encode(key_data), password, meta);
+    Ok(Keystore::PrivateKey(ks).to_json())
+}
+
+pub fn derive_accounts(keystore_json: &str, password: &str, derivations: &str) -> Result<String> {
+    let derivations: Vec<Derivation> = serde_json::from_str(derivations)?;
+    let mut keystore = Keystore::from_json(keystore_json)?;
+
+    let mut accounts = vec![];
+    {
+        let mut guard = KeystoreGuard::unlock_by_password(&mut keystore, password)?;
+        for derivation in derivations.iter() {
+            accounts.push(derive_account(guard.keystore_mut(), derivation)?);
+        }
+    }
+
+    let ret = DeriveResult {
+        keystore: serde_json::from_str(&keystore.to_json())?,
+        accounts,
+    };
+    Ok(serde_json::to_string(&ret)?)
+}
+
+fn derive_account(keystore: &mut Keystore, derivation: &Derivation) -> Result<Account> {
+    let mut coin_info = coin_info_from_param(
+        &derivation.chain_type,
+        &derivation.network,
+        &derivation.seg_wit,
+        &derivation.curve,
+    )?;
+    coin_info.derivation_path = derivation.path.to_owned();
+
+    tcx_dispatch::derive_account(keystore, &derivation.chain_type, &coin_info)
+}
+
+/// `input` is the json form of the chain's tx input message, the json form of the signed output
+/// is returned
+pub fn sign_tx(
+    keystore_json: &str,
+    password: &str,
+    chain_type: &str,
+    address: &str,
+    input: &str,
+) -> Result<String> {
+    let mut keystore = Keystore::from_json(keystore_json)?;
+    let mut guard = KeystoreGuard::unlock_by_password(&mut keystore, password)?;
+    let keystore = guard.keystore_mut();
+
+    tcx_dispatch::sign_tx(keystore, chain_type, address, &JsonCodec { input })
+}
+
+/// Reads the json tx input and writes the signed tx as json
+struct JsonCodec<'a> {
+    input: &'a str,
+}
+
+impl<'a> TxCodec for JsonCodec<'a> {
+    type Output = String;
+
+    fn decode<I: TxMessage>(&self) -> Result<I> {
+        Ok(serde_json::from_str(self.input)?)
+    }
+
+    fn encode<O: TxMessage>(&self, output: O) -> Result<String> {
+        Ok(serde_json::to_string(&output)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tcx_constants::{TEST_MNEMONIC, TEST_PASSWORD};
+
+    #[test]
+    fn test_import_derive_and_sign() {
+        let keystore = import_mnemonic(TEST_MNEMONIC, TEST_PASSWORD, "test-wallet", "").unwrap();
+
+        let derivations = r#"[{"chainType": "TRON", "path": "m/44'/195'/0'/0/0"}]"#;
+        let ret = derive_accounts(&keystore, TEST_PASSWORD, derivations).unwrap();
+        let ret: Value = serde_json::from_str(&ret).unwrap();
+        assert_eq!(
+            ret["accounts"][0]["address"],
+            "TLZnqkrSNLUWNrZMug8u9b6pJ3XcTGbzDV"
+        );
+        assert_eq!(
+            ret["keystore"]["activeAccounts"][0]["address"],
+            "TLZnqkrSNLUWNrZMug8u9b6pJ3XcTGbzDV"
+        );
+
+        let keystore = ret["keystore"].to_string();
+        let input = r#"{"rawData": "0a0202a22208e216e254e43ee10840c8cbe4e3df2d5a67080112630a2d747970652e676f6f676c65617069732e636f6d2f70726f746f636f6c2e5472616e73666572436f6e747261637412320a15415c68cc82c87446f602f019e5fd797437f5b79cc212154156a6076cd1537fa317c2606e4edfa4acd3e8e92e18a08d06709084e1e3df2d"}"#;
+        let output = sign_tx(
+            &keystore,
+            TEST_PASSWORD,
+            "TRON",
+            "TLZnqkrSNLUWNrZMug8u9b6pJ3XcTGbzDV",
+            input,
+        )
+        .unwrap();
+        let output: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(output["signatures"][0], "bbf5ce0549490613a26c3ac4fc8574e748eabda05662b2e49cea818216b9da18691e78cd6379000e9c8a35c13dfbf620f269be90a078b58799b56dc20da3bdf200");
+
+        let ret = sign_tx(
+            &keystore,
+            "WRONG PASSWORD",
+            "TRON",
+            "TLZnqkrSNLUWNrZMug8u9b6pJ3XcTGbzDV",
+            input,
+        );
+        assert_eq!(format!("{}", ret.err().unwrap()), "password_incorrect");
+    }
+
+    #[test]
+    fn test_derive_dogecoin() {
+        let keystore = import_mnemonic(TEST_MNEMONIC, TEST_PASSWORD, "test-wallet", "").unwrap();
+
+        let derivations = r#"[{"chainType": "DOGECOIN", "path": "m/44'/3'/0'/0/0", "network": "MAINNET", "segWit": "NONE"}]"#;
+        let ret = derive_accounts(&keystore, TEST_PASSWORD, derivations).unwrap();
+        let ret: Value = serde_json::from_str(&ret).unwrap();
+        assert_eq!(
+            ret["accounts"][0]["address"],
+            "DQ4tVEqdPWHc1aVBm4Sfwft8XyNRPMEchR"
+        );
+    }
+
+    #[test]
+    fn test_import_private_key() {
+        let keystore = import_private_key(
+            "L2hfzPyVC1jWH7n2QLTe7tVTb6btg9smp5UVzhEBxLYaSFF7sCZB",
+            TEST_PASSWORD,
+            "test-pk",
+            "",
+        )
+        .unwrap();
+        let ks = Keystore::from_json(&keystore).unwrap();
+        assert!(!ks.determinable());
+    }
+}
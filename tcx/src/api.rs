@@ -3,19 +3,26 @@
 /// the real action and param field is the real param of that method.
 /// When an error occurred, the `call_tcx_api` will return a `Response` which isSuccess field be false and error field is the reason
 /// which cause the error.
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TcxAction {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub method: std::string::String,
     #[prost(message, optional, tag = "2")]
+    #[serde(default)]
+    #[serde(skip)]
     pub param: ::std::option::Option<::prost_types::Any>,
 }
 /// A common response when error occurred.
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Response {
     #[prost(bool, tag = "1")]
+    #[serde(default)]
     pub is_success: bool,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub error: std::string::String,
 }
 /// Initialization
@@ -23,34 +30,198 @@ pub struct Response {
 /// FUNCTION: init_token_core_x(InitTokenCoreXParam)
 ///
 /// initialize tcx by passing keystore folder and xpub encryption params
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InitTokenCoreXParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub file_dir: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub xpub_common_key: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub xpub_common_iv: std::string::String,
     #[prost(bool, tag = "4")]
+    #[serde(default)]
     pub is_debug: bool,
+    /// the default policy is used when absent
+    #[prost(message, optional, tag = "5")]
+    #[serde(default)]
+    pub security_policy: ::std::option::Option<SecurityPolicy>,
+    /// registered next to the built in coins, a bitcoin fork needs both its coin and networks.
+    /// The registry is process wide and can't be removed from, later calls and every other
+    /// instance see these coins. Either all of them are registered or none is
+    #[prost(message, repeated, tag = "6")]
+    #[serde(default)]
+    pub coins: ::std::vec::Vec<CoinConfig>,
+    #[prost(message, repeated, tag = "7")]
+    #[serde(default)]
+    pub btc_fork_networks: ::std::vec::Vec<BtcForkNetworkConfig>,
+}
+/// The rules every create and import checks the new keystore against, the password rules only apply
+/// to passwords the user picks and not to the one of an imported v3 or substrate keystore.
+/// A field left 0 or empty takes its default, minPasswordLength 8, minPbkdf2Rounds and minScryptN 262144.
+/// minPasswordEntropy is in bits and 0 disables the check, an empty allowedKdfs allows pbkdf2 and scrypt,
+/// allowDebugWeakKdf lets isDebug lower the kdf rounds below minPbkdf2Rounds to speed up tests.
+/// The errors are password_too_short, password_too_weak, kdf_not_allowed and kdf_params_too_weak.
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityPolicy {
+    #[prost(uint32, tag = "1")]
+    #[serde(default)]
+    pub min_password_length: u32,
+    #[prost(double, tag = "2")]
+    #[serde(default)]
+    pub min_password_entropy: f64,
+    #[prost(string, repeated, tag = "3")]
+    #[serde(default)]
+    pub allowed_kdfs: ::std::vec::Vec<std::string::String>,
+    #[prost(uint32, tag = "4")]
+    #[serde(default)]
+    pub min_pbkdf2_rounds: u32,
+    #[prost(uint32, tag = "5")]
+    #[serde(default)]
+    pub min_scrypt_n: u32,
+    #[prost(bool, tag = "6")]
+    #[serde(default)]
+    pub allow_debug_weak_kdf: bool,
+}
+/// A coin derived by keystore_common_derive, path is checked against the syntax of the curve.
+/// The errors are chain_type_invalid, invalid_curve_type, the path errors and coin_info_conflict
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinConfig {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub chain_type: std::string::String,
+    #[prost(string, tag = "2")]
+    #[serde(default)]
+    pub path: std::string::String,
+    /// SECP256k1, ED25519, SubSr25519, NIST256p1 or BLS
+    #[prost(string, tag = "3")]
+    #[serde(default)]
+    pub curve: std::string::String,
+    #[prost(string, tag = "4")]
+    #[serde(default)]
+    pub network: std::string::String,
+    #[prost(string, tag = "5")]
+    #[serde(default)]
+    pub seg_wit: std::string::String,
+}
+/// The address and extended key versions of a bitcoin fork, segWit is NONE, P2WPKH or SEGWIT.
+/// The errors are network_invalid, seg_wit_invalid, btc_fork_prefix_invalid, btc_fork_hrp_invalid,
+/// btc_fork_hrp_conflict and btc_fork_network_conflict
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BtcForkNetworkConfig {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub chain_type: std::string::String,
+    #[prost(string, tag = "2")]
+    #[serde(default)]
+    pub network: std::string::String,
+    #[prost(string, tag = "3")]
+    #[serde(default)]
+    pub seg_wit: std::string::String,
+    /// the bech32 prefix of SEGWIT addresses
+    #[prost(string, tag = "4")]
+    #[serde(default)]
+    pub hrp: std::string::String,
+    #[prost(uint32, tag = "5")]
+    #[serde(default)]
+    pub p2pkh_prefix: u32,
+    #[prost(uint32, tag = "6")]
+    #[serde(default)]
+    pub p2sh_prefix: u32,
+    #[prost(uint32, tag = "7")]
+    #[serde(default)]
+    pub private_prefix: u32,
+    /// 4 bytes hex encoded
+    #[prost(string, tag = "8")]
+    #[serde(default)]
+    pub xpub_prefix: std::string::String,
+    #[prost(string, tag = "9")]
+    #[serde(default)]
+    pub xprv_prefix: std::string::String,
+    /// the smallest output in the smallest unit of the coin, 0 takes the bitcoin dust 546
+    #[prost(uint64, tag = "10")]
+    #[serde(default)]
+    #[serde(with = "tcx_constants::json_int64")]
+    pub dust_threshold: u64,
+    /// the largest fee signed without allowAbsurdFee in the smallest unit, 0 takes 10000000
+    #[prost(uint64, tag = "11")]
+    #[serde(default)]
+    #[serde(with = "tcx_constants::json_int64")]
+    pub max_fee: u64,
+}
+/// FUNCTION: supported_coins(): SupportedCoinsResult
+///
+/// the coins and bitcoin fork networks tcx supports, the built in ones first
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportedCoinsResult {
+    #[prost(message, repeated, tag = "1")]
+    #[serde(default)]
+    pub coins: ::std::vec::Vec<CoinConfig>,
+    #[prost(message, repeated, tag = "2")]
+    #[serde(default)]
+    pub btc_fork_networks: ::std::vec::Vec<BtcForkNetworkConfig>,
+}
+/// FUNCTION: coin_info_query(CoinInfoQueryParam): CoinInfoQueryResult
+///
+/// every coin matching the query, an empty field matches any value. resolved is the coin the
+/// other apis pick for the same fields, it's unset when they fail with coin_info_ambiguous
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinInfoQueryParam {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub chain_type: std::string::String,
+    #[prost(string, tag = "2")]
+    #[serde(default)]
+    pub network: std::string::String,
+    #[prost(string, tag = "3")]
+    #[serde(default)]
+    pub seg_wit: std::string::String,
+    #[prost(string, tag = "4")]
+    #[serde(default)]
+    pub curve: std::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinInfoQueryResult {
+    #[prost(message, repeated, tag = "1")]
+    #[serde(default)]
+    pub coins: ::std::vec::Vec<CoinConfig>,
+    #[prost(message, optional, tag = "2")]
+    #[serde(default)]
+    pub resolved: ::std::option::Option<CoinConfig>,
 }
 ///
 ///// FUNCTION: export_private_key(ExportPrivateKeyParam): KeystoreCommonExportResult
 /////
 ///// export the private key from a private key keystore or a hd keystore
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ExportPrivateKeyParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub password: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub chain_type: std::string::String,
     #[prost(string, tag = "4")]
+    #[serde(default)]
     pub network: std::string::String,
     #[prost(string, tag = "5")]
+    #[serde(default)]
     pub main_address: std::string::String,
     #[prost(string, tag = "6")]
+    #[serde(default)]
     pub path: std::string::String,
 }
 ///
@@ -59,11 +230,14 @@ pub struct ExportPrivateKeyParam {
 ///// FUNCTION: keystore_common_verify(WalletKeyParam) -> Response
 /////
 ///// verify the password of the keystore
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct WalletKeyParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub password: std::string::String,
 }
 /// Hd Store
@@ -71,101 +245,165 @@ pub struct WalletKeyParam {
 /// FUNCTION: hd_store_create(HdStoreCreateParam): WalletResult
 ///
 /// create a new hd keystore
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct HdStoreCreateParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub password: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub password_hint: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub name: std::string::String,
+    /// the BIP-39 passphrase, stored encrypted with the mnemonic
+    #[prost(string, tag = "4")]
+    #[serde(default)]
+    pub passphrase: std::string::String,
+    /// the language code of the new mnemonic, e.g. en, zh-hans, zh-hant, ja, ko or es, english when empty
+    #[prost(string, tag = "5")]
+    #[serde(default)]
+    pub language: std::string::String,
+    /// 12, 15, 18, 21 or 24, 12 when 0
+    #[prost(uint32, tag = "6")]
+    #[serde(default)]
+    pub word_count: u32,
+    /// hex encoded caller supplied entropy of 16 to 32 bytes, e.g. hashed dice rolls, generated when empty
+    #[prost(string, tag = "7")]
+    #[serde(default)]
+    pub entropy: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct WalletResult {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub name: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub source: std::string::String,
     #[prost(message, repeated, tag = "4")]
+    #[serde(default)]
     pub accounts: ::std::vec::Vec<AccountResponse>,
     #[prost(int64, tag = "5")]
+    #[serde(default)]
+    #[serde(with = "tcx_constants::json_int64")]
     pub created_at: i64,
 }
 /// FUNCTION: hd_store_import(HdStoreImportParam): WalletResult
 ///
 /// create a new hd keystore by mnemonic
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct HdStoreImportParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub mnemonic: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub password: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub source: std::string::String,
     #[prost(string, tag = "4")]
+    #[serde(default)]
     pub name: std::string::String,
     #[prost(string, tag = "5")]
+    #[serde(default)]
     pub password_hint: std::string::String,
     #[prost(bool, tag = "6")]
+    #[serde(default)]
     pub overwrite: bool,
+    /// the BIP-39 passphrase, stored encrypted with the mnemonic
+    #[prost(string, tag = "7")]
+    #[serde(default)]
+    pub passphrase: std::string::String,
+    /// the language code of the mnemonic, detected when empty
+    #[prost(string, tag = "8")]
+    #[serde(default)]
+    pub language: std::string::String,
 }
 /// FUNCTION: hd_store_derive(HdStoreDeriveParam): AccountsResponse
 ///
 /// derive new accounts from a hd keystore
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct KeystoreCommonDeriveParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub password: std::string::String,
     #[prost(message, repeated, tag = "3")]
+    #[serde(default)]
     pub derivations: ::std::vec::Vec<keystore_common_derive_param::Derivation>,
 }
 pub mod keystore_common_derive_param {
-    #[derive(Clone, PartialEq, ::prost::Message)]
+    #[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
     pub struct Derivation {
         #[prost(string, tag = "1")]
+        #[serde(default)]
         pub chain_type: std::string::String,
         #[prost(string, tag = "2")]
+        #[serde(default)]
         pub path: std::string::String,
         #[prost(string, tag = "3")]
+        #[serde(default)]
         pub network: std::string::String,
         #[prost(string, tag = "4")]
+        #[serde(default)]
         pub seg_wit: std::string::String,
         #[prost(string, tag = "5")]
+        #[serde(default)]
         pub chain_id: std::string::String,
         #[prost(string, tag = "6")]
+        #[serde(default)]
         pub curve: std::string::String,
     }
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AccountResponse {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub chain_type: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub address: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub path: std::string::String,
     #[prost(string, tag = "4")]
+    #[serde(default)]
     pub extended_xpub_key: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AccountsResponse {
     #[prost(message, repeated, tag = "1")]
+    #[serde(default)]
     pub accounts: ::std::vec::Vec<AccountResponse>,
 }
 /// FUNCTION: hd_store_export(KeystoreCommonExportResult): KeystoreCommonExistsResult
 ///
 /// export the mnemonic from a hd keystore
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct KeystoreCommonExportResult {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
     #[prost(enumeration = "KeyType", tag = "2")]
+    #[serde(default)]
     pub r#type: i32,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub value: std::string::String,
 }
 /// Private Key Store
@@ -173,33 +411,45 @@ pub struct KeystoreCommonExportResult {
 /// FUNCTION: private_key_store_import(PrivateKeyStoreImportParam): WalletResult
 ///
 /// create a new private key keystore by a private key
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PrivateKeyStoreImportParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub private_key: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub password: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub name: std::string::String,
     #[prost(string, tag = "4")]
+    #[serde(default)]
     pub password_hint: std::string::String,
     #[prost(bool, tag = "5")]
+    #[serde(default)]
     pub overwrite: bool,
     #[prost(string, tag = "6")]
+    #[serde(default)]
     pub encoding: std::string::String,
 }
 /// FUNCTION: private_key_store_export(PrivateKeyStoreExportParam): KeystoreCommonExportResult
 ///
 /// export the private key from a private key keystore
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PrivateKeyStoreExportParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub password: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub chain_type: std::string::String,
     #[prost(string, tag = "4")]
+    #[serde(default)]
     pub network: std::string::String,
 }
 /// Keystore Common
@@ -211,28 +461,41 @@ pub struct PrivateKeyStoreExportParam {
 /// FUNCTION: keystore_common_exists(KeystoreCommonExistsParam): KeystoreCommonExistsResult
 ///
 /// Check is there a keystore was generate by the special privateKey or mnemonic
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct KeystoreCommonExistsParam {
     #[prost(enumeration = "KeyType", tag = "1")]
+    #[serde(default)]
     pub r#type: i32,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub value: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub encoding: std::string::String,
+    /// the BIP-39 passphrase of a mnemonic value
+    #[prost(string, tag = "4")]
+    #[serde(default)]
+    pub passphrase: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct KeystoreCommonExistsResult {
     #[prost(bool, tag = "1")]
+    #[serde(default)]
     pub is_exists: bool,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub id: std::string::String,
 }
 /// FUNCTION: keystore_common_accounts(KeystoreCommonAccountsParam): AccountsResponse
 ///
 /// List all accounts from the keystore
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct KeystoreCommonAccountsParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
 }
 /// Sign Transaction
@@ -241,21 +504,29 @@ pub struct KeystoreCommonAccountsParam {
 ///
 /// Sign transaction. This api is used for sign any chain_type, you should build the right TxInput instance and
 /// put it in the `input` field
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SignParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
     #[prost(string, tag = "4")]
+    #[serde(default)]
     pub chain_type: std::string::String,
     #[prost(string, tag = "5")]
+    #[serde(default)]
     pub address: std::string::String,
     #[prost(message, optional, tag = "6")]
+    #[serde(default)]
+    #[serde(skip)]
     pub input: ::std::option::Option<::prost_types::Any>,
     #[prost(oneof = "sign_param::Key", tags = "2, 3")]
+    #[serde(default)]
     pub key: ::std::option::Option<sign_param::Key>,
 }
 pub mod sign_param {
-    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    #[derive(Clone, PartialEq, ::prost::Oneof, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
     pub enum Key {
         #[prost(string, tag = "2")]
         Password(std::string::String),
@@ -263,128 +534,533 @@ pub mod sign_param {
         DerivedKey(std::string::String),
     }
 }
+/// FUNCTION: batch_sign_tx(BatchSignParam): BatchSignResult
+///
+/// Sign multiple transactions with one keystore unlock. The keystore is unlocked once by the `key` field,
+/// then every item of `signParams` is signed by the signer of its `chainType`. The `id` and `key` of each item
+/// are ignored. The result contains one entry for every item in the same order.
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSignParam {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub id: std::string::String,
+    #[prost(message, repeated, tag = "4")]
+    #[serde(default)]
+    pub sign_params: ::std::vec::Vec<SignParam>,
+    #[prost(oneof = "batch_sign_param::Key", tags = "2, 3")]
+    #[serde(default)]
+    pub key: ::std::option::Option<batch_sign_param::Key>,
+}
+pub mod batch_sign_param {
+    #[derive(Clone, PartialEq, ::prost::Oneof, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub enum Key {
+        #[prost(string, tag = "2")]
+        Password(std::string::String),
+        #[prost(string, tag = "3")]
+        DerivedKey(std::string::String),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSignResult {
+    #[prost(message, repeated, tag = "1")]
+    #[serde(default)]
+    pub results: ::std::vec::Vec<batch_sign_result::SignResult>,
+}
+pub mod batch_sign_result {
+    #[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SignResult {
+        #[prost(bool, tag = "1")]
+        #[serde(default)]
+        pub is_success: bool,
+        #[prost(string, tag = "2")]
+        #[serde(default)]
+        pub error: std::string::String,
+        #[prost(message, optional, tag = "3")]
+        #[serde(default)]
+        #[serde(skip)]
+        pub output: ::std::option::Option<::prost_types::Any>,
+    }
+}
 /// Other
 // TODO: annotate following message usage
 
 /// btc-fork
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ExternalAddressParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub chain_type: std::string::String,
     #[prost(uint32, tag = "3")]
+    #[serde(default)]
     pub external_idx: u32,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ExternalAddressResult {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub address: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub derived_path: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub r#type: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ExternalAddressExtra {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub enc_xpub: std::string::String,
     #[prost(message, optional, tag = "2")]
+    #[serde(default)]
     pub external_address: ::std::option::Option<external_address_extra::ExternalAddress>,
 }
 pub mod external_address_extra {
-    #[derive(Clone, PartialEq, ::prost::Message)]
+    #[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
     pub struct ExternalAddress {
         #[prost(string, tag = "1")]
+        #[serde(default)]
         pub address: std::string::String,
         #[prost(string, tag = "2")]
+        #[serde(default)]
         pub derived_path: std::string::String,
         #[prost(string, tag = "3")]
+        #[serde(default)]
         pub r#type: std::string::String,
     }
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BtcForkDeriveExtraParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub network: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub seg_wit: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct HdStoreExtendedPublicKeyParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub password: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub chain_type: std::string::String,
     #[prost(string, tag = "4")]
+    #[serde(default)]
     pub address: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct HdStoreExtendedPublicKeyResponse {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub extended_public_key: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PublicKeyParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub chain_type: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub address: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PublicKeyResult {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub chain_type: std::string::String,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub address: std::string::String,
     #[prost(string, tag = "4")]
+    #[serde(default)]
+    pub public_key: std::string::String,
+}
+/// FUNCTION: verify_signature(VerifySignatureParam): Response
+///
+/// check a signature made by sign_tx or a counterparty, isSuccess is false when it doesn't match.
+/// Without a public key the signer of a recoverable secp256k1 signature is compared to the address
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifySignatureParam {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub chain_type: std::string::String,
+    #[prost(string, tag = "2")]
+    #[serde(default)]
+    pub network: std::string::String,
+    #[prost(string, tag = "3")]
+    #[serde(default)]
+    pub seg_wit: std::string::String,
+    #[prost(string, tag = "4")]
+    #[serde(default)]
+    pub curve: std::string::String,
+    #[prost(string, tag = "5")]
+    #[serde(default)]
+    pub address: std::string::String,
+    /// hex encoded
+    #[prost(string, tag = "6")]
+    #[serde(default)]
+    pub public_key: std::string::String,
+    /// hex encoded, the 32 bytes hash for secp256k1
+    #[prost(string, tag = "7")]
+    #[serde(default)]
+    pub message: std::string::String,
+    /// hex encoded
+    #[prost(string, tag = "8")]
+    #[serde(default)]
+    pub signature: std::string::String,
+}
+/// FUNCTION: backup_wallets(BackupWalletsParam): BackupWalletsResult
+///
+/// export every keystore of the wallet dir into a single bundle encrypted by the password,
+/// the keystores inside keep their own passwords
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupWalletsParam {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub password: std::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupWalletsResult {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub bundle: std::string::String,
+    #[prost(uint32, tag = "2")]
+    #[serde(default)]
+    pub count: u32,
+}
+/// FUNCTION: restore_wallets(RestoreWalletsParam): RestoreWalletsResult
+///
+/// import the keystores of a bundle made by backup_wallets. A keystore whose key is already in the wallet dir
+/// is skipped and returned in conflicts, unless overwrite is set which replaces the existing one under its id
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreWalletsParam {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub bundle: std::string::String,
+    #[prost(string, tag = "2")]
+    #[serde(default)]
+    pub password: std::string::String,
+    #[prost(bool, tag = "3")]
+    #[serde(default)]
+    pub overwrite: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreWalletsResult {
+    #[prost(message, repeated, tag = "1")]
+    #[serde(default)]
+    pub restored: ::std::vec::Vec<WalletResult>,
+    #[prost(message, repeated, tag = "2")]
+    #[serde(default)]
+    pub conflicts: ::std::vec::Vec<WalletResult>,
+}
+/// FUNCTION: v3_keystore_import(V3KeystoreImportParam): WalletResult
+///
+/// import a Web3 Secret Storage (V3) keystore of geth or MyEtherWallet as a private key wallet
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct V3KeystoreImportParam {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub keystore: std::string::String,
+    #[prost(string, tag = "2")]
+    #[serde(default)]
+    pub password: std::string::String,
+    #[prost(string, tag = "3")]
+    #[serde(default)]
+    pub name: std::string::String,
+    #[prost(string, tag = "4")]
+    #[serde(default)]
+    pub password_hint: std::string::String,
+    #[prost(bool, tag = "5")]
+    #[serde(default)]
+    pub overwrite: bool,
+}
+/// FUNCTION: v3_keystore_export(V3KeystoreExportParam): V3KeystoreExportResult
+///
+/// export a private key wallet as a V3 keystore encrypted by the wallet password, kdf is scrypt when empty or pbkdf2
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct V3KeystoreExportParam {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub id: std::string::String,
+    #[prost(string, tag = "2")]
+    #[serde(default)]
+    pub password: std::string::String,
+    #[prost(string, tag = "3")]
+    #[serde(default)]
+    pub kdf: std::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct V3KeystoreExportResult {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub keystore: std::string::String,
+}
+/// FUNCTION: slip39_shares_export(Slip39SharesExportParam): Slip39SharesExportResult
+///
+/// split the BIP-32 seed of a hd wallet into SLIP-39 shares, any groupThreshold of the groups recover it
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Slip39Group {
+    #[prost(uint32, tag = "1")]
+    #[serde(default)]
+    pub member_threshold: u32,
+    #[prost(uint32, tag = "2")]
+    #[serde(default)]
+    pub member_count: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Slip39SharesExportParam {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub id: std::string::String,
+    #[prost(string, tag = "2")]
+    #[serde(default)]
+    pub password: std::string::String,
+    #[prost(uint32, tag = "3")]
+    #[serde(default)]
+    pub group_threshold: u32,
+    #[prost(message, repeated, tag = "4")]
+    #[serde(default)]
+    pub groups: ::std::vec::Vec<Slip39Group>,
+    #[prost(string, tag = "5")]
+    #[serde(default)]
+    pub passphrase: std::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Slip39GroupShares {
+    #[prost(string, repeated, tag = "1")]
+    #[serde(default)]
+    pub shares: ::std::vec::Vec<std::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Slip39SharesExportResult {
+    #[prost(message, repeated, tag = "1")]
+    #[serde(default)]
+    pub groups: ::std::vec::Vec<Slip39GroupShares>,
+}
+/// FUNCTION: slip39_import(Slip39ImportParam): WalletResult
+///
+/// recover a hd wallet from SLIP-39 shares, the master secret is its BIP-32 seed. The wallet has no
+/// mnemonic to export and no sr25519 (polkadot, kusama) accounts
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Slip39ImportParam {
+    #[prost(string, repeated, tag = "1")]
+    #[serde(default)]
+    pub shares: ::std::vec::Vec<std::string::String>,
+    #[prost(string, tag = "2")]
+    #[serde(default)]
+    pub passphrase: std::string::String,
+    #[prost(string, tag = "3")]
+    #[serde(default)]
+    pub password: std::string::String,
+    #[prost(string, tag = "4")]
+    #[serde(default)]
+    pub name: std::string::String,
+    #[prost(string, tag = "5")]
+    #[serde(default)]
+    pub password_hint: std::string::String,
+    #[prost(bool, tag = "6")]
+    #[serde(default)]
+    pub overwrite: bool,
+}
+/// FUNCTION: mnemonic_words_verify(MnemonicWordsVerifyParam): Response
+///
+/// check the words at the given positions of a hd wallet's mnemonic without exporting it,
+/// isSuccess is false when any word is wrong
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MnemonicWordsVerifyParam {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub id: std::string::String,
+    #[prost(string, tag = "2")]
+    #[serde(default)]
+    pub password: std::string::String,
+    #[prost(message, repeated, tag = "3")]
+    #[serde(default)]
+    pub words: ::std::vec::Vec<MnemonicWord>,
+}
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MnemonicWord {
+    /// the zero based position in the mnemonic
+    #[prost(uint32, tag = "1")]
+    #[serde(default)]
+    pub index: u32,
+    #[prost(string, tag = "2")]
+    #[serde(default)]
+    pub word: std::string::String,
+}
+/// FUNCTION: mnemonic_candidates(MnemonicCandidatesParam): MnemonicCandidatesResult
+///
+/// the phrases satisfying the BIP-39 checksum for a mnemonic with one invalid word, a missing
+/// word marked by ? or a left out word
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MnemonicCandidatesParam {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub mnemonic: std::string::String,
+    /// the language code of the mnemonic, detected when empty
+    #[prost(string, tag = "2")]
+    #[serde(default)]
+    pub language: std::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MnemonicCandidatesResult {
+    #[prost(string, repeated, tag = "1")]
+    #[serde(default)]
+    pub candidates: ::std::vec::Vec<std::string::String>,
+}
+/// FUNCTION: sub_addresses_derive(SubAddressesDeriveParam): SubAddressesDeriveResult
+///
+/// the addresses below an account of a hd wallet derived from its extended public key, it needs
+/// no password. The hardened only ed25519 and BLS accounts can't derive them
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAddressesDeriveParam {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub id: std::string::String,
+    #[prost(string, tag = "2")]
+    #[serde(default)]
+    pub chain_type: std::string::String,
+    /// the address of the account the paths are relative to
+    #[prost(string, tag = "3")]
+    #[serde(default)]
+    pub address: std::string::String,
+    /// relative to the account's extended public key, as 0/1 or /1 for sr25519
+    #[prost(string, repeated, tag = "4")]
+    #[serde(default)]
+    pub relative_paths: ::std::vec::Vec<std::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAddressesDeriveResult {
+    #[prost(message, repeated, tag = "1")]
+    #[serde(default)]
+    pub addresses: ::std::vec::Vec<SubAddress>,
+}
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAddress {
+    #[prost(string, tag = "1")]
+    #[serde(default)]
+    pub path: std::string::String,
+    #[prost(string, tag = "2")]
+    #[serde(default)]
+    pub address: std::string::String,
+    /// hex encoded
+    #[prost(string, tag = "3")]
+    #[serde(default)]
     pub public_key: std::string::String,
 }
 /// only support two types
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::prost::Enumeration,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
 #[repr(i32)]
 pub enum KeyType {
     Mnemonic = 0,
     PrivateKey = 1,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct VerifyDerivedKeyParam {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub derived_key: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DerivedKeyResult {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
     #[prost(string, tag = "2")]
+    #[serde(default)]
     pub derived_key: std::string::String,
 }
 /// Only used in Android or iOS
 
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CacheDerivedKeyResult {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
     #[prost(bool, tag = "2")]
+    #[serde(default)]
     pub enable_derived_key: bool,
     #[prost(string, tag = "3")]
+    #[serde(default)]
     pub mode: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct WalletId {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub id: std::string::String,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BiometricModeResult {
     #[prost(string, tag = "1")]
+    #[serde(default)]
     pub mode: std::string::String,
 }
@@ -0,0 +1,114 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use tcx_chain::tcx_ensure;
+use tcx_chain::Keystore;
+use tcx_crypto::{Crypto, Key, Pbkdf2Params};
+
+use crate::error_handling::Result;
+
+pub const BUNDLE_VERSION: i64 = 1;
+
+/// The backup of a whole wallet dir, `crypto` encrypts the json of a `BundlePayload`
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletBundle {
+    pub version: i64,
+    pub created_at: i64,
+    pub crypto: Crypto<Pbkdf2Params>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BundlePayload {
+    keystores: Vec<Value>,
+}
+
+/// Encrypt the json of `keystores` into a bundle json
+pub fn encrypt_bundle(
+    keystores: &[&Keystore],
+    password: &str,
+    kdf_params: Pbkdf2Params,
+) -> Result<String> {
+    let payload = BundlePayload {
+        keystores: keystores
+            .iter()
+            .map(|keystore| serde_json::from_str(&keystore.to_json()))
+            .collect::<std::result::Result<Vec<Value>, _>>()?,
+    };
+    let payload_json = serde_json::to_vec(&payload)?;
+
+    let bundle = WalletBundle {
+        version: BUNDLE_VERSION,
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs() as i64,
+        crypto: Crypto::new_with_kdf_params(password, &payload_json, kdf_params),
+    };
+    Ok(serde_json::to_string(&bundle)?)
+}
+
+/// Decrypt a bundle json, every keystore is validated before any is returned so a broken
+/// bundle restores nothing
+pub fn decrypt_bundle(bundle: &str, password: &str) -> Result<Vec<Keystore>> {
+    let bundle: WalletBundle =
+        serde_json::from_str(bundle).map_err(|_| format_err!("bundle_invalid"))?;
+    tcx_ensure!(
+        bundle.version == BUNDLE_VERSION,
+        format_err!("unsupported_bundle_version")
+    );
+
+    let payload_json = bundle.crypto.decrypt(Key::Password(password.to_owned()))?;
+    let payload: BundlePayload =
+        serde_json::from_slice(&payload_json).map_err(|_| format_err!("bundle_invalid"))?;
+
+    payload
+        .keystores
+        .iter()
+        .map(|keystore| Keystore::from_json(&keystore.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decrypt_bundle, encrypt_bundle};
+    use tcx_chain::{Keystore, Metadata};
+    use tcx_constants::{TEST_MNEMONIC, TEST_PASSWORD};
+    use tcx_crypto::Pbkdf2Params;
+
+    #[test]
+    fn test_encrypt_decrypt_bundle() {
+        let keystores = vec![
+            Keystore::from_mnemonic(TEST_MNEMONIC, TEST_PASSWORD, Metadata::default()).unwrap(),
+            Keystore::from_private_key(
+                "a392604efc2fad9c0b3da43b5f698a2e3f270f170d859912be0d54742275c5f6",
+                TEST_PASSWORD,
+                Metadata::default(),
+            ),
+        ];
+        let keystore_refs: Vec<&Keystore> = keystores.iter().collect();
+        let bundle = encrypt_bundle(
+            &keystore_refs,
+            "bundle password",
+            Pbkdf2Params::with_rounds(1024),
+        )
+        .unwrap();
+
+        let restored = decrypt_bundle(&bundle, "bundle password").unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored[0].id(), keystores[0].id());
+        assert_eq!(restored[1].key_hash(), keystores[1].key_hash());
+
+        let err = decrypt_bundle(&bundle, "wrong password").err().unwrap();
+        assert_eq!(format!("{}", err), "password_incorrect");
+
+        let err = decrypt_bundle("{}", "bundle password").err().unwrap();
+        assert_eq!(format!("{}", err), "bundle_invalid");
+
+        let v2 = bundle.replace("\"version\":1", "\"version\":2");
+        let err = decrypt_bundle(&v2, "bundle password").err().unwrap();
+        assert_eq!(format!("{}", err), "unsupported_bundle_version");
+    }
+}
@@ -0,0 +1,427 @@
+use std::str::FromStr;
+
+use tcx_chain::tcx_ensure;
+use tcx_constants::{
+    add_coins, btc_fork_networks, coin_info_from_param, coin_infos, coin_infos_from_param,
+    BtcForkNetwork, CoinInfo, CurveType, DEFAULT_DUST_THRESHOLD, DEFAULT_MAX_FEE,
+};
+use tcx_primitive::{get_account_path, DerivePath};
+
+use crate::api::{
+    BtcForkNetworkConfig, CoinConfig, CoinInfoQueryParam, CoinInfoQueryResult, SupportedCoinsResult,
+};
+use crate::error_handling::Result;
+
+/// Registers the coins and bitcoin fork networks passed to `init_token_core_x` next to the built
+/// in ones. The registry is process wide, and either every entry is added or none is
+pub fn register_coins(coins: &[CoinConfig], networks: &[BtcForkNetworkConfig]) -> Result<()> {
+    let coin_infos = coins
+        .iter()
+        .map(coin_info_from_config)
+        .collect::<Result<Vec<CoinInfo>>>()?;
+    let networks = networks
+        .iter()
+        .map(network_from_config)
+        .collect::<Result<Vec<BtcForkNetwork>>>()?;
+
+    add_coins(coin_infos, networks)
+}
+
+pub fn supported_coins() -> SupportedCoinsResult {
+    SupportedCoinsResult {
+        coins: coin_infos()
+            .into_iter()
+            .map(coin_config_from_info)
+            .collect(),
+        btc_fork_networks: btc_fork_networks()
+            .into_iter()
+            .map(|network| BtcForkNetworkConfig {
+                chain_type: network.coin,
+                network: network.network,
+                seg_wit: network.seg_wit,
+                hrp: network.hrp,
+                p2pkh_prefix: u32::from(network.p2pkh_prefix),
+                p2sh_prefix: u32::from(network.p2sh_prefix),
+                private_prefix: u32::from(network.private_prefix),
+                xpub_prefix: hex::encode(network.xpub_prefix),
+                xprv_prefix: hex::encode(network.xprv_prefix),
+                dust_threshold: network.dust_threshold,
+                max_fee: network.max_fee,
+            })
+            .collect(),
+    }
+}
+
+pub fn query_coins(param: &CoinInfoQueryParam) -> CoinInfoQueryResult {
+    let coins = coin_infos_from_param(
+        &param.chain_type,
+        &param.network,
+        &param.seg_wit,
+        &param.curve,
+    );
+    let resolved = coin_info_from_param(
+        &param.chain_type,
+        &param.network,
+        &param.seg_wit,
+        &param.curve,
+    );
+    CoinInfoQueryResult {
+        coins: coins.into_iter().map(coin_config_from_info).collect(),
+        resolved: resolved.ok().map(coin_config_from_info),
+    }
+}
+
+fn coin_config_from_info(coin_info: CoinInfo) -> CoinConfig {
+    CoinConfig {
+        chain_type: coin_info.coin,
+        path: coin_info.derivation_path,
+        curve: coin_info.curve.as_str().to_string(),
+        network: coin_info.network,
+        seg_wit: coin_info.seg_wit,
+    }
+}
+
+fn chain_type_from_config(chain_type: &str) -> Result<String> {
+    let chain_type = chain_type.to_uppercase();
+    tcx_ensure!(
+        !chain_type.is_empty()
+            && chain_type
+                .bytes()
+                .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_'),
+        format_err!("chain_type_invalid")
+    );
+    Ok(chain_type)
+}
+
+fn coin_info_from_config(config: &CoinConfig) -> Result<CoinInfo> {
+    let curve = CurveType::from_str(&config.curve)?;
+    DerivePath::parse(curve, &config.path)?;
+    // the extended public key of a secp256k1 account is stored at its account path
+    if curve == CurveType::SECP256k1 {
+        get_account_path(&config.path)?;
+    }
+
+    Ok(CoinInfo {
+        coin: chain_type_from_config(&config.chain_type)?,
+        derivation_path: config.path.to_string(),
+        curve,
+        network: config.network.to_uppercase(),
+        seg_wit: config.seg_wit.to_uppercase(),
+    })
+}
+
+fn network_from_config(config: &BtcForkNetworkConfig) -> Result<BtcForkNetwork> {
+    let chain_type = chain_type_from_config(&config.chain_type)?;
+    let network = config.network.to_uppercase();
+    tcx_ensure!(
+        network == "MAINNET" || network == "TESTNET",
+        format_err!("network_invalid")
+    );
+    let seg_wit = config.seg_wit.to_uppercase();
+    tcx_ensure!(
+        ["NONE", "P2WPKH", "SEGWIT"].contains(&seg_wit.as_str()),
+        format_err!("seg_wit_invalid")
+    );
+
+    tcx_ensure!(
+        config.p2pkh_prefix <= 0xff
+            && config.p2sh_prefix <= 0xff
+            && config.private_prefix <= 0xff
+            && config.p2pkh_prefix != config.p2sh_prefix,
+        format_err!("btc_fork_prefix_invalid")
+    );
+    let xpub_prefix = version_from_hex(&config.xpub_prefix)?;
+    let xprv_prefix = version_from_hex(&config.xprv_prefix)?;
+    tcx_ensure!(
+        xpub_prefix != xprv_prefix,
+        format_err!("btc_fork_prefix_invalid")
+    );
+
+    // bech32 takes 1 to 83 printable characters, lower case to keep the addresses canonical
+    let hrp = config.hrp.as_str();
+    tcx_ensure!(
+        hrp.len() <= 83
+            && hrp
+                .bytes()
+                .all(|b| (33..=126).contains(&b) && !b.is_ascii_uppercase())
+            && (seg_wit != "SEGWIT" || !hrp.is_empty()),
+        format_err!("btc_fork_hrp_invalid")
+    );
+
+    Ok(BtcForkNetwork {
+        coin: chain_type,
+        network,
+        seg_wit,
+        hrp: hrp.to_string(),
+        p2pkh_prefix: config.p2pkh_prefix as u8,
+        p2sh_prefix: config.p2sh_prefix as u8,
+        private_prefix: config.private_prefix as u8,
+        xpub_prefix,
+        xprv_prefix,
+        dust_threshold: if config.dust_threshold == 0 {
+            DEFAULT_DUST_THRESHOLD
+        } else {
+            config.dust_threshold
+        },
+        max_fee: if config.max_fee == 0 {
+            DEFAULT_MAX_FEE
+        } else {
+            config.max_fee
+        },
+    })
+}
+
+fn version_from_hex(version: &str) -> Result<[u8; 4]> {
+    let bytes = hex::decode(version).map_err(|_| format_err!("btc_fork_prefix_invalid"))?;
+    tcx_ensure!(bytes.len() == 4, format_err!("btc_fork_prefix_invalid"));
+    let mut ret = [0u8; 4];
+    ret.copy_from_slice(&bytes);
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{query_coins, register_coins, supported_coins};
+    use crate::api::{BtcForkNetworkConfig, CoinConfig, CoinInfoQueryParam};
+    use tcx_constants::btc_fork_network::network_from_param;
+    use tcx_constants::coin_info_from_param;
+
+    fn coin_config() -> CoinConfig {
+        CoinConfig {
+            chain_type: "REGTESTCOIN".to_string(),
+            path: "m/44'/1'/0'/0/0".to_string(),
+            curve: "SECP256k1".to_string(),
+            network: "TESTNET".to_string(),
+            seg_wit: "NONE".to_string(),
+        }
+    }
+
+    fn network_config() -> BtcForkNetworkConfig {
+        BtcForkNetworkConfig {
+            chain_type: "REGTESTCOIN".to_string(),
+            network: "TESTNET".to_string(),
+            seg_wit: "NONE".to_string(),
+            hrp: "rtc".to_string(),
+            p2pkh_prefix: 0x3c,
+            p2sh_prefix: 0x3d,
+            private_prefix: 0xbc,
+            xpub_prefix: "043587cf".to_string(),
+            xprv_prefix: "04358394".to_string(),
+            dust_threshold: 5460,
+            max_fee: 50_000_000,
+        }
+    }
+
+    #[test]
+    fn test_register_coins() {
+        register_coins(&[coin_config()], &[network_config()]).unwrap();
+        // registering the same entries again is fine
+        register_coins(&[coin_config()], &[network_config()]).unwrap();
+
+        let coin_info = coin_info_from_param("REGTESTCOIN", "TESTNET", "NONE", "").unwrap();
+        assert_eq!(coin_info.derivation_path, "m/44'/1'/0'/0/0");
+        let network = network_from_param("REGTESTCOIN", "TESTNET", "NONE").unwrap();
+        assert_eq!(network.p2pkh_prefix, 0x3c);
+        assert_eq!(network.dust_threshold, 5460);
+        assert_eq!(network.max_fee, 50_000_000);
+
+        let mut config = network_config();
+        config.chain_type = "DUSTLESSCOIN".to_string();
+        config.hrp = "".to_string();
+        config.p2pkh_prefix = 0x44;
+        config.p2sh_prefix = 0x45;
+        config.dust_threshold = 0;
+        config.max_fee = 0;
+        register_coins(&[], &[config]).unwrap();
+        let network = network_from_param("DUSTLESSCOIN", "TESTNET", "NONE").unwrap();
+        assert_eq!(network.dust_threshold, 546);
+        assert_eq!(network.max_fee, 10_000_000);
+
+        let supported = supported_coins();
+        assert!(supported
+            .coins
+            .iter()
+            .any(|coin| coin.chain_type == "BITCOIN"));
+        assert!(supported.coins.contains(&coin_config()));
+        assert!(supported.btc_fork_networks.contains(&network_config()));
+
+        let mut conflict = coin_config();
+        conflict.path = "m/44'/1'/1'/0/0".to_string();
+        let err = register_coins(&[conflict], &[]).err().unwrap();
+        assert_eq!(format!("{}", err), "coin_info_conflict");
+        let mut conflict = network_config();
+        conflict.p2pkh_prefix = 0x3e;
+        let err = register_coins(&[], &[conflict]).err().unwrap();
+        assert_eq!(format!("{}", err), "btc_fork_network_conflict");
+        let mut conflict = network_config();
+        conflict.chain_type = "OTHERCOIN".to_string();
+        conflict.hrp = "ltc".to_string();
+        let err = register_coins(&[], &[conflict]).err().unwrap();
+        assert_eq!(format!("{}", err), "btc_fork_hrp_conflict");
+        let mut conflict = network_config();
+        conflict.chain_type = "OTHERCOIN".to_string();
+        conflict.hrp = "".to_string();
+        for (p2pkh_prefix, p2sh_prefix) in vec![(0x6f, 0x46), (0x46, 0xc4), (0x46, 0x3c)] {
+            conflict.p2pkh_prefix = p2pkh_prefix;
+            conflict.p2sh_prefix = p2sh_prefix;
+            let err = register_coins(&[], &[conflict.clone()]).err().unwrap();
+            assert_eq!(format!("{}", err), "btc_fork_prefix_conflict");
+        }
+        // nor does a mainnet share the prefixes of its testnet
+        let mut mainnet = network_config();
+        mainnet.network = "MAINNET".to_string();
+        let err = register_coins(&[], &[mainnet]).err().unwrap();
+        assert_eq!(format!("{}", err), "btc_fork_prefix_conflict");
+    }
+
+    #[test]
+    fn test_register_coins_is_all_or_nothing() {
+        let mut coin = coin_config();
+        coin.chain_type = "HALFCOIN".to_string();
+        let mut network = network_config();
+        network.chain_type = "HALFCOIN".to_string();
+        network.hrp = "ltc".to_string();
+        let err = register_coins(&[coin.clone()], &[network.clone()])
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "btc_fork_hrp_conflict");
+        assert!(coin_info_from_param("HALFCOIN", "TESTNET", "NONE", "").is_err());
+
+        let mut other_path = coin.clone();
+        other_path.path = "m/44'/1'/1'/0/0".to_string();
+        let err = register_coins(&[coin.clone(), other_path], &[])
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "coin_info_conflict");
+        assert!(coin_info_from_param("HALFCOIN", "TESTNET", "NONE", "").is_err());
+
+        let mut other_prefix = network_config();
+        other_prefix.chain_type = "HALFCOIN".to_string();
+        other_prefix.hrp = "half".to_string();
+        other_prefix.p2pkh_prefix = 0x40;
+        other_prefix.p2sh_prefix = 0x41;
+        network = other_prefix.clone();
+        other_prefix.p2pkh_prefix = 0x42;
+        let err = register_coins(&[], &[network.clone(), other_prefix])
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "btc_fork_network_conflict");
+        assert!(network_from_param("HALFCOIN", "TESTNET", "NONE").is_none());
+
+        register_coins(&[coin.clone(), coin], &[network.clone(), network]).unwrap();
+        assert!(coin_info_from_param("HALFCOIN", "TESTNET", "NONE", "").is_ok());
+        assert!(network_from_param("HALFCOIN", "TESTNET", "NONE").is_some());
+    }
+
+    #[test]
+    fn test_register_invalid_coins() {
+        let invalid_coins = vec![
+            ("chain_type", "BAD COIN", "chain_type_invalid"),
+            ("curve", "secp256r1", "invalid_curve_type"),
+            ("path", "m/44'/x/0'", "invalid_derivation_path_segment# x"),
+            ("path", "m/44'/1'", "m/44'/1' path is too short"),
+        ];
+        for (field, value, expected) in invalid_coins {
+            let mut config = coin_config();
+            config.chain_type = "INVALIDCOIN".to_string();
+            match field {
+                "chain_type" => config.chain_type = value.to_string(),
+                "curve" => config.curve = value.to_string(),
+                _ => config.path = value.to_string(),
+            }
+            let err = register_coins(&[config], &[]).err().unwrap();
+            assert_eq!(format!("{}", err), expected, "{}", value);
+        }
+
+        let invalid_networks = vec![
+            ("network", "REGTEST", "network_invalid"),
+            ("seg_wit", "TAPROOT", "seg_wit_invalid"),
+            ("p2sh_prefix", "60", "btc_fork_prefix_invalid"),
+            ("p2pkh_prefix", "256", "btc_fork_prefix_invalid"),
+            ("xpub_prefix", "0435", "btc_fork_prefix_invalid"),
+            ("xprv_prefix", "043587cf", "btc_fork_prefix_invalid"),
+            ("hrp", "RTC", "btc_fork_hrp_invalid"),
+        ];
+        for (field, value, expected) in invalid_networks {
+            let mut config = network_config();
+            config.chain_type = "INVALIDCOIN".to_string();
+            match field {
+                "network" => config.network = value.to_string(),
+                "seg_wit" => config.seg_wit = value.to_string(),
+                "p2sh_prefix" => config.p2sh_prefix = value.parse().unwrap(),
+                "p2pkh_prefix" => config.p2pkh_prefix = value.parse().unwrap(),
+                "xpub_prefix" => config.xpub_prefix = value.to_string(),
+                "xprv_prefix" => config.xprv_prefix = value.to_string(),
+                _ => config.hrp = value.to_string(),
+            }
+            let err = register_coins(&[], &[config]).err().unwrap();
+            assert_eq!(format!("{}", err), expected, "{}", value);
+        }
+
+        let mut config = network_config();
+        config.chain_type = "INVALIDCOIN".to_string();
+        config.seg_wit = "SEGWIT".to_string();
+        config.hrp = "".to_string();
+        let err = register_coins(&[], &[config]).err().unwrap();
+        assert_eq!(format!("{}", err), "btc_fork_hrp_invalid");
+
+        assert!(coin_info_from_param("INVALIDCOIN", "", "", "").is_err());
+    }
+
+    #[test]
+    fn test_query_coins() {
+        let query = |chain_type: &str, network: &str, seg_wit: &str, curve: &str| {
+            query_coins(&CoinInfoQueryParam {
+                chain_type: chain_type.to_string(),
+                network: network.to_string(),
+                seg_wit: seg_wit.to_string(),
+                curve: curve.to_string(),
+            })
+        };
+
+        let result = query("BITCOIN", "", "", "");
+        assert_eq!(result.coins.len(), 4);
+        let resolved = result.resolved.unwrap();
+        assert_eq!(resolved.network, "MAINNET");
+        assert_eq!(resolved.seg_wit, "NONE");
+        assert_eq!(resolved.path, "m/44'/0'/0'/0/0");
+
+        let result = query("BITCOIN", "TESTNET", "P2WPKH", "");
+        assert_eq!(result.coins.len(), 1);
+        assert_eq!(result.resolved.unwrap().path, "m/49'/1'/0'/0/0");
+
+        let result = query("FILECOIN", "TESTNET", "", "");
+        assert_eq!(result.coins.len(), 2);
+        assert_eq!(result.resolved.unwrap().curve, "SECP256k1");
+        let coin_info = coin_info_from_param("FILECOIN", "", "", "BLS").unwrap();
+        assert_eq!(coin_info.network, "MAINNET");
+        assert_eq!(coin_info.derivation_path, "m/2334/461/0/0");
+
+        let result = query("TRON", "", "", "");
+        assert_eq!(result.coins.len(), 1);
+        assert!(result.resolved.is_some());
+
+        let result = query("UNKNOWNCOIN", "", "", "");
+        assert!(result.coins.is_empty());
+        assert!(result.resolved.is_none());
+
+        // a registered coin has no defaults
+        let mut mainnet = coin_config();
+        mainnet.chain_type = "AMBIGUOUSCOIN".to_string();
+        mainnet.network = "MAINNET".to_string();
+        mainnet.path = "m/44'/0'/0'/0/0".to_string();
+        let mut testnet = mainnet.clone();
+        testnet.network = "TESTNET".to_string();
+        testnet.path = "m/44'/1'/0'/0/0".to_string();
+        register_coins(&[mainnet, testnet], &[]).unwrap();
+        let result = query("AMBIGUOUSCOIN", "", "", "");
+        assert_eq!(result.coins.len(), 2);
+        assert!(result.resolved.is_none());
+        let err = coin_info_from_param("AMBIGUOUSCOIN", "", "", "")
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "coin_info_ambiguous");
+        let coin_info = coin_info_from_param("AMBIGUOUSCOIN", "TESTNET", "", "").unwrap();
+        assert_eq!(coin_info.derivation_path, "m/44'/1'/0'/0/0");
+    }
+}
@@ -1,4 +1,4 @@
-use crate::filemanager::KEYSTORE_MAP;
+use crate::token_core::TokenCore;
 use core::result;
 use failure::{Backtrace, Error};
 use std::{cell::RefCell, mem, panic};
@@ -22,24 +22,18 @@ fn notify_err(err: Error) {
     });
 }
 
-fn lock_all_keystore() {
-    let mut map = KEYSTORE_MAP.write();
-    for ks in map.values_mut() {
-        ks.lock();
-    }
-}
-
 /// catch any error and format to string
 /// ref: <https://doc.rust-lang.org/edition-guide/rust-2018/error-handling-and-panics/controlling-panics-with-std-panic.html>
 #[cfg_attr(tarpaulin, skip)]
-pub unsafe fn landingpad<F: FnOnce() -> Result<T> + panic::UnwindSafe, T>(f: F) -> T {
-    match panic::catch_unwind(f) {
+pub unsafe fn landingpad<F: FnOnce() -> Result<T>, T>(token_core: &TokenCore, f: F) -> T {
+    // the keystores are locked whatever happens, so the instance stays usable after a panic
+    match panic::catch_unwind(panic::AssertUnwindSafe(f)) {
         Ok(rv) => {
-            lock_all_keystore();
+            token_core.lock_all();
             rv.map_err(notify_err).unwrap_or_else(|_| mem::zeroed())
         }
         Err(err) => {
-            lock_all_keystore();
+            token_core.lock_all();
             use std::any::Any;
             let err = &*err as &dyn Any;
             let msg = match err.downcast_ref::<&str>() {
@@ -0,0 +1,325 @@
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+use prost::Message;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use tcx_btc_fork::{BtcForkSignedTxOutput, BtcForkTxInput};
+use tcx_ckb::{CkbTxInput, CkbTxOutput};
+use tcx_constants::is_btc_fork;
+use tcx_filecoin::{SignedMessage, UnsignedMessage};
+use tcx_substrate::{SubstrateKeystoreParam, SubstrateRawTxIn, SubstrateTxOut};
+use tcx_tezos::transaction::{TezosRawTxIn, TezosTxOut};
+use tcx_tron::transaction::{TronMessageInput, TronMessageOutput, TronTxInput, TronTxOutput};
+
+use crate::api::{AccountsResponse, ExportSubstrateKeystoreResult};
+use crate::api::{
+    BackupWalletsParam, BackupWalletsResult, CoinInfoQueryParam, CoinInfoQueryResult,
+    MnemonicCandidatesParam, MnemonicCandidatesResult, MnemonicWordsVerifyParam,
+    RestoreWalletsParam, RestoreWalletsResult, Slip39ImportParam, Slip39SharesExportParam,
+    Slip39SharesExportResult, SubAddressesDeriveParam, SubAddressesDeriveResult,
+    V3KeystoreExportParam, V3KeystoreExportResult, V3KeystoreImportParam, VerifySignatureParam,
+};
+use crate::api::{
+    BatchSignParam, BatchSignResult, DerivedKeyResult, ExportPrivateKeyParam, HdStoreCreateParam,
+    HdStoreImportParam, InitTokenCoreXParam, KeystoreCommonAccountsParam,
+    KeystoreCommonDeriveParam, KeystoreCommonExistsParam, KeystoreCommonExistsResult,
+    KeystoreCommonExportResult, PrivateKeyStoreExportParam, PrivateKeyStoreImportParam,
+    PublicKeyParam, PublicKeyResult, Response, SignParam, WalletKeyParam, WalletResult,
+};
+use crate::error_handling::{landingpad, Result, LAST_ERROR};
+use crate::handler;
+use crate::handler::encode_message;
+use crate::token_core::TokenCore;
+
+/// The json form of `TcxAction`, `param` is the json form of the param message of `method`.
+/// The `input` of a `SignParam` is the json form of the tx input of its `chainType`
+#[derive(Deserialize)]
+struct JsonAction {
+    method: String,
+    #[serde(default)]
+    param: Value,
+}
+
+type Handler = fn(&TokenCore, &[u8]) -> Result<Vec<u8>>;
+
+/// Run the handler of `method` with a json param and return the json form of its result. When an
+/// error occurred the json form of a `Response` is returned whose `isSuccess` is false
+pub(crate) unsafe fn dispatch_json(
+    token_core: &TokenCore,
+    json_str: *const c_char,
+) -> *const c_char {
+    let json_c_str = CStr::from_ptr(json_str);
+
+    let reply: Vec<u8> = landingpad(token_core, || {
+        let action: JsonAction = serde_json::from_str(json_c_str.to_str()?)?;
+        let ret = call_json(token_core, &action.method, action.param)?;
+        Ok(ret.to_string().into_bytes())
+    });
+
+    let ret_str = if reply.is_empty() {
+        let error = LAST_ERROR.with(|e| match *e.borrow() {
+            Some(ref err) => err.to_string(),
+            None => "".to_string(),
+        });
+        let rsp = Response {
+            is_success: false,
+            error,
+        };
+        serde_json::to_string(&rsp).expect("encode error")
+    } else {
+        String::from_utf8(reply).expect("json reply")
+    };
+    CString::new(ret_str).unwrap().into_raw()
+}
+
+/// Run the handler of `method` with a json param, the same handler `call_tcx_api` runs
+#[allow(deprecated)]
+pub fn call_json(token_core: &TokenCore, method: &str, param: Value) -> Result<Value> {
+    match method.to_lowercase().as_str() {
+        "init_token_core_x" => {
+            let param: InitTokenCoreXParam = serde_json::from_value(param)?;
+            handler::init_token_core_x(token_core, &encode_message(param)?)?;
+            Ok(Value::Null)
+        }
+        "scan_keystores" => {
+            handler::scan_keystores(token_core)?;
+            Ok(Value::Null)
+        }
+        "supported_coins" => Ok(serde_json::to_value(
+            crate::coin_registry::supported_coins(),
+        )?),
+        "coin_info_query" => invoke::<CoinInfoQueryParam, CoinInfoQueryResult>(
+            token_core,
+            param,
+            handler::coin_info_query,
+        ),
+        "hd_store_create" => {
+            invoke::<HdStoreCreateParam, WalletResult>(token_core, param, handler::hd_store_create)
+        }
+        "hd_store_import" => {
+            invoke::<HdStoreImportParam, WalletResult>(token_core, param, handler::hd_store_import)
+        }
+        "hd_store_export" => invoke::<WalletKeyParam, KeystoreCommonExportResult>(
+            token_core,
+            param,
+            handler::hd_store_export,
+        ),
+        "export_mnemonic" => invoke::<WalletKeyParam, KeystoreCommonExportResult>(
+            token_core,
+            param,
+            handler::export_mnemonic,
+        ),
+        "keystore_common_derive" => invoke::<KeystoreCommonDeriveParam, AccountsResponse>(
+            token_core,
+            param,
+            handler::keystore_common_derive,
+        ),
+        "private_key_store_import" => invoke::<PrivateKeyStoreImportParam, WalletResult>(
+            token_core,
+            param,
+            handler::private_key_store_import,
+        ),
+        "private_key_store_export" => invoke::<
+            PrivateKeyStoreExportParam,
+            KeystoreCommonExportResult,
+        >(
+            token_core, param, handler::private_key_store_export
+        ),
+        "export_private_key" => invoke::<ExportPrivateKeyParam, KeystoreCommonExportResult>(
+            token_core,
+            param,
+            handler::export_private_key,
+        ),
+        "keystore_common_verify" => {
+            invoke::<WalletKeyParam, Response>(token_core, param, handler::keystore_common_verify)
+        }
+        "keystore_common_delete" => {
+            invoke::<WalletKeyParam, Response>(token_core, param, handler::keystore_common_delete)
+        }
+        "keystore_common_exists" => {
+            invoke::<KeystoreCommonExistsParam, KeystoreCommonExistsResult>(
+                token_core,
+                param,
+                handler::keystore_common_exists,
+            )
+        }
+        "keystore_common_accounts" => invoke::<KeystoreCommonAccountsParam, AccountsResponse>(
+            token_core,
+            param,
+            handler::keystore_common_accounts,
+        ),
+        "backup_wallets" => invoke::<BackupWalletsParam, BackupWalletsResult>(
+            token_core,
+            param,
+            handler::backup_wallets,
+        ),
+        "restore_wallets" => invoke::<RestoreWalletsParam, RestoreWalletsResult>(
+            token_core,
+            param,
+            handler::restore_wallets,
+        ),
+        "v3_keystore_import" => invoke::<V3KeystoreImportParam, WalletResult>(
+            token_core,
+            param,
+            handler::v3_keystore_import,
+        ),
+        "v3_keystore_export" => invoke::<V3KeystoreExportParam, V3KeystoreExportResult>(
+            token_core,
+            param,
+            handler::v3_keystore_export,
+        ),
+        "slip39_shares_export" => invoke::<Slip39SharesExportParam, Slip39SharesExportResult>(
+            token_core,
+            param,
+            handler::slip39_shares_export,
+        ),
+        "slip39_import" => {
+            invoke::<Slip39ImportParam, WalletResult>(token_core, param, handler::slip39_import)
+        }
+        "mnemonic_words_verify" => invoke::<MnemonicWordsVerifyParam, Response>(
+            token_core,
+            param,
+            handler::mnemonic_words_verify,
+        ),
+        "mnemonic_candidates" => invoke::<MnemonicCandidatesParam, MnemonicCandidatesResult>(
+            token_core,
+            param,
+            handler::mnemonic_candidates,
+        ),
+        "sub_addresses_derive" => invoke::<SubAddressesDeriveParam, SubAddressesDeriveResult>(
+            token_core,
+            param,
+            handler::sub_addresses_derive,
+        ),
+        "sign_tx" => sign_tx(token_core, param),
+        "batch_sign_tx" => batch_sign_tx(token_core, param),
+        "get_public_key" => {
+            invoke::<PublicKeyParam, PublicKeyResult>(token_core, param, handler::get_public_key)
+        }
+        "verify_signature" => {
+            invoke::<VerifySignatureParam, Response>(token_core, param, handler::verify_signature)
+        }
+        "tron_sign_msg" => tron_sign_message(token_core, param),
+        "substrate_keystore_exists" => {
+            invoke::<SubstrateKeystoreParam, KeystoreCommonExistsResult>(
+                token_core,
+                param,
+                handler::substrate_keystore_exists,
+            )
+        }
+        "substrate_keystore_import" => invoke::<SubstrateKeystoreParam, WalletResult>(
+            token_core,
+            param,
+            handler::import_substrate_keystore,
+        ),
+        "substrate_keystore_export" => {
+            invoke::<ExportPrivateKeyParam, ExportSubstrateKeystoreResult>(
+                token_core,
+                param,
+                handler::export_substrate_keystore,
+            )
+        }
+        "get_derived_key" => {
+            invoke::<WalletKeyParam, DerivedKeyResult>(token_core, param, handler::get_derived_key)
+        }
+        "unlock_then_crash" => {
+            invoke::<WalletKeyParam, Response>(token_core, param, handler::unlock_then_crash)
+        }
+        _ => Err(format_err!("unsupported_method")),
+    }
+}
+
+fn invoke<P, R>(token_core: &TokenCore, param: Value, handler: Handler) -> Result<Value>
+where
+    P: Message + DeserializeOwned,
+    R: Message + Default + Serialize,
+{
+    let param: P = serde_json::from_value(param)?;
+    let ret = handler(token_core, &encode_message(param)?)?;
+    decode_json::<R>(&ret)
+}
+
+fn encode_any<T: Message + DeserializeOwned>(input: Value) -> Result<::prost_types::Any> {
+    let input: T = serde_json::from_value(input)?;
+    Ok(::prost_types::Any {
+        type_url: "imtoken".to_string(),
+        value: encode_message(input)?,
+    })
+}
+
+fn decode_json<T: Message + Default + Serialize>(data: &[u8]) -> Result<Value> {
+    let msg = T::decode(data)?;
+    Ok(serde_json::to_value(msg)?)
+}
+
+fn encode_tx_input(chain_type: &str, input: Value) -> Result<::prost_types::Any> {
+    match chain_type {
+        "TRON" => encode_any::<TronTxInput>(input),
+        "NERVOS" => encode_any::<CkbTxInput>(input),
+        "POLKADOT" | "KUSAMA" => encode_any::<SubstrateRawTxIn>(input),
+        "FILECOIN" => encode_any::<UnsignedMessage>(input),
+        "TEZOS" => encode_any::<TezosRawTxIn>(input),
+        chain_type if is_btc_fork(chain_type) => encode_any::<BtcForkTxInput>(input),
+        _ => Err(format_err!("unsupported_chain")),
+    }
+}
+
+fn decode_tx_output(chain_type: &str, output: &[u8]) -> Result<Value> {
+    match chain_type {
+        "TRON" => decode_json::<TronTxOutput>(output),
+        "NERVOS" => decode_json::<CkbTxOutput>(output),
+        "POLKADOT" | "KUSAMA" => decode_json::<SubstrateTxOut>(output),
+        "FILECOIN" => decode_json::<SignedMessage>(output),
+        "TEZOS" => decode_json::<TezosTxOut>(output),
+        chain_type if is_btc_fork(chain_type) => decode_json::<BtcForkSignedTxOutput>(output),
+        _ => Err(format_err!("unsupported_chain")),
+    }
+}
+
+fn sign_param_from_json(param: &Value) -> Result<SignParam> {
+    let mut sign_param: SignParam = serde_json::from_value(param.clone())?;
+    sign_param.input = Some(encode_tx_input(
+        &sign_param.chain_type,
+        param["input"].clone(),
+    )?);
+    Ok(sign_param)
+}
+
+fn sign_tx(token_core: &TokenCore, param: Value) -> Result<Value> {
+    let param = sign_param_from_json(&param)?;
+    let ret = handler::sign_tx(token_core, &encode_message(param.clone())?)?;
+    decode_tx_output(&param.chain_type, &ret)
+}
+
+fn batch_sign_tx(token_core: &TokenCore, param: Value) -> Result<Value> {
+    let mut batch_param: BatchSignParam = serde_json::from_value(param.clone())?;
+    let sign_params = match param["signParams"].as_array() {
+        Some(items) => items
+            .iter()
+            .map(sign_param_from_json)
+            .collect::<Result<Vec<SignParam>>>()?,
+        None => vec![],
+    };
+    batch_param.sign_params = sign_params.clone();
+
+    let ret = handler::batch_sign_tx(token_core, &encode_message(batch_param)?)?;
+    let batch_result = BatchSignResult::decode(ret.as_slice())?;
+    let mut json = serde_json::to_value(&batch_result)?;
+    for (idx, result) in batch_result.results.iter().enumerate() {
+        if let Some(output) = &result.output {
+            json["results"][idx]["output"] =
+                decode_tx_output(&sign_params[idx].chain_type, &output.value)?;
+        }
+    }
+    Ok(json)
+}
+
+fn tron_sign_message(token_core: &TokenCore, param: Value) -> Result<Value> {
+    let mut sign_param: SignParam = serde_json::from_value(param.clone())?;
+    sign_param.input = Some(encode_any::<TronMessageInput>(param["input"].clone())?);
+    let ret = handler::tron_sign_message(token_core, &encode_message(sign_param)?)?;
+    decode_json::<TronMessageOutput>(&ret)
+}
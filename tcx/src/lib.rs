@@ -5,6 +5,8 @@ use std::os::raw::c_char;
 use prost::Message;
 
 pub mod api;
+mod backup;
+pub mod coin_registry;
 
 use crate::api::{Response, TcxAction};
 
@@ -14,19 +16,25 @@ pub mod handler;
 use crate::error_handling::{landingpad, LAST_BACKTRACE, LAST_ERROR};
 #[allow(deprecated)]
 use crate::handler::{
-    encode_message, export_mnemonic, export_private_key, get_derived_key, hd_store_create,
-    hd_store_export, hd_store_import, keystore_common_accounts, keystore_common_delete,
-    keystore_common_derive, keystore_common_exists, keystore_common_verify,
+    batch_sign_tx, encode_message, export_mnemonic, export_private_key, get_derived_key,
+    hd_store_create, hd_store_export, hd_store_import, keystore_common_accounts,
+    keystore_common_delete, keystore_common_derive, keystore_common_exists, keystore_common_verify,
     private_key_store_export, private_key_store_import, sign_tx, tron_sign_message,
     unlock_then_crash,
 };
 
 mod filemanager;
+pub mod json_transport;
+pub mod security_policy;
+pub mod token_core;
+
+use crate::filemanager::TOKEN_CORE;
+pub use crate::security_policy::SecurityPolicy;
+pub use crate::token_core::{TokenCore, TokenCoreConfig};
 
 use crate::handler::{
     export_substrate_keystore, get_public_key, import_substrate_keystore, substrate_keystore_exists,
 };
-use parking_lot::RwLock;
 
 extern crate serde_json;
 
@@ -35,10 +43,6 @@ extern crate failure;
 #[macro_use]
 extern crate lazy_static;
 
-lazy_static! {
-    pub static ref IS_DEBUG: RwLock<bool> = RwLock::new(false);
-}
-
 #[no_mangle]
 pub unsafe extern "C" fn free_const_string(s: *const c_char) {
     if s.is_null() {
@@ -49,73 +53,174 @@ pub unsafe extern "C" fn free_const_string(s: *const c_char) {
 
 /// dispatch protobuf rpc call
 ///
-#[allow(deprecated)]
 #[no_mangle]
 pub unsafe extern "C" fn call_tcx_api(hex_str: *const c_char) -> *const c_char {
+    dispatch(&TOKEN_CORE, hex_str)
+}
+
+/// create an independent instance for `call_tcx_api_with_context`, it must be initialized by
+/// `init_token_core_x` and released by `free_token_core`
+#[no_mangle]
+pub unsafe extern "C" fn new_token_core() -> *mut TokenCore {
+    Box::into_raw(Box::new(TokenCore::new("")))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn free_token_core(token_core: *mut TokenCore) {
+    if token_core.is_null() {
+        return;
+    }
+    Box::from_raw(token_core);
+}
+
+/// dispatch protobuf rpc call to the instance created by `new_token_core`
+#[no_mangle]
+pub unsafe extern "C" fn call_tcx_api_with_context(
+    token_core: *const TokenCore,
+    hex_str: *const c_char,
+) -> *const c_char {
+    dispatch(&*token_core, hex_str)
+}
+
+/// dispatch json rpc call, the request is `{"method": "...", "param": {...}}` where `param` is the
+/// json form of the protobuf param, and the reply is the json form of the protobuf result
+#[no_mangle]
+pub unsafe extern "C" fn call_tcx_api_json(json_str: *const c_char) -> *const c_char {
+    json_transport::dispatch_json(&TOKEN_CORE, json_str)
+}
+
+/// dispatch json rpc call to the instance created by `new_token_core`
+#[no_mangle]
+pub unsafe extern "C" fn call_tcx_api_json_with_context(
+    token_core: *const TokenCore,
+    json_str: *const c_char,
+) -> *const c_char {
+    json_transport::dispatch_json(&*token_core, json_str)
+}
+
+#[allow(deprecated)]
+unsafe fn dispatch(token_core: &TokenCore, hex_str: *const c_char) -> *const c_char {
     let hex_c_str = CStr::from_ptr(hex_str);
     let hex_str = hex_c_str.to_str().expect("parse_arguments to_str");
 
     let data = hex::decode(hex_str).expect("parse_arguments hex decode");
     let action: TcxAction = TcxAction::decode(data.as_slice()).expect("decode tcx api");
     let reply: Vec<u8> = match action.method.to_lowercase().as_str() {
-        "init_token_core_x" => landingpad(|| {
-            handler::init_token_core_x(&action.param.unwrap().value).unwrap();
+        "init_token_core_x" => landingpad(token_core, || {
+            handler::init_token_core_x(token_core, &action.param.unwrap().value)?;
             Ok(vec![])
         }),
-        "scan_keystores" => landingpad(|| {
-            handler::scan_keystores().unwrap();
+        "scan_keystores" => landingpad(token_core, || {
+            handler::scan_keystores(token_core).unwrap();
             Ok(vec![])
         }),
-        "hd_store_create" => landingpad(|| hd_store_create(&action.param.unwrap().value)),
-        "hd_store_import" => landingpad(|| hd_store_import(&action.param.unwrap().value)),
-        "hd_store_export" => landingpad(|| hd_store_export(&action.param.unwrap().value)),
-        "export_mnemonic" => landingpad(|| export_mnemonic(&action.param.unwrap().value)),
-        "keystore_common_derive" => {
-            landingpad(|| keystore_common_derive(&action.param.unwrap().value))
-        }
+        "supported_coins" => landingpad(token_core, || handler::supported_coins(token_core)),
+        "coin_info_query" => landingpad(token_core, || {
+            handler::coin_info_query(token_core, &action.param.unwrap().value)
+        }),
+        "hd_store_create" => landingpad(token_core, || {
+            hd_store_create(token_core, &action.param.unwrap().value)
+        }),
+        "hd_store_import" => landingpad(token_core, || {
+            hd_store_import(token_core, &action.param.unwrap().value)
+        }),
+        "hd_store_export" => landingpad(token_core, || {
+            hd_store_export(token_core, &action.param.unwrap().value)
+        }),
+        "export_mnemonic" => landingpad(token_core, || {
+            export_mnemonic(token_core, &action.param.unwrap().value)
+        }),
+        "keystore_common_derive" => landingpad(token_core, || {
+            keystore_common_derive(token_core, &action.param.unwrap().value)
+        }),
 
-        "private_key_store_import" => {
-            landingpad(|| private_key_store_import(&action.param.unwrap().value))
-        }
-        "private_key_store_export" => {
-            landingpad(|| private_key_store_export(&action.param.unwrap().value))
-        }
-        "export_private_key" => landingpad(|| export_private_key(&action.param.unwrap().value)),
-        "keystore_common_verify" => {
-            landingpad(|| keystore_common_verify(&action.param.unwrap().value))
-        }
-        "keystore_common_delete" => {
-            landingpad(|| keystore_common_delete(&action.param.unwrap().value))
-        }
-        "keystore_common_exists" => {
-            landingpad(|| keystore_common_exists(&action.param.unwrap().value))
-        }
-        "keystore_common_accounts" => {
-            landingpad(|| keystore_common_accounts(&action.param.unwrap().value))
-        }
+        "private_key_store_import" => landingpad(token_core, || {
+            private_key_store_import(token_core, &action.param.unwrap().value)
+        }),
+        "private_key_store_export" => landingpad(token_core, || {
+            private_key_store_export(token_core, &action.param.unwrap().value)
+        }),
+        "export_private_key" => landingpad(token_core, || {
+            export_private_key(token_core, &action.param.unwrap().value)
+        }),
+        "keystore_common_verify" => landingpad(token_core, || {
+            keystore_common_verify(token_core, &action.param.unwrap().value)
+        }),
+        "keystore_common_delete" => landingpad(token_core, || {
+            keystore_common_delete(token_core, &action.param.unwrap().value)
+        }),
+        "keystore_common_exists" => landingpad(token_core, || {
+            keystore_common_exists(token_core, &action.param.unwrap().value)
+        }),
+        "keystore_common_accounts" => landingpad(token_core, || {
+            keystore_common_accounts(token_core, &action.param.unwrap().value)
+        }),
+        "backup_wallets" => landingpad(token_core, || {
+            handler::backup_wallets(token_core, &action.param.unwrap().value)
+        }),
+        "restore_wallets" => landingpad(token_core, || {
+            handler::restore_wallets(token_core, &action.param.unwrap().value)
+        }),
+        "v3_keystore_import" => landingpad(token_core, || {
+            handler::v3_keystore_import(token_core, &action.param.unwrap().value)
+        }),
+        "v3_keystore_export" => landingpad(token_core, || {
+            handler::v3_keystore_export(token_core, &action.param.unwrap().value)
+        }),
+        "slip39_shares_export" => landingpad(token_core, || {
+            handler::slip39_shares_export(token_core, &action.param.unwrap().value)
+        }),
+        "slip39_import" => landingpad(token_core, || {
+            handler::slip39_import(token_core, &action.param.unwrap().value)
+        }),
+        "mnemonic_words_verify" => landingpad(token_core, || {
+            handler::mnemonic_words_verify(token_core, &action.param.unwrap().value)
+        }),
+        "mnemonic_candidates" => landingpad(token_core, || {
+            handler::mnemonic_candidates(token_core, &action.param.unwrap().value)
+        }),
+        "sub_addresses_derive" => landingpad(token_core, || {
+            handler::sub_addresses_derive(token_core, &action.param.unwrap().value)
+        }),
 
-        "sign_tx" => landingpad(|| sign_tx(&action.param.unwrap().value)),
-        "get_public_key" => landingpad(|| get_public_key(&action.param.unwrap().value)),
+        "sign_tx" => landingpad(token_core, || {
+            sign_tx(token_core, &action.param.unwrap().value)
+        }),
+        "batch_sign_tx" => landingpad(token_core, || {
+            batch_sign_tx(token_core, &action.param.unwrap().value)
+        }),
+        "get_public_key" => landingpad(token_core, || {
+            get_public_key(token_core, &action.param.unwrap().value)
+        }),
+        "verify_signature" => landingpad(token_core, || {
+            handler::verify_signature(token_core, &action.param.unwrap().value)
+        }),
 
-        "tron_sign_msg" => landingpad(|| tron_sign_message(&action.param.unwrap().value)),
+        "tron_sign_msg" => landingpad(token_core, || {
+            tron_sign_message(token_core, &action.param.unwrap().value)
+        }),
 
-        "substrate_keystore_exists" => {
-            landingpad(|| substrate_keystore_exists(&action.param.unwrap().value))
-        }
+        "substrate_keystore_exists" => landingpad(token_core, || {
+            substrate_keystore_exists(token_core, &action.param.unwrap().value)
+        }),
 
-        "substrate_keystore_import" => {
-            landingpad(|| import_substrate_keystore(&action.param.unwrap().value))
-        }
+        "substrate_keystore_import" => landingpad(token_core, || {
+            import_substrate_keystore(token_core, &action.param.unwrap().value)
+        }),
 
-        "substrate_keystore_export" => {
-            landingpad(|| export_substrate_keystore(&action.param.unwrap().value))
-        }
+        "substrate_keystore_export" => landingpad(token_core, || {
+            export_substrate_keystore(token_core, &action.param.unwrap().value)
+        }),
 
         // !!! WARNING !!! used for `cache_dk` feature
-        "get_derived_key" => landingpad(|| get_derived_key(&action.param.unwrap().value)),
+        "get_derived_key" => landingpad(token_core, || {
+            get_derived_key(token_core, &action.param.unwrap().value)
+        }),
         // !!! WARNING !!! used for test only
-        "unlock_then_crash" => landingpad(|| unlock_then_crash(&action.param.unwrap().value)),
-        _ => landingpad(|| Err(format_err!("unsupported_method"))),
+        "unlock_then_crash" => landingpad(token_core, || {
+            unlock_then_crash(token_core, &action.param.unwrap().value)
+        }),
+        _ => landingpad(token_core, || Err(format_err!("unsupported_method"))),
     };
 
     let ret_str = hex::encode(reply);
@@ -152,7 +257,6 @@ pub unsafe extern "C" fn get_last_err_message() -> *const c_char {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::filemanager::KEYSTORE_MAP;
     use api::sign_param::Key;
     use error_handling::Result;
     use std::ffi::{CStr, CString};
@@ -162,6 +266,7 @@ mod tests {
     use std::path::Path;
 
     use crate::api::keystore_common_derive_param::Derivation;
+    use crate::api::{batch_sign_param, BatchSignParam, BatchSignResult};
     use crate::api::{
         AccountsResponse, DerivedKeyResult, ExportPrivateKeyParam, HdStoreCreateParam,
         InitTokenCoreXParam, KeyType, KeystoreCommonAccountsParam, KeystoreCommonDeriveParam,
@@ -214,9 +319,13 @@ mod tests {
             xpub_common_key: "B888D25EC8C12BD5043777B1AC49F872".to_string(),
             xpub_common_iv: "9C0C30889CBCC5E01AB5B2BB88715799".to_string(),
             is_debug: false,
+            security_policy: None,
+            coins: vec![],
+            btc_fork_networks: vec![],
         };
 
-        handler::init_token_core_x(&encode_message(param).unwrap()).expect("should init tcx");
+        handler::init_token_core_x(&TOKEN_CORE, &encode_message(param).unwrap())
+            .expect("should init tcx");
     }
 
     fn teardown() {
@@ -258,8 +367,10 @@ mod tests {
             name: "test-wallet".to_string(),
             password_hint: "imtoken".to_string(),
             overwrite: true,
+            passphrase: "".to_string(),
+            language: "".to_string(),
         };
-        let ret = hd_store_import(&encode_message(param).unwrap()).unwrap();
+        let ret = hd_store_import(&TOKEN_CORE, &encode_message(param).unwrap()).unwrap();
         WalletResult::decode(ret.as_slice()).unwrap()
     }
 
@@ -273,7 +384,7 @@ mod tests {
             encoding: "".to_string(),
         };
 
-        let ret = private_key_store_import(&encode_message(param).unwrap()).unwrap();
+        let ret = private_key_store_import(&TOKEN_CORE, &encode_message(param).unwrap()).unwrap();
         WalletResult::decode(ret.as_slice()).unwrap()
     }
 
@@ -288,7 +399,7 @@ mod tests {
             encoding: "".to_string(),
         };
 
-        let ret = private_key_store_import(&encode_message(param).unwrap()).unwrap();
+        let ret = private_key_store_import(&TOKEN_CORE, &encode_message(param).unwrap()).unwrap();
         WalletResult::decode(ret.as_slice()).unwrap()
     }
 
@@ -348,6 +459,173 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_call_tcx_api_with_context() {
+        let dir = "/tmp/imtoken/context_wallets";
+        let p = Path::new(dir);
+        if !p.exists() {
+            fs::create_dir_all(p).expect("shoud create filedir");
+        }
+
+        let call = |token_core: *const TokenCore, method: &str, msg: Vec<u8>| {
+            let param = TcxAction {
+                method: method.to_string(),
+                param: Some(::prost_types::Any {
+                    type_url: "imtoken".to_string(),
+                    value: msg,
+                }),
+            };
+            let param_hex = hex::encode(encode_message(param).unwrap());
+            let _ = unsafe { clear_err() };
+            let ret_hex = unsafe {
+                _to_str(call_tcx_api_with_context(
+                    token_core,
+                    _to_c_char(&param_hex),
+                ))
+            };
+            assert!(unsafe { _to_str(get_last_err_message()) }.is_empty());
+            hex::decode(ret_hex).unwrap()
+        };
+
+        let token_core = unsafe { new_token_core() };
+        let init_param = InitTokenCoreXParam {
+            file_dir: dir.to_string(),
+            xpub_common_key: "B888D25EC8C12BD5043777B1AC49F872".to_string(),
+            xpub_common_iv: "9C0C30889CBCC5E01AB5B2BB88715799".to_string(),
+            is_debug: true,
+            security_policy: None,
+            coins: vec![],
+            btc_fork_networks: vec![],
+        };
+        call(
+            token_core,
+            "init_token_core_x",
+            encode_message(init_param).unwrap(),
+        );
+
+        let import_param = HdStoreImportParam {
+            mnemonic: TEST_MNEMONIC.to_string(),
+            password: TEST_PASSWORD.to_string(),
+            source: "MNEMONIC".to_string(),
+            name: "test-wallet".to_string(),
+            password_hint: "imtoken".to_string(),
+            overwrite: true,
+            passphrase: "".to_string(),
+            language: "".to_string(),
+        };
+        let ret = call(
+            token_core,
+            "hd_store_import",
+            encode_message(import_param).unwrap(),
+        );
+        let wallet: WalletResult = WalletResult::decode(ret.as_slice()).unwrap();
+
+        let context = unsafe { &*token_core };
+        assert_eq!(context.wallet_dir(), dir);
+        assert!(context.keystores().read().contains_key(&wallet.id));
+        assert!(!TOKEN_CORE.keystores().read().contains_key(&wallet.id));
+
+        context.delete_wallet(&wallet.id, TEST_PASSWORD).unwrap();
+        unsafe { free_token_core(token_core) };
+    }
+
+    fn call_json_api(json: serde_json::Value) -> serde_json::Value {
+        let ret = unsafe { _to_str(call_tcx_api_json(_to_c_char(&json.to_string()))) };
+        serde_json::from_str(ret).unwrap()
+    }
+
+    #[test]
+    fn test_call_tcx_api_json() {
+        run_test(|| {
+            let wallet = call_json_api(serde_json::json!({
+                "method": "hd_store_import",
+                "param": {
+                    "mnemonic": TEST_MNEMONIC,
+                    "password": TEST_PASSWORD,
+                    "source": "MNEMONIC",
+                    "name": "test-wallet",
+                    "overwrite": true
+                }
+            }));
+            let wallet_id = wallet["id"].as_str().unwrap().to_string();
+            assert!(wallet["createdAt"].is_string());
+
+            let accounts = call_json_api(serde_json::json!({
+                "method": "keystore_common_derive",
+                "param": {
+                    "id": wallet_id,
+                    "password": TEST_PASSWORD,
+                    "derivations": [{
+                        "chainType": "TRON",
+                        "path": "m/44'/195'/0'/0/0"
+                    }]
+                }
+            }));
+            assert_eq!(
+                accounts["accounts"][0]["address"],
+                "TLZnqkrSNLUWNrZMug8u9b6pJ3XcTGbzDV"
+            );
+
+            let raw_data = "0a0202a22208e216e254e43ee10840c8cbe4e3df2d5a67080112630a2d747970652e676f6f676c65617069732e636f6d2f70726f746f636f6c2e5472616e73666572436f6e747261637412320a15415c68cc82c87446f602f019e5fd797437f5b79cc212154156a6076cd1537fa317c2606e4edfa4acd3e8e92e18a08d06709084e1e3df2d";
+            let output = call_json_api(serde_json::json!({
+                "method": "sign_tx",
+                "param": {
+                    "id": wallet_id,
+                    "key": { "password": TEST_PASSWORD },
+                    "chainType": "TRON",
+                    "address": "TLZnqkrSNLUWNrZMug8u9b6pJ3XcTGbzDV",
+                    "input": { "rawData": raw_data }
+                }
+            }));
+            assert_eq!(output["signatures"][0], "bbf5ce0549490613a26c3ac4fc8574e748eabda05662b2e49cea818216b9da18691e78cd6379000e9c8a35c13dfbf620f269be90a078b58799b56dc20da3bdf200");
+
+            let ret = call_json_api(serde_json::json!({
+                "method": "keystore_common_verify",
+                "param": { "id": wallet_id, "password": "WRONG PASSWORD" }
+            }));
+            assert_eq!(ret["isSuccess"], false);
+            assert_eq!(ret["error"], "password_incorrect");
+
+            let ret = call_json_api(serde_json::json!({
+                "method": "keystore_common_delete",
+                "param": { "id": wallet_id, "password": TEST_PASSWORD }
+            }));
+            assert_eq!(ret["isSuccess"], true);
+        });
+    }
+
+    #[test]
+    fn test_call_tcx_api_json_malformed() {
+        run_test(|| {
+            let ret = unsafe { _to_str(call_tcx_api_json(_to_c_char("{\"method\": "))) };
+            let ret: serde_json::Value = serde_json::from_str(ret).unwrap();
+            assert_eq!(ret["isSuccess"], false);
+            assert_ne!(ret["error"], "");
+        });
+    }
+
+    #[test]
+    fn test_json_int64_as_string() {
+        let input: BtcForkTxInput = serde_json::from_value(serde_json::json!({
+            "to": "mrU9pEmAx26HcbKVrABvgL7AwA5fjNFoDc",
+            "amount": "9007199254740993",
+            "fee": 1000,
+            "unspents": [{ "amount": "9007199254740993" }]
+        }))
+        .unwrap();
+        assert_eq!(input.amount, 9007199254740993);
+        assert_eq!(input.fee, 1000);
+        assert_eq!(input.unspents[0].amount, 9007199254740993);
+
+        let json = serde_json::to_value(&input).unwrap();
+        assert_eq!(json["amount"], "9007199254740993");
+        assert_eq!(json["fee"], "1000");
+        assert_eq!(json["maxFee"], "0");
+
+        let err = serde_json::from_value::<BtcForkTxInput>(serde_json::json!({ "amount": "1e3" }));
+        assert!(err.is_err());
+    }
+
     #[test]
     fn test_call_tcx_api() {
         run_test(|| {
@@ -358,6 +636,8 @@ mod tests {
                 name: "call_tcx_api".to_string(),
                 password_hint: "".to_string(),
                 overwrite: true,
+                passphrase: "".to_string(),
+                language: "".to_string(),
             };
             // let ret_bytes = call_api("hd_store_import", import_param).unwrap();
             let ret_bytes = hex::decode("0a2434656239623136392d323237392d343439332d616535342d62396233643761303630323512036161611a084d4e454d4f4e494328e9a1a2f305").unwrap();
@@ -373,13 +653,17 @@ mod tests {
             xpub_common_key: "B888D25EC8C12BD5043777B1AC49F872".to_string(),
             xpub_common_iv: "9C0C30889CBCC5E01AB5B2BB88715799".to_string(),
             is_debug: true,
+            security_policy: None,
+            coins: vec![],
+            btc_fork_networks: vec![],
         };
 
-        handler::init_token_core_x(&encode_message(param).unwrap()).expect("should init tcx");
+        handler::init_token_core_x(&TOKEN_CORE, &encode_message(param).unwrap())
+            .expect("should init tcx");
 
         let keystore_count;
         {
-            let mut map = KEYSTORE_MAP.write();
+            let mut map = TOKEN_CORE.keystores().write();
             keystore_count = map.len();
             map.clear();
             assert_eq!(0, map.len());
@@ -390,7 +674,7 @@ mod tests {
         };
         let _ = call_api("scan_keystores", empty);
         {
-            let map = KEYSTORE_MAP.write();
+            let map = TOKEN_CORE.keystores().write();
 
             assert_eq!(keystore_count, map.len());
         }
@@ -403,6 +687,10 @@ mod tests {
                 password: TEST_PASSWORD.to_string(),
                 password_hint: "".to_string(),
                 name: "aaa".to_string(),
+                passphrase: "".to_string(),
+                language: "".to_string(),
+                word_count: 0,
+                entropy: "".to_string(),
             };
 
             let ret = call_api("hd_store_create", param).unwrap();
@@ -461,6 +749,8 @@ mod tests {
                     name: "test-wallet".to_string(),
                     password_hint: "imtoken".to_string(),
                     overwrite: true,
+                    passphrase: "".to_string(),
+                    language: "".to_string(),
                 };
 
                 let ret = call_api("hd_store_import", param);
@@ -500,6 +790,78 @@ mod tests {
         })
     }
 
+    #[test]
+    pub fn test_hd_store_derive_dogecoin_and_dash() {
+        run_test(|| {
+            let import_result: WalletResult = import_default_wallet();
+
+            let cases = vec![
+                (
+                    "DOGECOIN",
+                    "MAINNET",
+                    "m/44'/3'/0'/0/0",
+                    "DQ4tVEqdPWHc1aVBm4Sfwft8XyNRPMEchR",
+                    "QStUhP4SJLQC6ohNPaqiCBTJGTvAT4GCc5boyrs2Rnrj42Sgum3d",
+                ),
+                (
+                    "DOGECOIN",
+                    "TESTNET",
+                    "m/44'/1'/0'/0/0",
+                    "nZKaSJP5DAv4MSSNG4zyB833s92rHdzyqW",
+                    "cjVvGvRrsL4TKDF7nj7hb6X9ccD6MqyJ8yy89GKHvB8wvK5zz2bR",
+                ),
+                (
+                    "DASH",
+                    "MAINNET",
+                    "m/44'/5'/0'/0/0",
+                    "XqqWUaYw1NjaeDai27BUTmWw41PRLVHYNE",
+                    "XJ8LNHJn4EJkjTL6KvrU6Dykqduua12N1V4X7Ta9kDqjFKP53TS2",
+                ),
+                (
+                    "DASH",
+                    "TESTNET",
+                    "m/44'/1'/0'/0/0",
+                    "yRSs2ERrP3RiS9ChvPzaGWPHrkzKWYVwd8",
+                    "cSLf8bi4X2EBNBDagaKuzkLB7jonh1Rqp6is2Md6wDdXMekFqFzN",
+                ),
+            ];
+            for (chain_type, network, path, address, wif) in cases {
+                let derivation = Derivation {
+                    chain_type: chain_type.to_string(),
+                    path: path.to_string(),
+                    network: network.to_string(),
+                    seg_wit: "NONE".to_string(),
+                    chain_id: "".to_string(),
+                    curve: "".to_string(),
+                };
+                let param = KeystoreCommonDeriveParam {
+                    id: import_result.id.to_string(),
+                    password: TEST_PASSWORD.to_string(),
+                    derivations: vec![derivation],
+                };
+                let ret = call_api("keystore_common_derive", param).unwrap();
+                let result: AccountsResponse = AccountsResponse::decode(ret.as_slice()).unwrap();
+                assert_eq!(result.accounts.first().unwrap().chain_type, chain_type);
+                assert_eq!(result.accounts.first().unwrap().address, address);
+
+                let param = ExportPrivateKeyParam {
+                    id: import_result.id.to_string(),
+                    password: TEST_PASSWORD.to_string(),
+                    chain_type: chain_type.to_string(),
+                    network: network.to_string(),
+                    main_address: address.to_string(),
+                    path: path.to_string(),
+                };
+                let ret = call_api("export_private_key", param).unwrap();
+                let export_result: KeystoreCommonExportResult =
+                    KeystoreCommonExportResult::decode(ret.as_slice()).unwrap();
+                assert_eq!(export_result.value, wif);
+            }
+
+            remove_created_wallet(&import_result.id);
+        })
+    }
+
     #[test]
     pub fn test_hd_store_export() {
         run_test(|| {
@@ -560,6 +922,8 @@ mod tests {
                 name: "test-wallet".to_string(),
                 password_hint: "imtoken".to_string(),
                 overwrite: true,
+                passphrase: "".to_string(),
+                language: "".to_string(),
             };
             let ret = call_api("hd_store_import", param).unwrap();
             let import_result: WalletResult = WalletResult::decode(ret.as_slice()).unwrap();
@@ -881,7 +1245,8 @@ mod tests {
                 encoding: "TEZOS".to_string(),
             };
 
-            let ret = private_key_store_import(&encode_message(param).unwrap()).unwrap();
+            let ret =
+                private_key_store_import(&TOKEN_CORE, &encode_message(param).unwrap()).unwrap();
             let import_result: WalletResult = WalletResult::decode(ret.as_slice()).unwrap();
             assert_eq!(0, import_result.accounts.len());
 
@@ -911,6 +1276,7 @@ mod tests {
                 value: "edskRgu8wHxjwayvnmpLDDijzD3VZDoAH7ZLqJWuG4zg7LbxmSWZWhtkSyM5Uby41rGfsBGk4iPKWHSDniFyCRv3j7YFCknyHH"
                     .to_string(),
                 encoding: "TEZOS".to_string(),
+                passphrase: "".to_string(),
             };
 
             let ret_bytes = call_api("keystore_common_exists", param).unwrap();
@@ -1010,7 +1376,8 @@ mod tests {
                 encoding: "".to_string(),
             };
 
-            let ret = private_key_store_import(&encode_message(param).unwrap()).unwrap();
+            let ret =
+                private_key_store_import(&TOKEN_CORE, &encode_message(param).unwrap()).unwrap();
             let import_result: WalletResult = WalletResult::decode(ret.as_slice()).unwrap();
 
             assert_eq!(0, import_result.accounts.len());
@@ -1073,7 +1440,8 @@ mod tests {
                 encoding: "".to_string(),
             };
 
-            let ret = private_key_store_import(&encode_message(param).unwrap()).unwrap();
+            let ret =
+                private_key_store_import(&TOKEN_CORE, &encode_message(param).unwrap()).unwrap();
             let import_result: WalletResult = WalletResult::decode(ret.as_slice()).unwrap();
 
             assert_eq!(0, import_result.accounts.len());
@@ -1135,7 +1503,8 @@ mod tests {
                 encoding: "".to_string(),
             };
 
-            let ret = private_key_store_import(&encode_message(param).unwrap()).unwrap();
+            let ret =
+                private_key_store_import(&TOKEN_CORE, &encode_message(param).unwrap()).unwrap();
             let import_result: WalletResult = WalletResult::decode(ret.as_slice()).unwrap();
 
             assert_eq!(0, import_result.accounts.len());
@@ -1425,7 +1794,8 @@ mod tests {
                 encoding: "".to_string(),
             };
 
-            let ret = private_key_store_import(&encode_message(param).unwrap()).unwrap();
+            let ret =
+                private_key_store_import(&TOKEN_CORE, &encode_message(param).unwrap()).unwrap();
             let wallet: WalletResult = WalletResult::decode(ret.as_slice()).unwrap();
 
             let derivation = Derivation {
@@ -1490,7 +1860,8 @@ mod tests {
                 encoding: "".to_string(),
             };
 
-            let ret_bytes = private_key_store_import(&encode_message(param).unwrap()).unwrap();
+            let ret_bytes =
+                private_key_store_import(&TOKEN_CORE, &encode_message(param).unwrap()).unwrap();
             let import_result: WalletResult = WalletResult::decode(ret_bytes.as_slice()).unwrap();
 
             let param: WalletKeyParam = WalletKeyParam {
@@ -1515,6 +1886,7 @@ mod tests {
                 r#type: KeyType::PrivateKey as i32,
                 value: "5JZc7wGRUr4J1RHDcM9ySWKLfQ2xjRUEo612qC4RLJ3G7jzJ4qx".to_string(),
                 encoding: "".to_string(),
+                passphrase: "".to_string(),
             };
 
             let ret_bytes = call_api("keystore_common_exists", param).unwrap();
@@ -1533,6 +1905,7 @@ mod tests {
                 r#type: KeyType::Mnemonic as i32,
                 value: format!("{}", TEST_MNEMONIC).to_string(),
                 encoding: "".to_string(),
+                passphrase: "".to_string(),
             };
 
             let ret_bytes = call_api("keystore_common_exists", param).unwrap();
@@ -1546,6 +1919,7 @@ mod tests {
                 r#type: KeyType::PrivateKey as i32,
                 value: "L2hfzPyVC1jWH7n2QLTe7tVTb6btg9smp5UVzhEBxLYaSFF7sCZB".to_string(),
                 encoding: "".to_string(),
+                passphrase: "".to_string(),
             };
 
             let ret_bytes = call_api("keystore_common_exists", param).unwrap();
@@ -1559,6 +1933,7 @@ mod tests {
                 value: "a392604efc2fad9c0b3da43b5f698a2e3f270f170d859912be0d54742275c5f6"
                     .to_string(),
                 encoding: "".to_string(),
+                passphrase: "".to_string(),
             };
 
             let ret_bytes = call_api("keystore_common_exists", param).unwrap();
@@ -1763,6 +2138,78 @@ mod tests {
         })
     }
 
+    #[test]
+    pub fn test_batch_sign_tx() {
+        run_test(|| {
+            let derivation = Derivation {
+                chain_type: "TRON".to_string(),
+                path: "m/44'/195'/0'/0/0".to_string(),
+                network: "".to_string(),
+                seg_wit: "".to_string(),
+                chain_id: "".to_string(),
+                curve: "".to_string(),
+            };
+
+            let wallet = import_and_derive(derivation);
+
+            let raw_data = "0a0202a22208e216e254e43ee10840c8cbe4e3df2d5a67080112630a2d747970652e676f6f676c65617069732e636f6d2f70726f746f636f6c2e5472616e73666572436f6e747261637412320a15415c68cc82c87446f602f019e5fd797437f5b79cc212154156a6076cd1537fa317c2606e4edfa4acd3e8e92e18a08d06709084e1e3df2d".to_string();
+            let input = TronTxInput { raw_data };
+            let input_value = encode_message(input).unwrap();
+            let tron_tx = SignParam {
+                id: "".to_string(),
+                key: None,
+                chain_type: "TRON".to_string(),
+                address: wallet.accounts.first().unwrap().address.to_string(),
+                input: Some(::prost_types::Any {
+                    type_url: "imtoken".to_string(),
+                    value: input_value.clone(),
+                }),
+            };
+            let unsupported_tx = SignParam {
+                chain_type: "TRON1".to_string(),
+                ..tron_tx.clone()
+            };
+
+            let param = BatchSignParam {
+                id: wallet.id.to_string(),
+                key: Some(batch_sign_param::Key::Password(
+                    "WRONG PASSWORD".to_string(),
+                )),
+                sign_params: vec![tron_tx.clone()],
+            };
+            let ret = call_api("batch_sign_tx", param);
+            assert!(ret.is_err());
+            assert_eq!(format!("{}", ret.err().unwrap()), "password_incorrect");
+
+            let param = BatchSignParam {
+                id: wallet.id.to_string(),
+                key: Some(batch_sign_param::Key::Password(TEST_PASSWORD.to_string())),
+                sign_params: vec![tron_tx.clone(), unsupported_tx, tron_tx],
+            };
+            let ret = call_api("batch_sign_tx", param).unwrap();
+            let result: BatchSignResult = BatchSignResult::decode(ret.as_slice()).unwrap();
+            assert_eq!(result.results.len(), 3);
+
+            let expected_sign = "bbf5ce0549490613a26c3ac4fc8574e748eabda05662b2e49cea818216b9da18691e78cd6379000e9c8a35c13dfbf620f269be90a078b58799b56dc20da3bdf200";
+            for idx in [0, 2].iter() {
+                let item = &result.results[*idx];
+                assert!(item.is_success);
+                let output: TronTxOutput =
+                    TronTxOutput::decode(item.output.as_ref().unwrap().value.as_slice()).unwrap();
+                assert_eq!(expected_sign, output.signatures[0]);
+            }
+            assert!(!result.results[1].is_success);
+            assert_eq!(result.results[1].error, "unsupported_chain");
+
+            {
+                let map = TOKEN_CORE.keystores().read();
+                let keystore: &Keystore = map.get(&wallet.id).unwrap();
+                assert!(keystore.is_locked());
+            }
+            remove_created_wallet(&wallet.id);
+        })
+    }
+
     // #[test]
     // pub fn test_sign_substrate_tx() {
     //     run_test(|| {
@@ -2529,6 +2976,9 @@ mod tests {
                     change_address: "".to_string(),
                     network: "TESTNET".to_string(),
                     seg_wit: "NONE".to_string(),
+                    max_fee: 0,
+                    max_fee_ratio: 0,
+                    allow_absurd_fee: false,
                 };
                 let input_value = encode_message(tx_input).unwrap();
                 let tx = SignParam {
@@ -2580,7 +3030,7 @@ mod tests {
                 }),
             };
             {
-                let map = KEYSTORE_MAP.read();
+                let map = TOKEN_CORE.keystores().read();
                 let keystore: &Keystore = map.get(&wallet.id).unwrap();
                 assert!(keystore.is_locked());
             }
@@ -2591,7 +3041,7 @@ mod tests {
             assert_eq!(expected_sign, output.signatures[0]);
 
             {
-                let map = KEYSTORE_MAP.read();
+                let map = TOKEN_CORE.keystores().read();
                 let keystore: &Keystore = map.get(&wallet.id).unwrap();
                 assert!(keystore.is_locked());
             }
@@ -2607,9 +3057,13 @@ mod tests {
             xpub_common_key: "B888D25EC8C12BD5043777B1AC49F872".to_string(),
             xpub_common_iv: "9C0C30889CBCC5E01AB5B2BB88715799".to_string(),
             is_debug: true,
+            security_policy: None,
+            coins: vec![],
+            btc_fork_networks: vec![],
         };
 
-        handler::init_token_core_x(&encode_message(param).unwrap()).expect("should init tcx");
+        handler::init_token_core_x(&TOKEN_CORE, &encode_message(param).unwrap())
+            .expect("should init tcx");
 
         let param = WalletKeyParam {
             id: "cb1ba2d7-7b89-4595-9753-d16b6e317c6b".to_string(),
@@ -2639,7 +3093,7 @@ mod tests {
     //            is_debug: true,
     //        };
     //
-    //        handler::init_token_core_x(&encode_message(param).unwrap()).expect("should init tcx");
+    //        handler::init_token_core_x(&TOKEN_CORE, &encode_message(param).unwrap()).expect("should init tcx");
     //
     //        let param = PrivateKeyStoreExportParam {
     //            id: "cb1ba2d7-7b89-4595-9753-d16b6e317c6b".to_string(),
@@ -2681,7 +3135,7 @@ mod tests {
             let rsp: Response = Response::decode(err_bytes.as_slice()).unwrap();
             assert!(!rsp.is_success);
             assert_eq!(rsp.error, "test_unlock_then_crash");
-            let map = KEYSTORE_MAP.read();
+            let map = TOKEN_CORE.keystores().read();
             let keystore: &Keystore = map.get(&wallet.id).unwrap();
             assert!(keystore.is_locked())
         });
@@ -0,0 +1,224 @@
+use tcx_crypto::KdfParams;
+
+use crate::api;
+use crate::error_handling::Result;
+
+/// The password and kdf rules of a `TokenCore`, checked by every path that encrypts a new keystore
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecurityPolicy {
+    pub min_password_length: u32,
+    /// bits estimated by `password_entropy`, 0 disables the check
+    pub min_password_entropy: f64,
+    /// `kdf` names of the keystore crypto, e.g. pbkdf2 or scrypt
+    pub allowed_kdfs: Vec<String>,
+    pub min_pbkdf2_rounds: u32,
+    pub min_scrypt_n: u32,
+    /// allow the debug mode to create keystores below the minimum kdf cost
+    pub allow_debug_weak_kdf: bool,
+}
+
+impl Default for SecurityPolicy {
+    fn default() -> Self {
+        SecurityPolicy {
+            min_password_length: 8,
+            min_password_entropy: 0.0,
+            allowed_kdfs: vec!["pbkdf2".to_string(), "scrypt".to_string()],
+            min_pbkdf2_rounds: 262144,
+            min_scrypt_n: 262144,
+            allow_debug_weak_kdf: true,
+        }
+    }
+}
+
+/// A field left 0 or empty in the proto policy takes its default, so a partially filled policy
+/// only changes the rules it sets
+impl From<api::SecurityPolicy> for SecurityPolicy {
+    fn from(policy: api::SecurityPolicy) -> Self {
+        let default = SecurityPolicy::default();
+        let or_default = |value: u32, default: u32| if value == 0 { default } else { value };
+        let allowed_kdfs = if policy.allowed_kdfs.is_empty() {
+            default.allowed_kdfs
+        } else {
+            policy
+                .allowed_kdfs
+                .iter()
+                .map(|kdf| kdf.to_lowercase())
+                .collect()
+        };
+        SecurityPolicy {
+            min_password_length: or_default(
+                policy.min_password_length,
+                default.min_password_length,
+            ),
+            // 0 is the default, it disables the check
+            min_password_entropy: policy.min_password_entropy,
+            allowed_kdfs,
+            min_pbkdf2_rounds: or_default(policy.min_pbkdf2_rounds, default.min_pbkdf2_rounds),
+            min_scrypt_n: or_default(policy.min_scrypt_n, default.min_scrypt_n),
+            allow_debug_weak_kdf: policy.allow_debug_weak_kdf,
+        }
+    }
+}
+
+impl SecurityPolicy {
+    pub fn check_password(&self, password: &str) -> Result<()> {
+        if (password.chars().count() as u32) < self.min_password_length {
+            return Err(format_err!("password_too_short"));
+        }
+        if password_entropy(password) < self.min_password_entropy {
+            return Err(format_err!("password_too_weak"));
+        }
+        Ok(())
+    }
+
+    /// `is_debug` is the debug mode of the instance, it only lowers the minimum cost when
+    /// `allow_debug_weak_kdf` is set
+    pub fn check_kdf<T: KdfParams>(&self, params: &T, is_debug: bool) -> Result<()> {
+        let kdf = T::kdf_key();
+        if !self.allowed_kdfs.contains(&kdf) {
+            return Err(format_err!("kdf_not_allowed"));
+        }
+        if is_debug && self.allow_debug_weak_kdf {
+            return Ok(());
+        }
+        let min_cost = match kdf.as_str() {
+            "pbkdf2" => self.min_pbkdf2_rounds,
+            "scrypt" => self.min_scrypt_n,
+            _ => 0,
+        };
+        if params.cost() < min_cost {
+            return Err(format_err!("kdf_params_too_weak"));
+        }
+        Ok(())
+    }
+}
+
+/// Estimate the entropy in bits as the length times log2 of the size of the character classes
+/// used, a lower bound attackers reach by brute force rather than a measure of real strength
+pub fn password_entropy(password: &str) -> f64 {
+    let mut pool = 0u32;
+    if password.chars().any(|c| c.is_ascii_lowercase()) {
+        pool += 26;
+    }
+    if password.chars().any(|c| c.is_ascii_uppercase()) {
+        pool += 26;
+    }
+    if password.chars().any(|c| c.is_ascii_digit()) {
+        pool += 10;
+    }
+    if password
+        .chars()
+        .any(|c| c.is_ascii_punctuation() || c == ' ')
+    {
+        pool += 33;
+    }
+    if password.chars().any(|c| !c.is_ascii()) {
+        pool += 100;
+    }
+    if pool == 0 {
+        return 0.0;
+    }
+    password.chars().count() as f64 * (pool as f64).log2()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{password_entropy, SecurityPolicy};
+    use crate::api;
+    use tcx_constants::TEST_PASSWORD;
+    use tcx_crypto::{Pbkdf2Params, SCryptParams};
+
+    #[test]
+    fn test_check_password() {
+        let policy = SecurityPolicy::default();
+        assert!(policy.check_password(TEST_PASSWORD).is_ok());
+        assert_eq!(
+            format!("{}", policy.check_password("").err().unwrap()),
+            "password_too_short"
+        );
+
+        let policy = SecurityPolicy {
+            min_password_entropy: 60.0,
+            ..SecurityPolicy::default()
+        };
+        assert!(policy.check_password(TEST_PASSWORD).is_ok());
+        assert_eq!(
+            format!("{}", policy.check_password("aaaaaaaaaa").err().unwrap()),
+            "password_too_weak"
+        );
+
+        assert_eq!(password_entropy(""), 0.0);
+        assert!(password_entropy("abcd1234") > password_entropy("abcdefgh"));
+    }
+
+    #[test]
+    fn test_check_kdf() {
+        let policy = SecurityPolicy::default();
+        assert!(policy
+            .check_kdf(&Pbkdf2Params::with_rounds(262144), false)
+            .is_ok());
+        assert_eq!(
+            format!(
+                "{}",
+                policy
+                    .check_kdf(&Pbkdf2Params::with_rounds(1024), false)
+                    .err()
+                    .unwrap()
+            ),
+            "kdf_params_too_weak"
+        );
+        assert!(policy
+            .check_kdf(&Pbkdf2Params::with_rounds(1024), true)
+            .is_ok());
+
+        let policy = SecurityPolicy::from(api::SecurityPolicy {
+            min_password_length: 8,
+            min_password_entropy: 0.0,
+            allowed_kdfs: vec!["SCRYPT".to_string()],
+            min_pbkdf2_rounds: 262144,
+            min_scrypt_n: 262144,
+            allow_debug_weak_kdf: false,
+        });
+        assert!(policy.check_kdf(&SCryptParams::default(), false).is_ok());
+        assert_eq!(
+            format!(
+                "{}",
+                policy
+                    .check_kdf(&Pbkdf2Params::with_rounds(262144), true)
+                    .err()
+                    .unwrap()
+            ),
+            "kdf_not_allowed"
+        );
+    }
+
+    #[test]
+    fn test_partial_policy_falls_back_to_default() {
+        let policy = SecurityPolicy::from(api::SecurityPolicy {
+            min_password_length: 12,
+            ..api::SecurityPolicy::default()
+        });
+        assert_eq!(
+            policy,
+            SecurityPolicy {
+                min_password_length: 12,
+                allow_debug_weak_kdf: false,
+                ..SecurityPolicy::default()
+            }
+        );
+        assert_eq!(
+            format!("{}", policy.check_password("short pass").err().unwrap()),
+            "password_too_short"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                policy
+                    .check_kdf(&Pbkdf2Params::with_rounds(1024), false)
+                    .err()
+                    .unwrap()
+            ),
+            "kdf_params_too_weak"
+        );
+    }
+}
@@ -0,0 +1,1904 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use base58::ToBase58;
+use bytes::BytesMut;
+use parking_lot::RwLock;
+use prost::Message;
+use serde_json::Value;
+
+use tcx_chain::tcx_ensure;
+use tcx_chain::{
+    check_mnemonic_language, key_hash_from_mnemonic_with_passphrase, key_hash_from_private_key,
+    Keystore, KeystoreGuard,
+};
+use tcx_chain::{Account, HdKeystore, Metadata, PrivateKeystore, Slip39Group, Source, V3Keystore};
+use tcx_chain::{MessageSigner, TransactionSigner};
+use tcx_constants::coin_info::coin_info_from_param;
+use tcx_constants::{CoinInfo, CurveType};
+use tcx_crypto::aes::cbc::encrypt_pkcs7;
+use tcx_crypto::hash::dsha256;
+use tcx_crypto::{Pbkdf2Params, SCryptParams};
+use tcx_dispatch::{address_from_public_key, TxCodec, TxMessage};
+use tcx_filecoin::KeyInfo;
+use tcx_primitive::{
+    generate_mnemonic_with_word_count, get_account_path, language_from_code, mnemonic_candidates,
+    mnemonic_from_entropy, normalize_mnemonic, private_key_without_version, FromHex,
+    TypedPrivateKey, TypedPublicKey,
+};
+use tcx_primitive::{Bip32DeterministicPublicKey, Ss58Codec};
+use tcx_substrate::{
+    decode_substrate_keystore, encode_substrate_keystore, ExportSubstrateKeystoreResult,
+    SubstrateKeystore, SubstrateKeystoreParam,
+};
+use tcx_tezos::{build_tezos_base58_private_key, pars_tezos_private_key};
+use zeroize::Zeroizing;
+
+use crate::api::batch_sign_result::SignResult;
+use crate::api::keystore_common_derive_param::Derivation;
+use crate::api::sign_param::Key;
+use crate::api::{
+    batch_sign_param, AccountResponse, AccountsResponse, BackupWalletsParam, BackupWalletsResult,
+    BatchSignParam, BatchSignResult, DerivedKeyResult, ExportPrivateKeyParam, HdStoreCreateParam,
+    HdStoreImportParam, KeyType, KeystoreCommonDeriveParam, KeystoreCommonExistsParam,
+    KeystoreCommonExistsResult, KeystoreCommonExportResult, MnemonicCandidatesParam,
+    MnemonicCandidatesResult, MnemonicWordsVerifyParam, PrivateKeyStoreImportParam, PublicKeyParam,
+    PublicKeyResult, RestoreWalletsParam, RestoreWalletsResult, SignParam, Slip39GroupShares,
+    Slip39ImportParam, Slip39SharesExportParam, Slip39SharesExportResult, SubAddress,
+    SubAddressesDeriveParam, SubAddressesDeriveResult, V3KeystoreExportParam,
+    V3KeystoreExportResult, V3KeystoreImportParam, VerifySignatureParam, WalletResult,
+};
+use crate::backup::{decrypt_bundle, encrypt_bundle};
+use crate::error_handling::Result;
+use crate::filemanager::{delete_keystore_file, flush_keystore};
+use crate::security_policy::SecurityPolicy;
+
+/// The settings of a `TokenCore` instance, `init_token_core_x` builds one from its param
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenCoreConfig {
+    /// hex encoded aes-128 key used to encrypt the extended public keys returned to callers
+    pub xpub_common_key: String,
+    pub xpub_common_iv: String,
+    /// print every message returned by the api
+    pub is_debug: bool,
+    /// pbkdf2 rounds of the keystores created by this instance
+    pub kdf_rounds: u32,
+    pub security_policy: SecurityPolicy,
+}
+
+impl Default for TokenCoreConfig {
+    fn default() -> Self {
+        TokenCoreConfig {
+            xpub_common_key: "B888D25EC8C12BD5043777B1AC49F872".to_string(),
+            xpub_common_iv: "9C0C30889CBCC5E01AB5B2BB88715799".to_string(),
+            is_debug: false,
+            kdf_rounds: 262144,
+            security_policy: SecurityPolicy::default(),
+        }
+    }
+}
+
+/// The wallet manager behind `call_tcx_api`.
+///
+/// A `TokenCore` owns every keystore found in its wallet directory together with its
+/// `TokenCoreConfig`. Instances share no keystores or settings, so one process can hold as
+/// many as it needs, each bound to its own directory. The registered coins are the exception,
+/// like the built in ones they are process wide. Rust callers use the typed methods below
+/// instead of going through the hex encoded protobuf FFI.
+///
+/// ```no_run
+/// use tcx::api::HdStoreCreateParam;
+/// use tcx::TokenCore;
+///
+/// let token_core = TokenCore::open("/tmp/imtoken/wallets").unwrap();
+/// let wallet = token_core
+///     .create_wallet(&HdStoreCreateParam {
+///         password: "Insecure Password".to_string(),
+///         password_hint: "".to_string(),
+///         name: "main".to_string(),
+///         passphrase: "".to_string(),
+///         language: "".to_string(),
+///         word_count: 0,
+///         entropy: "".to_string(),
+///     })
+///     .unwrap();
+/// ```
+pub struct TokenCore {
+    wallet_dir: RwLock<String>,
+    config: RwLock<TokenCoreConfig>,
+    keystores: RwLock<HashMap<String, Keystore>>,
+}
+
+impl TokenCore {
+    /// Create an instance bound to `wallet_dir` without loading any keystore
+    pub fn new(wallet_dir: &str) -> Self {
+        Self::with_config(wallet_dir, TokenCoreConfig::default())
+    }
+
+    pub fn with_config(wallet_dir: &str, config: TokenCoreConfig) -> Self {
+        TokenCore {
+            wallet_dir: RwLock::new(wallet_dir.to_string()),
+            config: RwLock::new(config),
+            keystores: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Create an instance bound to `wallet_dir` and load all keystores in it
+    pub fn open(wallet_dir: &str) -> Result<Self> {
+        let token_core = TokenCore::new(wallet_dir);
+        token_core.scan_keystores()?;
+        Ok(token_core)
+    }
+
+    pub fn wallet_dir(&self) -> String {
+        self.wallet_dir.read().to_string()
+    }
+
+    /// Point this instance to another directory, the keystores already loaded are kept
+    /// until the next `scan_keystores`
+    pub fn set_wallet_dir(&self, wallet_dir: &str) {
+        *self.wallet_dir.write() = wallet_dir.to_string();
+    }
+
+    pub fn config(&self) -> TokenCoreConfig {
+        self.config.read().clone()
+    }
+
+    pub fn set_config(&self, config: TokenCoreConfig) {
+        *self.config.write() = config;
+    }
+
+    pub fn is_debug(&self) -> bool {
+        self.config.read().is_debug
+    }
+
+    /// Encode a message returned by the api, the message is printed in debug mode
+    pub fn encode_message(&self, msg: impl Message) -> Result<Vec<u8>> {
+        if self.is_debug() {
+            println!("{:#?}", msg);
+        }
+        let mut buf = BytesMut::with_capacity(msg.encoded_len());
+        msg.encode(&mut buf)?;
+        Ok(buf.to_vec())
+    }
+
+    /// The kdf params of a new keystore encrypted by `password`, the password and the params
+    /// are checked against the security policy
+    fn kdf_params(&self, password: &str) -> Result<Pbkdf2Params> {
+        self.config
+            .read()
+            .security_policy
+            .check_password(password)?;
+        self.pbkdf2_params()
+    }
+
+    fn pbkdf2_params(&self) -> Result<Pbkdf2Params> {
+        let config = self.config.read();
+        let kdf_params = Pbkdf2Params::with_rounds(config.kdf_rounds);
+        config
+            .security_policy
+            .check_kdf(&kdf_params, config.is_debug)?;
+        Ok(kdf_params)
+    }
+
+    /// The scrypt params of the V3 keystores, the cost follows the security policy like the
+    /// pbkdf2 rounds
+    fn scrypt_params(&self) -> Result<SCryptParams> {
+        let config = self.config.read();
+        let policy = &config.security_policy;
+        let n = if config.is_debug && policy.allow_debug_weak_kdf {
+            1024
+        } else {
+            policy.min_scrypt_n.max(262144)
+        };
+        let kdf_params = SCryptParams::with_n(n);
+        policy.check_kdf(&kdf_params, config.is_debug)?;
+        Ok(kdf_params)
+    }
+
+    /// Reload all keystores from the wallet directory
+    pub fn scan_keystores(&self) -> Result<()> {
+        let mut keystores: HashMap<String, Keystore> = HashMap::new();
+        let file_dir = self.wallet_dir.read();
+        let p = Path::new(file_dir.as_str());
+        let walk_dir = std::fs::read_dir(p).expect("read dir");
+        for entry in walk_dir {
+            let entry = entry.expect("DirEntry");
+            let fp = entry.path();
+            if !fp
+                .file_name()
+                .expect("file_name")
+                .to_str()
+                .expect("file_name str")
+                .ends_with(".json")
+            {
+                continue;
+            }
+
+            let mut f = fs::File::open(fp).expect("open file");
+            let mut contents = String::new();
+
+            let _ = f.read_to_string(&mut contents);
+            let v: Value = serde_json::from_str(&contents).expect("read json from content");
+
+            let version = v["version"].as_i64().expect("version");
+            if version == i64::from(HdKeystore::VERSION)
+                || version == i64::from(PrivateKeystore::VERSION)
+            {
+                let keystore = Keystore::from_json(&contents)?;
+                keystores.insert(keystore.id(), keystore);
+            }
+        }
+        *self.keystores.write() = keystores;
+        Ok(())
+    }
+
+    pub(crate) fn keystores(&self) -> &RwLock<HashMap<String, Keystore>> {
+        &self.keystores
+    }
+
+    /// Lock every loaded keystore, cached keys are dropped
+    pub fn lock_all(&self) {
+        let mut map = self.keystores.write();
+        for ks in map.values_mut() {
+            ks.lock();
+        }
+    }
+
+    pub fn with_keystore<T, F>(&self, id: &str, f: F) -> Result<T>
+    where
+        F: FnOnce(&Keystore) -> Result<T>,
+    {
+        let map = self.keystores.read();
+        match map.get(id) {
+            Some(keystore) => f(keystore),
+            _ => Err(format_err!("{}", "wallet_not_found")),
+        }
+    }
+
+    pub fn with_keystore_mut<T, F>(&self, id: &str, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Keystore) -> Result<T>,
+    {
+        let mut map = self.keystores.write();
+        match map.get_mut(id) {
+            Some(keystore) => f(keystore),
+            _ => Err(format_err!("{}", "wallet_not_found")),
+        }
+    }
+
+    fn cache_keystore(&self, keystore: Keystore) {
+        self.keystores
+            .write()
+            .insert(keystore.id().to_owned(), keystore);
+    }
+
+    fn flush_keystore(&self, keystore: &Keystore) -> Result<()> {
+        flush_keystore(&self.wallet_dir.read(), keystore)
+    }
+
+    fn enc_xpub(&self, xpub: &str, network: &str) -> Result<String> {
+        let xpk = Bip32DeterministicPublicKey::from_hex(xpub)?;
+        let ext_pub_key: String;
+        if network == "MAINNET" {
+            ext_pub_key = xpk.to_ss58check_with_version(&[0x04, 0x88, 0xB2, 0x1E]);
+        } else {
+            ext_pub_key = xpk.to_ss58check_with_version(&[0x04, 0x35, 0x87, 0xCF]);
+        }
+
+        let config = self.config.read();
+        let key_bytes = hex::decode(&config.xpub_common_key)?;
+        let iv_bytes = hex::decode(&config.xpub_common_iv)?;
+        let encrypted = encrypt_pkcs7(&ext_pub_key.as_bytes(), &key_bytes, &iv_bytes)?;
+        Ok(base64::encode(&encrypted))
+    }
+
+    fn find_id_by_key_hash(&self, key_hash: &str) -> Option<String> {
+        self.keystores
+            .read()
+            .values()
+            .find(|keystore| keystore.key_hash() == key_hash)
+            .map(|keystore| keystore.id())
+    }
+
+    fn save_new_keystore(
+        &self,
+        mut keystore: Keystore,
+        exist_id: Option<String>,
+    ) -> Result<WalletResult> {
+        if let Some(exist_id) = exist_id {
+            keystore.set_id(&exist_id);
+        }
+
+        self.flush_keystore(&keystore)?;
+
+        let wallet = wallet_result(&keystore);
+        self.cache_keystore(keystore);
+        Ok(wallet)
+    }
+
+    /// All the wallets of the wallet dir with their accounts, the oldest first
+    pub fn list_wallets(&self) -> Result<Vec<WalletResult>> {
+        let ids: Vec<String> = self.keystores.read().keys().cloned().collect();
+
+        let mut wallets = vec![];
+        for id in ids.iter() {
+            let mut wallet = self.with_keystore(id, |keystore| Ok(wallet_result(keystore)))?;
+            wallet.accounts = self.accounts(id)?.accounts;
+            wallets.push(wallet);
+        }
+        wallets.sort_by_key(|wallet| wallet.created_at);
+        Ok(wallets)
+    }
+
+    /// Export all the keystores of the wallet dir into one bundle encrypted by `param.password`
+    pub fn backup_wallets(&self, param: &BackupWalletsParam) -> Result<BackupWalletsResult> {
+        let kdf_params = self.kdf_params(&param.password)?;
+        let map = self.keystores.read();
+        let mut keystores: Vec<&Keystore> = map.values().collect();
+        keystores.sort_by_key(|keystore| keystore.meta().timestamp);
+
+        Ok(BackupWalletsResult {
+            bundle: encrypt_bundle(&keystores, &param.password, kdf_params)?,
+            count: keystores.len() as u32,
+        })
+    }
+
+    /// Import the keystores of a bundle, the first one of a key wins when the bundle holds
+    /// several. An existing wallet of the same key is kept and returned in `conflicts`
+    /// unless `param.overwrite` is set
+    pub fn restore_wallets(&self, param: &RestoreWalletsParam) -> Result<RestoreWalletsResult> {
+        let keystores = decrypt_bundle(&param.bundle, &param.password)?;
+
+        let mut key_hashes = HashSet::new();
+        let mut restored = vec![];
+        let mut conflicts = vec![];
+        for keystore in keystores {
+            if !key_hashes.insert(keystore.key_hash()) {
+                continue;
+            }
+
+            match self.find_id_by_key_hash(&keystore.key_hash()) {
+                Some(exist_id) if !param.overwrite => {
+                    conflicts.push(self.with_keystore(&exist_id, |ks| Ok(wallet_result(ks)))?);
+                }
+                exist_id => restored.push(self.save_new_keystore(keystore, exist_id)?),
+            }
+        }
+        Ok(RestoreWalletsResult {
+            restored,
+            conflicts,
+        })
+    }
+
+    pub fn create_wallet(&self, param: &HdStoreCreateParam) -> Result<WalletResult> {
+        let kdf_params = self.kdf_params(&param.password)?;
+        let mut meta = Metadata::default();
+        meta.name = param.name.to_owned();
+        meta.password_hint = param.password_hint.to_owned();
+        meta.source = Source::Mnemonic;
+
+        let language = if param.language.is_empty() {
+            language_from_code("en")?
+        } else {
+            language_from_code(&param.language)?
+        };
+        let word_count = if param.word_count == 0 {
+            12
+        } else {
+            param.word_count as usize
+        };
+        let mnemonic = if param.entropy.is_empty() {
+            Zeroizing::new(generate_mnemonic_with_word_count(language, word_count)?)
+        } else {
+            let entropy = Zeroizing::new(
+                hex::decode(&param.entropy).map_err(|_| format_err!("entropy_invalid"))?,
+            );
+            let mnemonic = mnemonic_from_entropy(&entropy, language)?;
+            tcx_ensure!(
+                param.word_count == 0 || mnemonic.split(' ').count() == word_count,
+                format_err!("mnemonic_length_invalid")
+            );
+            mnemonic
+        };
+        let ks = HdKeystore::from_mnemonic_with_passphrase(
+            &mnemonic,
+            &param.passphrase,
+            &param.password,
+            meta,
+            kdf_params,
+        )?;
+        self.save_new_keystore(Keystore::Hd(ks), None)
+    }
+
+    pub fn import_mnemonic(&self, param: &HdStoreImportParam) -> Result<WalletResult> {
+        let kdf_params = self.kdf_params(&param.password)?;
+        if !param.language.is_empty() {
+            check_mnemonic_language(&param.mnemonic, &param.language)?;
+        }
+        let key_hash = key_hash_from_mnemonic_with_passphrase(&param.mnemonic, &param.passphrase)?;
+        let founded_id = self.find_id_by_key_hash(&key_hash);
+
+        if founded_id.is_some() && !param.overwrite {
+            return Err(format_err!("{}", "address_already_exist"));
+        }
+
+        let mut meta = Metadata::default();
+        meta.name = param.name.to_owned();
+        meta.password_hint = param.password_hint.to_owned();
+        meta.source = Source::Mnemonic;
+
+        let ks = HdKeystore::from_mnemonic_with_passphrase(
+            &param.mnemonic,
+            &param.passphrase,
+            &param.password,
+            meta,
+            kdf_params,
+        )?;
+        self.save_new_keystore(Keystore::Hd(ks), founded_id)
+    }
+
+    pub fn import_slip39_shares(&self, param: &Slip39ImportParam) -> Result<WalletResult> {
+        let kdf_params = self.kdf_params(&param.password)?;
+        let mut meta = Metadata::default();
+        meta.name = param.name.to_owned();
+        meta.password_hint = param.password_hint.to_owned();
+        meta.source = Source::Mnemonic;
+
+        let keystore = Keystore::Hd(HdKeystore::from_slip39_shares_with_kdf_params(
+            &param.shares,
+            &param.passphrase,
+            &param.password,
+            meta,
+            kdf_params,
+        )?);
+        let founded_id = self.find_id_by_key_hash(&keystore.key_hash());
+        if founded_id.is_some() && !param.overwrite {
+            return Err(format_err!("{}", "address_already_exist"));
+        }
+        self.save_new_keystore(keystore, founded_id)
+    }
+
+    pub fn import_private_key(&self, param: &PrivateKeyStoreImportParam) -> Result<WalletResult> {
+        let kdf_params = self.kdf_params(&param.password)?;
+        self.import_private_key_with_kdf_params(param, kdf_params)
+    }
+
+    /// The password policy is left to the callers, a password the user picked is checked by
+    /// `import_private_key` but the one of an imported keystore is only the user's old choice
+    fn import_private_key_with_kdf_params(
+        &self,
+        param: &PrivateKeyStoreImportParam,
+        kdf_params: Pbkdf2Params,
+    ) -> Result<WalletResult> {
+        let key_hash = if param.encoding.eq("TEZOS") {
+            key_hash_from_tezos_format_pk(&param.private_key)?
+        } else {
+            key_hash_from_any_format_pk(&param.private_key)?
+        };
+        let founded_id = self.find_id_by_key_hash(&key_hash);
+
+        if founded_id.is_some() && !param.overwrite {
+            return Err(format_err!("{}", "address_already_exist"));
+        }
+
+        let pk_bytes = if param.encoding.eq("TEZOS") {
+            pars_tezos_private_key(&param.private_key)?
+        } else {
+            key_data_from_any_format_pk(&param.private_key)?
+        };
+        let private_key = hex::encode(pk_bytes);
+        let meta = Metadata {
+            name: param.name.to_owned(),
+            password_hint: param.password_hint.to_owned(),
+            source: Source::Private,
+            ..Metadata::default()
+        };
+        let pk_store = PrivateKeystore::from_private_key_with_kdf_params(
+            &private_key,
+            &param.password,
+            meta,
+            kdf_params,
+        );
+        self.save_new_keystore(Keystore::PrivateKey(pk_store), founded_id)
+    }
+
+    pub fn derive_accounts(&self, param: &KeystoreCommonDeriveParam) -> Result<AccountsResponse> {
+        self.with_keystore_mut(&param.id, |keystore| {
+            let mut guard = KeystoreGuard::unlock_by_password(keystore, &param.password)?;
+
+            let mut account_responses: Vec<AccountResponse> = vec![];
+
+            for derivation in param.derivations.iter() {
+                let account = derive_account(guard.keystore_mut(), derivation)?;
+                let enc_xpub =
+                    if account.ext_pub_key.is_empty() || account.curve != CurveType::SECP256k1 {
+                        Ok("".to_string())
+                    } else {
+                        self.enc_xpub(&account.ext_pub_key.to_string(), &account.network)
+                    }?;
+                let account_rsp = AccountResponse {
+                    chain_type: derivation.chain_type.to_owned(),
+                    address: account.address.to_owned(),
+                    path: account.derivation_path.to_owned(),
+                    extended_xpub_key: enc_xpub,
+                };
+                account_responses.push(account_rsp);
+            }
+
+            self.flush_keystore(guard.keystore())?;
+            Ok(AccountsResponse {
+                accounts: account_responses,
+            })
+        })
+    }
+
+    pub fn accounts(&self, id: &str) -> Result<AccountsResponse> {
+        self.with_keystore(id, |keystore| {
+            let mut accounts: Vec<AccountResponse> = vec![];
+            for account in keystore.accounts() {
+                let enc_xpub =
+                    if account.ext_pub_key.is_empty() || account.curve != CurveType::SECP256k1 {
+                        "".to_string()
+                    } else {
+                        self.enc_xpub(&account.ext_pub_key, &account.network)?
+                    };
+                let acc_rsp = AccountResponse {
+                    chain_type: account.coin.to_owned(),
+                    address: account.address.to_owned(),
+                    path: account.derivation_path.to_owned(),
+                    extended_xpub_key: enc_xpub.to_owned(),
+                };
+                accounts.push(acc_rsp);
+            }
+
+            Ok(AccountsResponse { accounts })
+        })
+    }
+
+    pub fn export_mnemonic(&self, id: &str, password: &str) -> Result<KeystoreCommonExportResult> {
+        self.with_keystore_mut(id, |keystore| {
+            let guard = KeystoreGuard::unlock_by_password(keystore, password)?;
+
+            tcx_ensure!(
+                guard.keystore().determinable(),
+                format_err!("{}", "private_keystore_cannot_export_mnemonic")
+            );
+
+            Ok(KeystoreCommonExportResult {
+                id: guard.keystore().id(),
+                r#type: KeyType::Mnemonic as i32,
+                value: guard.keystore().export()?,
+            })
+        })
+    }
+
+    pub fn export_slip39_shares(
+        &self,
+        param: &Slip39SharesExportParam,
+    ) -> Result<Slip39SharesExportResult> {
+        // out of range values are rejected as invalid thresholds rather than truncated
+        let to_u8 = |value: u32| u8::try_from(value).unwrap_or(u8::MAX);
+        let groups: Vec<Slip39Group> = param
+            .groups
+            .iter()
+            .map(|group| Slip39Group {
+                member_threshold: to_u8(group.member_threshold),
+                member_count: to_u8(group.member_count),
+            })
+            .collect();
+
+        self.with_keystore_mut(&param.id, |keystore| {
+            let guard = KeystoreGuard::unlock_by_password(keystore, &param.password)?;
+            let shares = guard.keystore().slip39_shares(
+                to_u8(param.group_threshold),
+                &groups,
+                &param.passphrase,
+            )?;
+
+            Ok(Slip39SharesExportResult {
+                groups: shares
+                    .into_iter()
+                    .map(|shares| Slip39GroupShares { shares })
+                    .collect(),
+            })
+        })
+    }
+
+    pub fn verify_mnemonic_words(&self, param: &MnemonicWordsVerifyParam) -> Result<bool> {
+        let words: Vec<(usize, &str)> = param
+            .words
+            .iter()
+            .map(|word| (word.index as usize, word.word.as_str()))
+            .collect();
+        self.with_keystore_mut(&param.id, |keystore| {
+            let guard = KeystoreGuard::unlock_by_password(keystore, &param.password)?;
+            guard.keystore().verify_mnemonic_words(&words)
+        })
+    }
+
+    pub fn mnemonic_candidates(
+        &self,
+        param: &MnemonicCandidatesParam,
+    ) -> Result<MnemonicCandidatesResult> {
+        let language = if param.language.is_empty() {
+            None
+        } else {
+            Some(language_from_code(&param.language)?)
+        };
+        let candidates = mnemonic_candidates(&normalize_mnemonic(&param.mnemonic), language)?;
+        Ok(MnemonicCandidatesResult { candidates })
+    }
+
+    pub fn derive_sub_addresses(
+        &self,
+        param: &SubAddressesDeriveParam,
+    ) -> Result<SubAddressesDeriveResult> {
+        self.with_keystore(&param.id, |keystore| {
+            let account = keystore
+                .account(&param.chain_type, &param.address)
+                .ok_or_else(|| format_err!("account_not_found"))?;
+            let coin_info = CoinInfo {
+                coin: account.coin.to_string(),
+                derivation_path: account.derivation_path.to_string(),
+                curve: account.curve,
+                network: account.network.to_string(),
+                seg_wit: account.seg_wit.to_string(),
+            };
+
+            let mut addresses = vec![];
+            for path in param.relative_paths.iter() {
+                let public_key =
+                    keystore.derive_public_key(&param.chain_type, &param.address, path)?;
+                addresses.push(SubAddress {
+                    path: path.to_string(),
+                    address: address_from_public_key(&param.chain_type, &public_key, &coin_info)?,
+                    public_key: hex::encode(public_key.to_bytes()),
+                });
+            }
+            Ok(SubAddressesDeriveResult { addresses })
+        })
+    }
+
+    pub fn export_private_key(
+        &self,
+        param: &ExportPrivateKeyParam,
+    ) -> Result<KeystoreCommonExportResult> {
+        self.with_keystore_mut(&param.id, |keystore| {
+            let mut guard = KeystoreGuard::unlock_by_password(keystore, &param.password)?;
+
+            let pk_hex = if param.path.is_empty() {
+                guard.keystore_mut().export_private_key(
+                    &param.chain_type,
+                    &param.main_address,
+                    None,
+                )?
+            } else {
+                // get the relative path
+                let mut relative_path: &str = param.path.as_str();
+                if param.path.starts_with("m") {
+                    let acc_path = get_account_path(relative_path)?;
+                    relative_path = &relative_path[acc_path.len()..];
+                }
+
+                if relative_path.starts_with("/") {
+                    relative_path = &relative_path[1..];
+                }
+
+                guard.keystore_mut().export_private_key(
+                    &param.chain_type,
+                    &param.main_address,
+                    Some(relative_path),
+                )?
+            };
+
+            let value = if ["TRON", "POLKADOT", "KUSAMA"].contains(&param.chain_type.as_str()) {
+                Ok(pk_hex.to_string())
+            } else if "FILECOIN".contains(&param.chain_type.as_str()) {
+                if let Some(account) = guard
+                    .keystore_mut()
+                    .account("FILECOIN", &param.main_address)
+                {
+                    Ok(hex::encode(
+                        KeyInfo::from_private_key(account.curve, &hex::decode(pk_hex)?)?
+                            .to_json()?,
+                    ))
+                } else {
+                    Err(format_err!("{}", "account_not_found"))
+                }
+            } else if "TEZOS".contains(&param.chain_type.as_str()) {
+                Ok(build_tezos_base58_private_key(pk_hex.as_str())?)
+            } else {
+                // private_key prefix is only about chain type and network
+                let coin_info = coin_info_from_param(&param.chain_type, &param.network, "", "")?;
+
+                let bytes = hex::decode(pk_hex.to_string())?;
+                let typed_pk = TypedPrivateKey::from_slice(CurveType::SECP256k1, &bytes)?;
+                typed_pk.fmt(&coin_info)
+            }?;
+
+            Ok(KeystoreCommonExportResult {
+                id: guard.keystore().id(),
+                r#type: KeyType::PrivateKey as i32,
+                value,
+            })
+        })
+    }
+
+    pub fn verify_password(&self, id: &str, password: &str) -> Result<()> {
+        self.with_keystore(id, |keystore| {
+            tcx_ensure!(
+                keystore.verify_password(password),
+                format_err!("{}", "password_incorrect")
+            );
+            Ok(())
+        })
+    }
+
+    pub fn delete_wallet(&self, id: &str, password: &str) -> Result<()> {
+        self.verify_password(id, password)?;
+        delete_keystore_file(&self.wallet_dir.read(), id)?;
+        self.keystores.write().remove(id);
+        Ok(())
+    }
+
+    pub fn exists(&self, param: &KeystoreCommonExistsParam) -> Result<KeystoreCommonExistsResult> {
+        let key_hash = if param.r#type == KeyType::Mnemonic as i32 {
+            key_hash_from_mnemonic_with_passphrase(&param.value, &param.passphrase)?
+        } else if param.encoding.eq("TEZOS") {
+            key_hash_from_tezos_format_pk(&param.value)?
+        } else {
+            key_hash_from_any_format_pk(&param.value)?
+        };
+
+        Ok(match self.find_id_by_key_hash(&key_hash) {
+            Some(id) => KeystoreCommonExistsResult {
+                is_exists: true,
+                id,
+            },
+            None => KeystoreCommonExistsResult {
+                is_exists: false,
+                id: "".to_owned(),
+            },
+        })
+    }
+
+    /// Sign a transaction whose input is packed in `param.input`, the returned bytes
+    /// are the encoded output message of the chain
+    pub fn sign_tx(&self, param: &SignParam) -> Result<Vec<u8>> {
+        self.with_keystore_mut(&param.id, |keystore| {
+            let mut guard = unlock_keystore(keystore, param.key.as_ref())?;
+            sign_tx_by_keystore(self, param, guard.keystore_mut())
+        })
+    }
+
+    /// Sign a typed transaction, e.g. a `TronTxInput` or a `BtcForkTransaction`
+    pub fn sign_transaction<I, O>(
+        &self,
+        id: &str,
+        key: &Key,
+        chain_type: &str,
+        address: &str,
+        tx: &I,
+    ) -> Result<O>
+    where
+        Keystore: TransactionSigner<I, O>,
+    {
+        self.with_keystore_mut(id, |keystore| {
+            let mut guard = unlock_keystore(keystore, Some(key))?;
+            guard
+                .keystore_mut()
+                .sign_transaction(chain_type, address, tx)
+        })
+    }
+
+    pub fn sign_message<I, O>(
+        &self,
+        id: &str,
+        key: &Key,
+        chain_type: &str,
+        address: &str,
+        message: &I,
+    ) -> Result<O>
+    where
+        Keystore: MessageSigner<I, O>,
+    {
+        self.with_keystore_mut(id, |keystore| {
+            let mut guard = unlock_keystore(keystore, Some(key))?;
+            guard
+                .keystore_mut()
+                .sign_message(chain_type, address, message)
+        })
+    }
+
+    /// Unlock the keystore once and sign every item of `param.sign_params`, an item
+    /// failure is reported in its own `SignResult`
+    pub fn batch_sign_tx(&self, param: &BatchSignParam) -> Result<BatchSignResult> {
+        let key = match param.key.clone().expect("key") {
+            batch_sign_param::Key::Password(password) => Key::Password(password),
+            batch_sign_param::Key::DerivedKey(derived_key) => Key::DerivedKey(derived_key),
+        };
+
+        self.with_keystore_mut(&param.id, |keystore| {
+            let mut guard = unlock_keystore(keystore, Some(&key))?;
+
+            let mut results: Vec<SignResult> = vec![];
+            for sign_param in param.sign_params.iter() {
+                let result = match sign_tx_by_keystore(self, sign_param, guard.keystore_mut()) {
+                    Ok(output) => SignResult {
+                        is_success: true,
+                        error: "".to_owned(),
+                        output: Some(::prost_types::Any {
+                            type_url: "imtoken".to_string(),
+                            value: output,
+                        }),
+                    },
+                    Err(err) => SignResult {
+                        is_success: false,
+                        error: err.to_string(),
+                        output: None,
+                    },
+                };
+                results.push(result);
+            }
+
+            Ok(BatchSignResult { results })
+        })
+    }
+
+    pub fn get_public_key(&self, param: &PublicKeyParam) -> Result<PublicKeyResult> {
+        self.with_keystore(&param.id, |keystore| {
+            let edpk_prefix: Vec<u8> = vec![0x0D, 0x0F, 0x25, 0xD9];
+            match param.chain_type.to_uppercase().as_str() {
+                "TEZOS" => {
+                    let account = keystore.account(&param.chain_type, &param.address);
+                    if let Some(acc) = account {
+                        tcx_ensure!(
+                            acc.public_key.is_some(),
+                            format_err!("account_not_contains_public_key")
+                        );
+                        let pub_key = hex::decode(acc.public_key.clone().unwrap())?;
+                        let to_hash = [edpk_prefix, pub_key].concat();
+                        let hashed = dsha256(&to_hash);
+                        let hash_with_checksum = [to_hash, hashed[0..4].to_vec()].concat();
+                        let edpk = hash_with_checksum.to_base58();
+                        Ok(PublicKeyResult {
+                            id: param.id.to_string(),
+                            chain_type: param.chain_type.to_string(),
+                            address: param.address.to_string(),
+                            public_key: edpk,
+                        })
+                    } else {
+                        Err(format_err!("account_not_found"))
+                    }
+                }
+                _ => Err(format_err!("unsupported_chain")),
+            }
+        })
+    }
+
+    pub fn verify_signature(&self, param: &VerifySignatureParam) -> Result<bool> {
+        let coin_info = coin_info_from_param(
+            &param.chain_type,
+            &param.network,
+            &param.seg_wit,
+            &param.curve,
+        )?;
+        let message = hex::decode(&param.message)?;
+        let signature = hex::decode(&param.signature)?;
+
+        let public_key = if param.public_key.is_empty() {
+            tcx_ensure!(
+                !param.address.is_empty() && coin_info.curve == CurveType::SECP256k1,
+                format_err!("public_key_required")
+            );
+            TypedPublicKey::recover(coin_info.curve, &message, &signature)?
+        } else {
+            let public_key =
+                TypedPublicKey::from_slice(coin_info.curve, &hex::decode(&param.public_key)?)?;
+            if !public_key.verify(&message, &signature)? {
+                return Ok(false);
+            }
+            public_key
+        };
+
+        if param.address.is_empty() {
+            Ok(true)
+        } else {
+            Ok(
+                address_from_public_key(&param.chain_type, &public_key, &coin_info)?
+                    == param.address,
+            )
+        }
+    }
+
+    pub fn get_derived_key(&self, id: &str, password: &str) -> Result<DerivedKeyResult> {
+        self.with_keystore(id, |keystore| {
+            let dk = keystore.get_derived_key(password)?;
+
+            Ok(DerivedKeyResult {
+                id: id.to_owned(),
+                derived_key: dk,
+            })
+        })
+    }
+
+    pub fn import_substrate_keystore(
+        &self,
+        param: &SubstrateKeystoreParam,
+    ) -> Result<WalletResult> {
+        let ks: SubstrateKeystore = serde_json::from_str(&param.keystore)?;
+        let _ = ks.validate()?;
+        let pk = decode_substrate_keystore(&ks, &param.password)?;
+        let pk_import_param = PrivateKeyStoreImportParam {
+            private_key: hex::encode(pk),
+            password: param.password.to_string(),
+            name: ks.meta.name,
+            password_hint: "".to_string(),
+            overwrite: param.overwrite,
+            encoding: "".to_string(),
+        };
+        self.import_private_key_with_kdf_params(&pk_import_param, self.pbkdf2_params()?)
+    }
+
+    pub fn export_substrate_keystore(
+        &self,
+        param: &ExportPrivateKeyParam,
+    ) -> Result<ExportSubstrateKeystoreResult> {
+        let meta = self.with_keystore(&param.id, |keystore| {
+            // !!! Warning !!! HDKeystore only can export raw sr25519 key,
+            // but polkadotjs keystore needs a Ed25519 expanded secret key.
+            if keystore.determinable() {
+                return Err(format_err!("{}", "hd_wallet_cannot_export_keystore"));
+            }
+            Ok(keystore.meta().clone())
+        })?;
+
+        let export_result = self.export_private_key(param)?;
+        let pk_bytes = hex::decode(export_result.value)?;
+        let coin = coin_info_from_param(&param.chain_type, &param.network, "", "")?;
+
+        let mut substrate_ks = encode_substrate_keystore(&param.password, &pk_bytes, &coin)?;
+
+        substrate_ks.meta.name = meta.name;
+        substrate_ks.meta.when_created = meta.timestamp;
+        let keystore_str = serde_json::to_string(&substrate_ks)?;
+        Ok(ExportSubstrateKeystoreResult {
+            keystore: keystore_str,
+        })
+    }
+
+    pub fn import_v3_keystore(&self, param: &V3KeystoreImportParam) -> Result<WalletResult> {
+        let v3_keystore = V3Keystore::from_json(&param.keystore)?;
+        let decrypted = v3_keystore.decrypt(&param.password)?;
+        let private_key = if decrypted.len() == 32 {
+            hex::encode(decrypted.as_slice())
+        } else {
+            // old imToken keystores encrypt the WIF of the key
+            let wif =
+                std::str::from_utf8(&decrypted).map_err(|_| format_err!("v3_keystore_invalid"))?;
+            hex::encode(key_data_from_any_format_pk(wif)?)
+        };
+
+        let pk_import_param = PrivateKeyStoreImportParam {
+            private_key,
+            password: param.password.to_string(),
+            name: param.name.to_string(),
+            password_hint: param.password_hint.to_string(),
+            overwrite: param.overwrite,
+            encoding: "".to_string(),
+        };
+        self.import_private_key_with_kdf_params(&pk_import_param, self.pbkdf2_params()?)
+    }
+
+    pub fn export_v3_keystore(
+        &self,
+        param: &V3KeystoreExportParam,
+    ) -> Result<V3KeystoreExportResult> {
+        let private_key = self.with_keystore_mut(&param.id, |keystore| {
+            if keystore.determinable() {
+                return Err(format_err!("{}", "hd_wallet_cannot_export_keystore"));
+            }
+            tcx_ensure!(
+                keystore
+                    .accounts()
+                    .iter()
+                    .all(|account| account.curve == CurveType::SECP256k1),
+                format_err!("{}", "invalid_curve_type")
+            );
+            let guard = KeystoreGuard::unlock_by_password(keystore, &param.password)?;
+            Ok(hex::decode(guard.keystore().export()?)?)
+        })?;
+        let private_key = Zeroizing::new(private_key);
+
+        let v3_keystore = match param.kdf.to_lowercase().as_str() {
+            "" | "scrypt" => {
+                V3Keystore::from_private_key(&private_key, &param.password, self.scrypt_params()?)?
+            }
+            "pbkdf2" => {
+                V3Keystore::from_private_key(&private_key, &param.password, self.pbkdf2_params()?)?
+            }
+            _ => return Err(format_err!("kdf_not_supported")),
+        };
+        Ok(V3KeystoreExportResult {
+            keystore: v3_keystore.to_json(),
+        })
+    }
+
+    pub fn substrate_keystore_exists(
+        &self,
+        param: &SubstrateKeystoreParam,
+    ) -> Result<KeystoreCommonExistsResult> {
+        let ks: SubstrateKeystore = serde_json::from_str(&param.keystore)?;
+        let _ = ks.validate()?;
+        let pk = decode_substrate_keystore(&ks, &param.password)?;
+
+        let exists_param = KeystoreCommonExistsParam {
+            r#type: KeyType::PrivateKey as i32,
+            value: hex::encode(&pk),
+            encoding: "".to_string(),
+            passphrase: "".to_string(),
+        };
+        self.exists(&exists_param)
+    }
+}
+
+fn wallet_result(keystore: &Keystore) -> WalletResult {
+    let meta = keystore.meta();
+    let source = if keystore.determinable() {
+        "MNEMONIC"
+    } else {
+        "PRIVATE"
+    };
+    WalletResult {
+        id: keystore.id(),
+        name: meta.name.to_owned(),
+        source: source.to_owned(),
+        accounts: vec![],
+        created_at: meta.timestamp.clone(),
+    }
+}
+
+fn unlock_keystore<'a>(keystore: &'a mut Keystore, key: Option<&Key>) -> Result<KeystoreGuard<'a>> {
+    match key.expect("key") {
+        Key::Password(password) => KeystoreGuard::unlock_by_password(keystore, password),
+        Key::DerivedKey(derived_key) => KeystoreGuard::unlock_by_derived_key(keystore, derived_key),
+    }
+}
+
+fn derive_account(keystore: &mut Keystore, derivation: &Derivation) -> Result<Account> {
+    let mut coin_info = coin_info_from_param(
+        &derivation.chain_type,
+        &derivation.network,
+        &derivation.seg_wit,
+        &derivation.curve,
+    )?;
+    coin_info.derivation_path = derivation.path.to_owned();
+
+    tcx_dispatch::derive_account(keystore, &derivation.chain_type, &coin_info)
+}
+
+fn key_data_from_any_format_pk(pk: &str) -> Result<Vec<u8>> {
+    let decoded = hex::decode(pk.to_string());
+    if decoded.is_ok() {
+        let bytes = decoded.unwrap();
+        if bytes.len() <= 64 {
+            Ok(bytes)
+        } else {
+            // import filecoin
+            Ok(KeyInfo::from_lotus(&bytes)?.decode_private_key()?)
+        }
+    } else {
+        private_key_without_version(pk)
+    }
+}
+
+fn key_hash_from_any_format_pk(pk: &str) -> Result<String> {
+    let key_data = key_data_from_any_format_pk(pk)?;
+    Ok(key_hash_from_private_key(&key_data))
+}
+
+fn key_hash_from_tezos_format_pk(pk: &str) -> Result<String> {
+    let key_data = pars_tezos_private_key(pk)?;
+    Ok(key_hash_from_private_key(&key_data))
+}
+
+/// Reads the protobuf tx input of a `SignParam` and encodes the signed tx with `encode_message`
+struct ProtoCodec<'a> {
+    token_core: &'a TokenCore,
+    input: &'a [u8],
+}
+
+impl<'a> TxCodec for ProtoCodec<'a> {
+    type Output = Vec<u8>;
+
+    fn decode<I: TxMessage>(&self) -> Result<I> {
+        Ok(I::decode(self.input)?)
+    }
+
+    fn encode<O: TxMessage>(&self, output: O) -> Result<Vec<u8>> {
+        self.token_core.encode_message(output)
+    }
+}
+
+fn sign_tx_by_keystore(
+    token_core: &TokenCore,
+    param: &SignParam,
+    keystore: &mut Keystore,
+) -> Result<Vec<u8>> {
+    let codec = ProtoCodec {
+        token_core,
+        input: &param.input.as_ref().expect("tx_input").value,
+    };
+    tcx_dispatch::sign_tx(keystore, &param.chain_type, &param.address, &codec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TokenCore, TokenCoreConfig};
+    use crate::api::keystore_common_derive_param::Derivation;
+    use crate::api::sign_param::Key;
+    use crate::api::{
+        BackupWalletsParam, HdStoreCreateParam, HdStoreImportParam, KeyType,
+        KeystoreCommonDeriveParam, KeystoreCommonExistsParam, MnemonicCandidatesParam,
+        MnemonicWord, MnemonicWordsVerifyParam, PrivateKeyStoreImportParam, RestoreWalletsParam,
+        Slip39Group, Slip39ImportParam, Slip39SharesExportParam, SubAddressesDeriveParam,
+        V3KeystoreExportParam, V3KeystoreImportParam, VerifySignatureParam,
+    };
+    use std::fs;
+    use std::path::Path;
+    use tcx_chain::{Address, V3Keystore};
+    use tcx_constants::coin_info::coin_info_from_param;
+    use tcx_constants::{CurveType, TEST_MNEMONIC, TEST_PASSWORD};
+    use tcx_crypto::SCryptParams;
+    use tcx_primitive::TypedPrivateKey;
+    use tcx_tron::transaction::{TronTxInput, TronTxOutput};
+    use tcx_tron::TrxAddress;
+
+    #[test]
+    fn test_token_core_instances_are_isolated() {
+        let debug_dir = "/tmp/imtoken/token_core_debug_wallets";
+        let release_dir = "/tmp/imtoken/token_core_release_wallets";
+        for dir in [debug_dir, release_dir].iter() {
+            let p = Path::new(dir);
+            if !p.exists() {
+                fs::create_dir_all(p).expect("shoud create filedir");
+            }
+        }
+
+        let debug = TokenCore::with_config(
+            debug_dir,
+            TokenCoreConfig {
+                is_debug: true,
+                kdf_rounds: 1024,
+                ..TokenCoreConfig::default()
+            },
+        );
+        let release = TokenCore::new(release_dir);
+        assert_eq!(release.config(), TokenCoreConfig::default());
+
+        let import_param = HdStoreImportParam {
+            mnemonic: TEST_MNEMONIC.to_string(),
+            password: TEST_PASSWORD.to_string(),
+            source: "MNEMONIC".to_string(),
+            name: "test-wallet".to_string(),
+            password_hint: "imtoken".to_string(),
+            overwrite: true,
+            passphrase: "".to_string(),
+            language: "".to_string(),
+        };
+        let wallet = debug.import_mnemonic(&import_param).unwrap();
+        assert!(debug.is_debug());
+        assert!(!release.is_debug());
+        assert!(release.accounts(&wallet.id).is_err());
+
+        let json = fs::read_to_string(format!("{}/{}.json", debug_dir, wallet.id)).unwrap();
+        let v: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(v["crypto"]["kdfparams"]["c"], 1024);
+
+        debug.delete_wallet(&wallet.id, TEST_PASSWORD).unwrap();
+    }
+
+    #[test]
+    fn test_security_policy() {
+        let token_core = TokenCore::with_config(
+            "/tmp/imtoken/token_core_policy_wallets",
+            TokenCoreConfig {
+                kdf_rounds: 1024,
+                ..TokenCoreConfig::default()
+            },
+        );
+        let mut import_param = HdStoreImportParam {
+            mnemonic: TEST_MNEMONIC.to_string(),
+            password: "".to_string(),
+            source: "MNEMONIC".to_string(),
+            name: "test-wallet".to_string(),
+            password_hint: "".to_string(),
+            overwrite: true,
+            passphrase: "".to_string(),
+            language: "".to_string(),
+        };
+        let err = token_core.import_mnemonic(&import_param).err().unwrap();
+        assert_eq!(format!("{}", err), "password_too_short");
+
+        import_param.password = TEST_PASSWORD.to_string();
+        let err = token_core.import_mnemonic(&import_param).err().unwrap();
+        assert_eq!(format!("{}", err), "kdf_params_too_weak");
+    }
+
+    #[test]
+    fn test_mnemonic_language() {
+        let wallet_dir = "/tmp/imtoken/token_core_language_wallets";
+        let _ = fs::remove_dir_all(wallet_dir);
+        fs::create_dir_all(wallet_dir).expect("shoud create filedir");
+        let token_core = TokenCore::with_config(
+            wallet_dir,
+            TokenCoreConfig {
+                is_debug: true,
+                kdf_rounds: 1024,
+                ..TokenCoreConfig::default()
+            },
+        );
+        let wallet = token_core
+            .create_wallet(&HdStoreCreateParam {
+                password: TEST_PASSWORD.to_string(),
+                password_hint: "".to_string(),
+                name: "japanese".to_string(),
+                passphrase: "".to_string(),
+                language: "ja".to_string(),
+                word_count: 0,
+                entropy: "".to_string(),
+            })
+            .unwrap();
+        let mnemonic = token_core
+            .export_mnemonic(&wallet.id, TEST_PASSWORD)
+            .unwrap()
+            .value;
+        assert!(!mnemonic.is_ascii());
+
+        let mut import_param = HdStoreImportParam {
+            mnemonic,
+            password: TEST_PASSWORD.to_string(),
+            source: "MNEMONIC".to_string(),
+            name: "japanese".to_string(),
+            password_hint: "".to_string(),
+            overwrite: true,
+            passphrase: "".to_string(),
+            language: "en".to_string(),
+        };
+        let err = token_core.import_mnemonic(&import_param).err().unwrap();
+        assert_eq!(format!("{}", err), "mnemonic_language_mismatch");
+
+        import_param.language = "".to_string();
+        let imported = token_core.import_mnemonic(&import_param).unwrap();
+        assert_eq!(imported.id, wallet.id);
+
+        let err = token_core
+            .create_wallet(&HdStoreCreateParam {
+                password: TEST_PASSWORD.to_string(),
+                password_hint: "".to_string(),
+                name: "klingon".to_string(),
+                passphrase: "".to_string(),
+                language: "tlh".to_string(),
+                word_count: 0,
+                entropy: "".to_string(),
+            })
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "mnemonic_language_unsupported");
+    }
+
+    #[test]
+    fn test_create_wallet_with_word_count_and_entropy() {
+        let wallet_dir = "/tmp/imtoken/token_core_entropy_wallets";
+        let _ = fs::remove_dir_all(wallet_dir);
+        fs::create_dir_all(wallet_dir).unwrap();
+        let token_core = TokenCore::with_config(
+            wallet_dir,
+            TokenCoreConfig {
+                is_debug: true,
+                kdf_rounds: 1024,
+                ..TokenCoreConfig::default()
+            },
+        );
+        let mut param = HdStoreCreateParam {
+            password: TEST_PASSWORD.to_string(),
+            password_hint: "".to_string(),
+            name: "entropy".to_string(),
+            passphrase: "".to_string(),
+            language: "".to_string(),
+            word_count: 24,
+            entropy: "".to_string(),
+        };
+        let wallet = token_core.create_wallet(&param).unwrap();
+        let mnemonic = token_core
+            .export_mnemonic(&wallet.id, TEST_PASSWORD)
+            .unwrap()
+            .value;
+        assert_eq!(mnemonic.split(' ').count(), 24);
+
+        param.word_count = 13;
+        let err = token_core.create_wallet(&param).err().unwrap();
+        assert_eq!(format!("{}", err), "mnemonic_length_invalid");
+
+        param.word_count = 0;
+        param.entropy = "9e885d952ad362caeb4efe34a8e91bd2".to_string();
+        let wallet = token_core.create_wallet(&param).unwrap();
+        let mnemonic = token_core
+            .export_mnemonic(&wallet.id, TEST_PASSWORD)
+            .unwrap()
+            .value;
+        assert_eq!(
+            mnemonic,
+            "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic"
+        );
+
+        param.word_count = 24;
+        let err = token_core.create_wallet(&param).err().unwrap();
+        assert_eq!(format!("{}", err), "mnemonic_length_invalid");
+
+        param.word_count = 0;
+        param.entropy = "000102030405060708090a0b0c0d0e0f".to_string();
+        let err = token_core.create_wallet(&param).err().unwrap();
+        assert_eq!(format!("{}", err), "entropy_too_weak");
+
+        param.entropy = "dice".to_string();
+        let err = token_core.create_wallet(&param).err().unwrap();
+        assert_eq!(format!("{}", err), "entropy_invalid");
+    }
+
+    #[test]
+    fn test_mnemonic_quiz_and_candidates() {
+        let wallet_dir = "/tmp/imtoken/token_core_quiz_wallets";
+        let _ = fs::remove_dir_all(wallet_dir);
+        fs::create_dir_all(wallet_dir).unwrap();
+        let token_core = TokenCore::with_config(
+            wallet_dir,
+            TokenCoreConfig {
+                is_debug: true,
+                kdf_rounds: 1024,
+                ..TokenCoreConfig::default()
+            },
+        );
+        let wallet = token_core
+            .import_mnemonic(&HdStoreImportParam {
+                mnemonic: TEST_MNEMONIC.to_string(),
+                password: TEST_PASSWORD.to_string(),
+                source: "MNEMONIC".to_string(),
+                name: "quiz".to_string(),
+                password_hint: "".to_string(),
+                overwrite: true,
+                passphrase: "".to_string(),
+                language: "".to_string(),
+            })
+            .unwrap();
+
+        let word = |index: u32, word: &str| MnemonicWord {
+            index,
+            word: word.to_string(),
+        };
+        let mut param = MnemonicWordsVerifyParam {
+            id: wallet.id.to_string(),
+            password: TEST_PASSWORD.to_string(),
+            words: vec![word(1, "kidney"), word(7, "wife")],
+        };
+        assert!(token_core.verify_mnemonic_words(&param).unwrap());
+        param.words = vec![word(1, "kidney"), word(7, "horse")];
+        assert!(!token_core.verify_mnemonic_words(&param).unwrap());
+        param.password = "WrongPassword".to_string();
+        let err = token_core.verify_mnemonic_words(&param).err().unwrap();
+        assert_eq!(format!("{}", err), "password_incorrect");
+
+        let result = token_core
+            .mnemonic_candidates(&MnemonicCandidatesParam {
+                mnemonic: TEST_MNEMONIC.replace("shadow", "shad"),
+                language: "".to_string(),
+            })
+            .unwrap();
+        assert_eq!(result.candidates, vec![TEST_MNEMONIC.to_string()]);
+        let err = token_core
+            .mnemonic_candidates(&MnemonicCandidatesParam {
+                mnemonic: TEST_MNEMONIC.replace("shadow", "?"),
+                language: "ja".to_string(),
+            })
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "mnemonic_too_many_invalid_words");
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let wallet_dir = "/tmp/imtoken/token_core_verify_wallets";
+        let _ = fs::remove_dir_all(wallet_dir);
+        fs::create_dir_all(wallet_dir).unwrap();
+        let token_core = TokenCore::open(wallet_dir).unwrap();
+
+        let sk = TypedPrivateKey::from_slice(CurveType::SECP256k1, &[0x11u8; 32]).unwrap();
+        let hash = [0x22u8; 32];
+        let signature = sk.sign_recoverable(&hash).unwrap();
+        let coin_info = coin_info_from_param("TRON", "", "", "").unwrap();
+        let address = TrxAddress::from_public_key(&sk.public_key(), &coin_info).unwrap();
+
+        let mut param = VerifySignatureParam {
+            chain_type: "TRON".to_string(),
+            network: "".to_string(),
+            seg_wit: "".to_string(),
+            curve: "".to_string(),
+            address: "".to_string(),
+            public_key: hex::encode(sk.public_key().to_bytes()),
+            message: hex::encode(hash),
+            signature: hex::encode(&signature),
+        };
+        assert!(token_core.verify_signature(&param).unwrap());
+        param.message = hex::encode([0x33u8; 32]);
+        assert!(!token_core.verify_signature(&param).unwrap());
+
+        // the signer is recovered from the signature when only the address is known
+        param.message = hex::encode(hash);
+        param.public_key = "".to_string();
+        param.address = address.to_string();
+        assert!(token_core.verify_signature(&param).unwrap());
+        param.address = "TXo4VDm8Qc5YBSjPhu8pMaxzTApSvLshWG".to_string();
+        assert!(!token_core.verify_signature(&param).unwrap());
+
+        let sk = TypedPrivateKey::from_slice(CurveType::ED25519, &[0x11u8; 32]).unwrap();
+        let mut param = VerifySignatureParam {
+            chain_type: "TEZOS".to_string(),
+            network: "".to_string(),
+            seg_wit: "".to_string(),
+            curve: "".to_string(),
+            address: "".to_string(),
+            public_key: hex::encode(sk.public_key().to_bytes()),
+            message: hex::encode(b"hello tezos"),
+            signature: hex::encode(sk.sign(b"hello tezos").unwrap()),
+        };
+        assert!(token_core.verify_signature(&param).unwrap());
+        param.public_key = "".to_string();
+        param.address = "tz1dLEU3WfzCrDq2bvoEz4cfLP5wg4S7xNo9".to_string();
+        let err = token_core.verify_signature(&param).err().unwrap();
+        assert_eq!(format!("{}", err), "public_key_required");
+    }
+
+    #[test]
+    fn test_derive_sub_addresses() {
+        let wallet_dir = "/tmp/imtoken/token_core_sub_address_wallets";
+        let _ = fs::remove_dir_all(wallet_dir);
+        fs::create_dir_all(wallet_dir).unwrap();
+        let token_core = TokenCore::with_config(
+            wallet_dir,
+            TokenCoreConfig {
+                is_debug: true,
+                kdf_rounds: 1024,
+                ..TokenCoreConfig::default()
+            },
+        );
+        let wallet = token_core
+            .import_mnemonic(&HdStoreImportParam {
+                mnemonic: TEST_MNEMONIC.to_string(),
+                password: TEST_PASSWORD.to_string(),
+                source: "MNEMONIC".to_string(),
+                name: "sub-addresses".to_string(),
+                password_hint: "".to_string(),
+                overwrite: true,
+                passphrase: "".to_string(),
+                language: "".to_string(),
+            })
+            .unwrap();
+        let derivation = |chain_type: &str, path: &str| Derivation {
+            chain_type: chain_type.to_string(),
+            path: path.to_string(),
+            network: "".to_string(),
+            seg_wit: "".to_string(),
+            chain_id: "".to_string(),
+            curve: "".to_string(),
+        };
+        let accounts = token_core
+            .derive_accounts(&KeystoreCommonDeriveParam {
+                id: wallet.id.to_string(),
+                password: TEST_PASSWORD.to_string(),
+                derivations: vec![
+                    derivation("TRON", "m/44'/195'/0'/0/0"),
+                    derivation("KUSAMA", "//kusama//imToken/0"),
+                    derivation("TEZOS", "m/44'/1729'/0'/0'"),
+                ],
+            })
+            .unwrap()
+            .accounts;
+
+        let mut param = SubAddressesDeriveParam {
+            id: wallet.id.to_string(),
+            chain_type: "TRON".to_string(),
+            address: accounts[0].address.to_string(),
+            relative_paths: vec!["0/0".to_string(), "0/1".to_string()],
+        };
+        let result = token_core.derive_sub_addresses(&param).unwrap();
+        assert_eq!(result.addresses[0].path, "0/0");
+        assert_eq!(result.addresses[0].address, accounts[0].address);
+        assert_ne!(result.addresses[1].address, accounts[0].address);
+
+        param.chain_type = "KUSAMA".to_string();
+        param.address = accounts[1].address.to_string();
+        param.relative_paths = vec!["/0".to_string()];
+        let result = token_core.derive_sub_addresses(&param).unwrap();
+        assert_eq!(result.addresses[0].address, accounts[1].address);
+        assert_eq!(accounts[1].extended_xpub_key, "");
+
+        param.chain_type = "TEZOS".to_string();
+        param.address = accounts[2].address.to_string();
+        param.relative_paths = vec!["m/1'".to_string()];
+        let err = token_core.derive_sub_addresses(&param).err().unwrap();
+        assert_eq!(format!("{}", err), "can_not_derive_key");
+    }
+
+    #[test]
+    fn test_import_mnemonic_with_passphrase() {
+        let wallet_dir = "/tmp/imtoken/token_core_passphrase_wallets";
+        let _ = fs::remove_dir_all(wallet_dir);
+        fs::create_dir_all(wallet_dir).expect("shoud create filedir");
+        let token_core = TokenCore::with_config(
+            wallet_dir,
+            TokenCoreConfig {
+                is_debug: true,
+                kdf_rounds: 1024,
+                ..TokenCoreConfig::default()
+            },
+        );
+        let wallet = token_core
+            .import_mnemonic(&HdStoreImportParam {
+                mnemonic: TEST_MNEMONIC.to_string(),
+                password: TEST_PASSWORD.to_string(),
+                source: "MNEMONIC".to_string(),
+                name: "test-wallet".to_string(),
+                password_hint: "".to_string(),
+                overwrite: true,
+                passphrase: "TREZOR".to_string(),
+                language: "".to_string(),
+            })
+            .unwrap();
+
+        let accounts = token_core
+            .derive_accounts(&KeystoreCommonDeriveParam {
+                id: wallet.id.to_string(),
+                password: TEST_PASSWORD.to_string(),
+                derivations: vec![Derivation {
+                    chain_type: "TRON".to_string(),
+                    path: "m/44'/195'/0'/0/0".to_string(),
+                    network: "".to_string(),
+                    seg_wit: "".to_string(),
+                    chain_id: "".to_string(),
+                    curve: "".to_string(),
+                }],
+            })
+            .unwrap();
+        assert_ne!(
+            accounts.accounts[0].address,
+            "TLZnqkrSNLUWNrZMug8u9b6pJ3XcTGbzDV"
+        );
+
+        let mut exists_param = KeystoreCommonExistsParam {
+            r#type: KeyType::Mnemonic as i32,
+            value: TEST_MNEMONIC.to_string(),
+            encoding: "".to_string(),
+            passphrase: "".to_string(),
+        };
+        assert!(!token_core.exists(&exists_param).unwrap().is_exists);
+        exists_param.passphrase = "TREZOR".to_string();
+        assert_eq!(token_core.exists(&exists_param).unwrap().id, wallet.id);
+    }
+
+    #[test]
+    fn test_backup_and_restore_wallets() {
+        let src_dir = "/tmp/imtoken/token_core_backup_src_wallets";
+        let dst_dir = "/tmp/imtoken/token_core_backup_dst_wallets";
+        for dir in [src_dir, dst_dir].iter() {
+            let _ = fs::remove_dir_all(dir);
+            fs::create_dir_all(dir).expect("shoud create filedir");
+        }
+        let debug_config = TokenCoreConfig {
+            is_debug: true,
+            kdf_rounds: 1024,
+            ..TokenCoreConfig::default()
+        };
+        let src = TokenCore::with_config(src_dir, debug_config.clone());
+        let dst = TokenCore::with_config(dst_dir, debug_config);
+
+        let wallet = src
+            .import_mnemonic(&HdStoreImportParam {
+                mnemonic: TEST_MNEMONIC.to_string(),
+                password: TEST_PASSWORD.to_string(),
+                source: "MNEMONIC".to_string(),
+                name: "test-wallet".to_string(),
+                password_hint: "".to_string(),
+                overwrite: true,
+                passphrase: "".to_string(),
+                language: "".to_string(),
+            })
+            .unwrap();
+        let backup = src
+            .backup_wallets(&BackupWalletsParam {
+                password: "bundle password".to_string(),
+            })
+            .unwrap();
+        assert_eq!(backup.count, 1);
+
+        let mut restore_param = RestoreWalletsParam {
+            bundle: backup.bundle,
+            password: "wrong password".to_string(),
+            overwrite: false,
+        };
+        let err = dst.restore_wallets(&restore_param).err().unwrap();
+        assert_eq!(format!("{}", err), "password_incorrect");
+
+        restore_param.password = "bundle password".to_string();
+        let ret = dst.restore_wallets(&restore_param).unwrap();
+        assert_eq!(ret.restored.len(), 1);
+        assert_eq!(ret.restored[0].id, wallet.id);
+        assert!(ret.conflicts.is_empty());
+        // the restored keystore keeps its own password
+        dst.verify_password(&wallet.id, TEST_PASSWORD).unwrap();
+        assert!(Path::new(&format!("{}/{}.json", dst_dir, wallet.id)).exists());
+
+        let ret = dst.restore_wallets(&restore_param).unwrap();
+        assert!(ret.restored.is_empty());
+        assert_eq!(ret.conflicts[0].id, wallet.id);
+
+        restore_param.overwrite = true;
+        let ret = dst.restore_wallets(&restore_param).unwrap();
+        assert_eq!(ret.restored[0].id, wallet.id);
+        assert_eq!(dst.list_wallets().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_v3_keystore_import_export() {
+        let wallet_dir = "/tmp/imtoken/token_core_v3_keystore_wallets";
+        let _ = fs::remove_dir_all(wallet_dir);
+        fs::create_dir_all(wallet_dir).expect("shoud create filedir");
+        let token_core = TokenCore::with_config(
+            wallet_dir,
+            TokenCoreConfig {
+                is_debug: true,
+                kdf_rounds: 1024,
+                ..TokenCoreConfig::default()
+            },
+        );
+
+        let wallet = token_core
+            .import_private_key(&PrivateKeyStoreImportParam {
+                private_key: "a392604efc2fad9c0b3da43b5f698a2e3f270f170d859912be0d54742275c5f6"
+                    .to_string(),
+                password: TEST_PASSWORD.to_string(),
+                name: "v3".to_string(),
+                password_hint: "".to_string(),
+                overwrite: true,
+                encoding: "".to_string(),
+            })
+            .unwrap();
+
+        for kdf in ["", "pbkdf2"].iter() {
+            let exported = token_core
+                .export_v3_keystore(&V3KeystoreExportParam {
+                    id: wallet.id.to_string(),
+                    password: TEST_PASSWORD.to_string(),
+                    kdf: kdf.to_string(),
+                })
+                .unwrap();
+            let v3: serde_json::Value = serde_json::from_str(&exported.keystore).unwrap();
+            assert_eq!(v3["version"], 3);
+            assert_eq!(v3["address"], "ef678007d18427e6022059dbc264f27507cd1ffc");
+
+            let mut import_param = V3KeystoreImportParam {
+                keystore: exported.keystore,
+                password: TEST_PASSWORD.to_string(),
+                name: "v3".to_string(),
+                password_hint: "".to_string(),
+                overwrite: false,
+            };
+            let err = token_core.import_v3_keystore(&import_param).err().unwrap();
+            assert_eq!(format!("{}", err), "address_already_exist");
+
+            import_param.overwrite = true;
+            let imported = token_core.import_v3_keystore(&import_param).unwrap();
+            assert_eq!(imported.id, wallet.id);
+        }
+
+        let err = token_core
+            .export_v3_keystore(&V3KeystoreExportParam {
+                id: wallet.id.to_string(),
+                password: TEST_PASSWORD.to_string(),
+                kdf: "argon2".to_string(),
+            })
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "kdf_not_supported");
+
+        let hd_wallet = token_core
+            .import_mnemonic(&HdStoreImportParam {
+                mnemonic: TEST_MNEMONIC.to_string(),
+                password: TEST_PASSWORD.to_string(),
+                source: "MNEMONIC".to_string(),
+                name: "test-wallet".to_string(),
+                password_hint: "".to_string(),
+                overwrite: true,
+                passphrase: "".to_string(),
+                language: "".to_string(),
+            })
+            .unwrap();
+        let err = token_core
+            .export_v3_keystore(&V3KeystoreExportParam {
+                id: hd_wallet.id,
+                password: TEST_PASSWORD.to_string(),
+                kdf: "".to_string(),
+            })
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "hd_wallet_cannot_export_keystore");
+
+        // the password of an imported keystore isn't held to the policy of new passwords
+        let private_key =
+            hex::decode("a392604efc2fad9c0b3da43b5f698a2e3f270f170d859912be0d54742275c5f6")
+                .unwrap();
+        let v3_keystore =
+            V3Keystore::from_private_key(&private_key, "1234", SCryptParams::with_n(1024)).unwrap();
+        let imported = token_core
+            .import_v3_keystore(&V3KeystoreImportParam {
+                keystore: v3_keystore.to_json(),
+                password: "1234".to_string(),
+                name: "v3".to_string(),
+                password_hint: "".to_string(),
+                overwrite: true,
+            })
+            .unwrap();
+        assert_eq!(imported.id, wallet.id);
+    }
+
+    #[test]
+    fn test_slip39_shares_export_import() {
+        let wallet_dir = "/tmp/imtoken/token_core_slip39_wallets";
+        let _ = fs::remove_dir_all(wallet_dir);
+        fs::create_dir_all(wallet_dir).expect("shoud create filedir");
+        let token_core = TokenCore::with_config(
+            wallet_dir,
+            TokenCoreConfig {
+                is_debug: true,
+                kdf_rounds: 1024,
+                ..TokenCoreConfig::default()
+            },
+        );
+        let wallet = token_core
+            .import_mnemonic(&HdStoreImportParam {
+                mnemonic: TEST_MNEMONIC.to_string(),
+                password: TEST_PASSWORD.to_string(),
+                source: "MNEMONIC".to_string(),
+                name: "test-wallet".to_string(),
+                password_hint: "".to_string(),
+                overwrite: true,
+                passphrase: "".to_string(),
+                language: "".to_string(),
+            })
+            .unwrap();
+
+        let mut export_param = Slip39SharesExportParam {
+            id: wallet.id.to_string(),
+            password: TEST_PASSWORD.to_string(),
+            group_threshold: 1,
+            groups: vec![
+                Slip39Group {
+                    member_threshold: 2,
+                    member_count: 3,
+                },
+                Slip39Group {
+                    member_threshold: 1,
+                    member_count: 1,
+                },
+            ],
+            passphrase: "slip39 passphrase".to_string(),
+        };
+        let exported = token_core.export_slip39_shares(&export_param).unwrap();
+        assert_eq!(exported.groups.len(), 2);
+
+        let mut import_param = Slip39ImportParam {
+            shares: exported.groups[0].shares[..2].to_vec(),
+            passphrase: "slip39 passphrase".to_string(),
+            password: TEST_PASSWORD.to_string(),
+            name: "slip39".to_string(),
+            password_hint: "".to_string(),
+            overwrite: false,
+        };
+        let err = token_core
+            .import_slip39_shares(&import_param)
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "address_already_exist");
+
+        import_param.overwrite = true;
+        import_param.shares = exported.groups[1].shares.to_vec();
+        let imported = token_core.import_slip39_shares(&import_param).unwrap();
+        assert_eq!(imported.id, wallet.id);
+
+        export_param.group_threshold = 3;
+        let err = token_core
+            .export_slip39_shares(&export_param)
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "slip39_threshold_invalid");
+    }
+
+    #[test]
+    fn test_token_core_with_own_dir() {
+        let dir = "/tmp/imtoken/token_core_wallets";
+        let p = Path::new(dir);
+        if !p.exists() {
+            fs::create_dir_all(p).expect("shoud create filedir");
+        }
+
+        let token_core = TokenCore::open(dir).unwrap();
+        let wallet = token_core
+            .import_mnemonic(&HdStoreImportParam {
+                mnemonic: TEST_MNEMONIC.to_string(),
+                password: TEST_PASSWORD.to_string(),
+                source: "MNEMONIC".to_string(),
+                name: "test-wallet".to_string(),
+                password_hint: "imtoken".to_string(),
+                overwrite: true,
+                passphrase: "".to_string(),
+                language: "".to_string(),
+            })
+            .unwrap();
+        assert!(Path::new(&format!("{}/{}.json", dir, wallet.id)).exists());
+
+        let accounts = token_core
+            .derive_accounts(&KeystoreCommonDeriveParam {
+                id: wallet.id.to_string(),
+                password: TEST_PASSWORD.to_string(),
+                derivations: vec![Derivation {
+                    chain_type: "TRON".to_string(),
+                    path: "m/44'/195'/0'/0/0".to_string(),
+                    network: "".to_string(),
+                    seg_wit: "".to_string(),
+                    chain_id: "".to_string(),
+                    curve: "".to_string(),
+                }],
+            })
+            .unwrap();
+        let address = accounts.accounts[0].address.to_string();
+        assert_eq!("TLZnqkrSNLUWNrZMug8u9b6pJ3XcTGbzDV", address);
+
+        let reopened = TokenCore::open(dir).unwrap();
+        assert_eq!(
+            reopened.accounts(&wallet.id).unwrap().accounts[0].address,
+            address
+        );
+        let wallets = reopened.list_wallets().unwrap();
+        let listed = wallets.iter().find(|w| w.id == wallet.id).unwrap();
+        assert_eq!(listed.name, "test-wallet");
+        assert_eq!(listed.source, "MNEMONIC");
+        assert_eq!(listed.accounts[0].address, address);
+
+        let input = TronTxInput {
+            raw_data: "0a0202a22208e216e254e43ee10840c8cbe4e3df2d5a67080112630a2d747970652e676f6f676c65617069732e636f6d2f70726f746f636f6c2e5472616e73666572436f6e747261637412320a15415c68cc82c87446f602f019e5fd797437f5b79cc212154156a6076cd1537fa317c2606e4edfa4acd3e8e92e18a08d06709084e1e3df2d".to_string(),
+        };
+        let output: TronTxOutput = reopened
+            .sign_transaction(
+                &wallet.id,
+                &Key::Password(TEST_PASSWORD.to_string()),
+                "TRON",
+                &address,
+                &input,
+            )
+            .unwrap();
+        assert_eq!("bbf5ce0549490613a26c3ac4fc8574e748eabda05662b2e49cea818216b9da18691e78cd6379000e9c8a35c13dfbf620f269be90a078b58799b56dc20da3bdf200", output.signatures[0]);
+
+        let ret = reopened.delete_wallet(&wallet.id, "WRONG PASSWORD");
+        assert_eq!(format!("{}", ret.err().unwrap()), "password_incorrect");
+        reopened.delete_wallet(&wallet.id, TEST_PASSWORD).unwrap();
+        assert!(token_core.keystores().read().contains_key(&wallet.id));
+        assert!(!Path::new(&format!("{}/{}.json", dir, wallet.id)).exists());
+        assert_eq!(
+            format!("{}", token_core.accounts("not_exist").err().unwrap()),
+            "wallet_not_found"
+        );
+    }
+}